@@ -0,0 +1,117 @@
+/// Where the app keeps its files
+///
+/// Settings, teams, hotkeys, logs, previews and the other user files live in
+/// an `FMGoalMusic` folder in the platform config directory, and converted
+/// music in one in the data directory. In portable mode both are replaced by
+/// a single `FMGoalMusic` folder beside the executable, so the whole install
+/// can be carried on a USB stick. Portable mode is on when a `portable` (or
+/// `portable.txt`) file sits next to the executable, or when the app is
+/// started with `--portable`.
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+
+const APP_DIR: &str = "FMGoalMusic";
+const PORTABLE_MARKERS: [&str; 2] = ["portable", "portable.txt"];
+
+static PORTABLE_FLAG: AtomicBool = AtomicBool::new(false);
+static PORTABLE_ROOT: OnceLock<Option<PathBuf>> = OnceLock::new();
+
+/// Turn portable mode on, as `--portable` does
+///
+/// Must be called before any path is resolved.
+pub fn enable_portable_mode() {
+    PORTABLE_FLAG.store(true, Ordering::Relaxed);
+}
+
+fn executable_dir() -> Option<PathBuf> {
+    env::current_exe().ok()?.parent().map(Path::to_path_buf)
+}
+
+fn has_portable_marker(dir: &Path) -> bool {
+    PORTABLE_MARKERS
+        .iter()
+        .any(|marker| dir.join(marker).is_file())
+}
+
+/// Folder beside the executable used in portable mode, `None` otherwise
+fn portable_root() -> Option<&'static Path> {
+    PORTABLE_ROOT
+        .get_or_init(|| {
+            let exe_dir = executable_dir()?;
+            let portable = PORTABLE_FLAG.load(Ordering::Relaxed) || has_portable_marker(&exe_dir);
+            portable.then(|| exe_dir.join(APP_DIR))
+        })
+        .as_deref()
+}
+
+pub fn is_portable() -> bool {
+    portable_root().is_some()
+}
+
+/// App folder under `base`, or the portable folder if there is one
+fn resolve(portable_root: Option<&Path>, base: Option<PathBuf>) -> Option<PathBuf> {
+    match portable_root {
+        Some(root) => Some(root.to_path_buf()),
+        None => base.map(|base| base.join(APP_DIR)),
+    }
+}
+
+fn ensure(dir: Option<PathBuf>, kind: &str) -> io::Result<PathBuf> {
+    let dir = dir.ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Could not determine user {kind} directory"),
+        )
+    })?;
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// Folder holding config.json, teams.json, hotkeys.json and other settings
+pub fn config_dir() -> io::Result<PathBuf> {
+    ensure(resolve(portable_root(), dirs::config_dir()), "config")
+}
+
+/// Folder holding converted music
+pub fn data_dir() -> io::Result<PathBuf> {
+    ensure(resolve(portable_root(), dirs::data_dir()), "data")
+}
+
+/// Subfolder of `config_dir()`, such as `logs` or `previews`, created if missing
+pub fn config_subdir(name: &str) -> io::Result<PathBuf> {
+    ensure(Some(config_dir()?.join(name)), "config")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_prefers_portable_root() {
+        let portable = Path::new("/apps/fm");
+        assert_eq!(
+            resolve(Some(portable), Some(PathBuf::from("/home/u/.config"))),
+            Some(portable.to_path_buf())
+        );
+        assert_eq!(
+            resolve(None, Some(PathBuf::from("/home/u/.config"))),
+            Some(PathBuf::from("/home/u/.config/FMGoalMusic"))
+        );
+        assert_eq!(resolve(None, None), None);
+    }
+
+    #[test]
+    fn test_portable_marker() {
+        let dir = env::temp_dir().join(format!("fm_goal_musics_portable_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        assert!(!has_portable_marker(&dir));
+
+        fs::write(dir.join("portable.txt"), "").unwrap();
+        assert!(has_portable_marker(&dir));
+    }
+}
@@ -282,6 +282,11 @@ impl AudioManager {
         self.audio_data = data;
     }
 
+    /// Whether a sound is playing or queued
+    pub fn is_playing(&self) -> bool {
+        self.sink.lock().map(|sink| !sink.empty()).unwrap_or(false)
+    }
+
     /// Stop any currently playing audio and clear queued sounds
     pub fn stop(&self) {
         if let Ok(mut sink) = self.sink.lock() {
@@ -1,4 +1,4 @@
-use crate::config::Config;
+use crate::app_paths;
 use crate::slug::slugify;
 use parking_lot::Mutex;
 use std::fs::{self, File};
@@ -23,9 +23,7 @@ fn get_musics_dir() -> Result<PathBuf, Box<dyn std::error::Error>> {
     }
 
     // Use a user-writable data directory for storing converted music files
-    let base = dirs::data_dir().ok_or("Could not determine user data directory")?;
-    let app_dir = base.join("FMGoalMusic");
-    let musics_dir = app_dir.join("musics");
+    let musics_dir = app_paths::data_dir()?.join("musics");
     fs::create_dir_all(&musics_dir)?;
 
     *cache = Some(musics_dir.clone());
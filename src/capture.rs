@@ -189,12 +189,10 @@ impl CaptureManager {
             )
             .map_err(|e| -> Box<dyn std::error::Error> {
                 // Provide helpful error messages based on the error type
-                let error_msg = format!("{}", e);
-
                 #[cfg(target_os = "macos")]
-                if error_msg.contains("permission")
-                    || error_msg.contains("denied")
-                    || error_msg.contains("authorization")
+                if ["permission", "denied", "authorization"]
+                    .iter()
+                    .any(|word| e.to_string().contains(word))
                 {
                     return format!(
                         "Screen Recording permission denied.\n\
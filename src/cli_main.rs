@@ -0,0 +1,672 @@
+// Command-line diagnostics for support and CI
+//
+// Runs the same OCR, goal rules and team matching as the app on saved
+// frames, without a window or audio device, so it works on a headless box.
+// Settings, teams and phrases are read from the app's config folder and are
+// never modified.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use tracing_subscriber::EnvFilter;
+use xcap::Monitor;
+
+use fm_goal_musics::capture::{CaptureManager, CaptureRegion};
+use fm_goal_musics::config::Config;
+use fm_goal_musics::detection::i18n_loader;
+use fm_goal_musics::detection::{match_goal_rule, GoalRule, I18nPhrases};
+use fm_goal_musics::ocr::OcrManager;
+use fm_goal_musics::team_matcher::{TeamIdentifier, TeamMatch, TeamMatcher};
+use fm_goal_musics::teams::TeamDatabase;
+use fm_goal_musics::utils::{IterationTiming, LatencyReport, LatencyStats};
+use fm_goal_musics::{app_paths, audio_converter, detection, profiles};
+
+const USAGE: &str = "\
+Usage: fm-goal-musics-cli [--profile <name>] [--portable] <command>
+
+Commands:
+  ocr <image>          Print the text each preprocessing variant reads
+  detect <image|dir>   Run the goal rules and team matching on saved frames
+  monitors             List displays and their sizes
+  convert <audio>      Convert an audio file to WAV in the music library
+  check                Validate settings, teams, phrases and the OCR data
+  bench [<dir>]        Time capture, preprocessing, OCR and matching per frame
+      --frames <n>       Iterations to run (default: bench_frames from config)
+      --json <file>      Save the results to compare with another build
+      --compare <file>   Show the p95 change against saved results
+
+Options:
+  --profile <name>     Use the settings of a configuration profile
+  --portable           Use the portable folder beside the executable
+
+Set RUST_LOG=info to see the app's log output.";
+
+const FRAME_EXTENSIONS: [&str; 3] = ["png", "jpg", "jpeg"];
+
+#[derive(Debug, PartialEq)]
+struct Options {
+    profile: Option<String>,
+    portable: bool,
+    /// Command name followed by its arguments
+    command: Vec<String>,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options> {
+    let mut options = Options {
+        profile: None,
+        portable: false,
+        command: Vec::new(),
+    };
+    while let Some(arg) = args.next() {
+        if arg == "--profile" {
+            let name = args
+                .next()
+                .ok_or_else(|| anyhow!("--profile needs a profile name"))?;
+            options.profile = Some(name);
+        } else if let Some(name) = arg.strip_prefix("--profile=") {
+            options.profile = Some(name.to_string());
+        } else if arg == "--portable" {
+            options.portable = true;
+        } else {
+            options.command.push(arg);
+        }
+    }
+    Ok(options)
+}
+
+fn main() -> Result<()> {
+    let options = parse_args(std::env::args().skip(1))?;
+    // Must come first: every file path depends on it
+    if options.portable {
+        app_paths::enable_portable_mode();
+    }
+
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("warn"));
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr)
+        .with_target(false)
+        .init();
+
+    let profile = options.profile.as_deref();
+    match options
+        .command
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .as_slice()
+    {
+        ["ocr", image] => ocr_variants(Path::new(image), profile),
+        ["detect", target] => detect(Path::new(target), profile),
+        ["monitors"] => monitors(),
+        ["convert", audio] => convert(Path::new(audio)),
+        ["check"] => check(profile),
+        ["bench", args @ ..] => bench(args, profile),
+        [] | ["help"] | ["-h"] | ["--help"] => {
+            println!("{USAGE}");
+            Ok(())
+        }
+        _ => bail!("Unknown command '{}'\n\n{USAGE}", options.command.join(" ")),
+    }
+}
+
+/// Config file of `profile`, or of the profile the app used last
+fn config_file(profile: Option<&str>) -> Result<PathBuf> {
+    let name = match profile {
+        Some(name) => profiles::list()?
+            .into_iter()
+            .find(|existing| existing.eq_ignore_ascii_case(name.trim()))
+            .ok_or_else(|| anyhow!("Profile '{name}' doesn't exist"))?,
+        None => profiles::active(),
+    };
+    profiles::profile_path(&name)
+}
+
+/// Settings of `profile`, or the defaults if it was never saved
+fn load_config(profile: Option<&str>) -> Result<Config> {
+    let path = config_file(profile)?;
+    if !path.exists() {
+        return Ok(Config::default());
+    }
+    let content =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    Config::parse_read_only(&content)
+        .map_err(|err| anyhow!("{} could not be read: {err}", path.display()))
+}
+
+/// The user's team database, or the built-in one before the app created it
+fn load_teams() -> Result<TeamDatabase> {
+    let path = TeamDatabase::database_path().map_err(|err| anyhow!("{err}"))?;
+    if !path.exists() {
+        return TeamDatabase::load_embedded().map_err(|err| anyhow!("{err}"));
+    }
+    let content =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&content).with_context(|| format!("{} could not be read", path.display()))
+}
+
+fn new_ocr(config: &Config) -> Result<OcrManager> {
+    OcrManager::new_with_options(config.ocr_threshold, config.enable_morph_open)
+        .map_err(|err| anyhow!("Failed to initialize OCR: {err}"))
+}
+
+fn open_frame(path: &Path) -> Result<image::RgbaImage> {
+    Ok(image::open(path)
+        .with_context(|| format!("Failed to open image {}", path.display()))?
+        .to_rgba8())
+}
+
+/// OCR text on a single line
+fn one_line(text: &str) -> String {
+    let line = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if line.is_empty() {
+        "(no text)".to_string()
+    } else {
+        line
+    }
+}
+
+/// `target` itself, or the images in it sorted by name
+///
+/// Binarised copies saved beside recorded frames (`NNN_binary.png`) are
+/// skipped, since OCR would preprocess them a second time.
+fn frame_paths(target: &Path) -> Result<Vec<PathBuf>> {
+    if !target.is_dir() {
+        return Ok(vec![target.to_path_buf()]);
+    }
+    let mut frames: Vec<PathBuf> = fs::read_dir(target)
+        .with_context(|| format!("Failed to read {}", target.display()))?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .and_then(|ext| ext.to_str())
+                .map(|ext| FRAME_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+                .unwrap_or(false)
+        })
+        .filter(|path| {
+            !path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .is_some_and(|stem| stem.ends_with("_binary"))
+        })
+        .collect();
+    frames.sort();
+    Ok(frames)
+}
+
+fn ocr_variants(image_path: &Path, profile: Option<&str>) -> Result<()> {
+    let config = load_config(profile)?;
+    let image = open_frame(image_path)?;
+    let mut ocr = new_ocr(&config)?;
+    let texts = ocr
+        .get_text_per_variant(&image)
+        .map_err(|err| anyhow!("OCR failed: {err}"))?;
+
+    for (variant, text) in texts {
+        println!("{variant:>13}: {}", one_line(&text));
+    }
+    Ok(())
+}
+
+/// Goal rules and team matching of a profile, applied to OCR text
+struct FrameJudge {
+    phrases: I18nPhrases,
+    custom_phrases: Vec<String>,
+    identifier: TeamIdentifier,
+    monitored: Vec<(String, TeamMatcher)>,
+}
+
+/// What the detection loop would make of one frame
+struct Judgement {
+    rule: Option<GoalRule>,
+    club: Option<TeamMatch>,
+    /// Why music would play, `None` when the frame is ignored
+    play: Option<String>,
+}
+
+impl FrameJudge {
+    fn new(config: &Config) -> Result<Self> {
+        let teams = load_teams()?;
+        let monitored = config
+            .team_profiles
+            .iter()
+            .filter_map(|profile| {
+                match teams.find_team(&profile.team.league, &profile.team.team_key) {
+                    Some(team) => Some((
+                        profile.team.display_name.clone(),
+                        TeamMatcher::with_threshold(&team, config.team_match_threshold),
+                    )),
+                    None => {
+                        eprintln!(
+                            "Warning: {} is not in the team database, ignoring it",
+                            profile.team.display_name
+                        );
+                        None
+                    }
+                }
+            })
+            .collect();
+        Ok(Self {
+            phrases: I18nPhrases::new(config.selected_language),
+            custom_phrases: config.custom_goal_phrases.clone(),
+            identifier: TeamIdentifier::with_threshold(&teams, config.team_match_threshold),
+            monitored,
+        })
+    }
+
+    fn judge(&self, text: &str) -> Judgement {
+        let Some(rule) = match_goal_rule(text, &self.phrases, &self.custom_phrases) else {
+            return Judgement {
+                rule: None,
+                club: None,
+                play: None,
+            };
+        };
+
+        // Same choice as the detection loop: the best matching monitored team
+        let scorer = self
+            .monitored
+            .iter()
+            .filter(|(_, matcher)| matcher.matches(text))
+            .max_by(|a, b| a.1.score(text).total_cmp(&b.1.score(text)));
+        let play = match scorer {
+            _ if self.monitored.is_empty() => Some("no team selected".to_string()),
+            Some((name, _)) => Some(format!("for {name}")),
+            None => None,
+        };
+        Judgement {
+            rule: Some(rule),
+            club: self.identifier.identify(text),
+            play,
+        }
+    }
+}
+
+fn detect(target: &Path, profile: Option<&str>) -> Result<()> {
+    let config = load_config(profile)?;
+    let frames = frame_paths(target)?;
+    if frames.is_empty() {
+        bail!("No PNG or JPEG images in {}", target.display());
+    }
+    let judge = FrameJudge::new(&config)?;
+    let mut ocr = new_ocr(&config)?;
+
+    let (mut goals, mut plays) = (0, 0);
+    for frame in &frames {
+        let image = open_frame(frame)?;
+        let text = ocr
+            .get_text_traced(&image)
+            .map_err(|err| anyhow!("OCR failed on {}: {err}", frame.display()))?
+            .text;
+
+        println!("{}", frame.display());
+        println!("  text:     {}", one_line(&text));
+        let judgement = judge.judge(&text);
+        let Some(rule) = judgement.rule else {
+            println!("  decision: no goal");
+            continue;
+        };
+        goals += 1;
+        println!("  rule:     {rule}");
+        match judgement.club {
+            Some(found) => println!(
+                "  club:     {} ({}, score {:.2})",
+                found.team.display_name, found.league, found.score
+            ),
+            None => println!("  club:     not in the team database"),
+        }
+        match judgement.play {
+            Some(reason) => {
+                plays += 1;
+                println!("  decision: play ({reason})");
+            }
+            None => println!("  decision: ignore (goal for another team)"),
+        }
+    }
+
+    println!(
+        "\n{} frame(s): {goals} goal banner(s), {plays} would play music",
+        frames.len()
+    );
+    Ok(())
+}
+
+/// Benchmark run saved with `bench --json`
+#[derive(Serialize, Deserialize)]
+struct BenchmarkResult {
+    app_version: String,
+    /// "live region" or the frame folder
+    source: String,
+    report: LatencyReport,
+}
+
+/// Where benchmark frames come from
+enum FrameSource {
+    Live(CaptureManager),
+    /// Frames decoded up front, so disk reads don't count as capture time
+    Files(Vec<image::RgbaImage>),
+}
+
+fn bench(args: &[&str], profile: Option<&str>) -> Result<()> {
+    let (mut folder, mut iterations, mut json, mut compare) = (None, None, None, None);
+    let mut args = args.iter();
+    while let Some(&arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .copied()
+                .ok_or_else(|| anyhow!("{arg} needs a value"))
+        };
+        match arg {
+            "--frames" => {
+                iterations = Some(
+                    value()?
+                        .parse::<usize>()
+                        .context("--frames needs a number")?,
+                )
+            }
+            "--json" => json = Some(PathBuf::from(value()?)),
+            "--compare" => compare = Some(PathBuf::from(value()?)),
+            other if other.starts_with("--") => bail!("Unknown bench option '{other}'"),
+            other => folder = Some(PathBuf::from(other)),
+        }
+    }
+
+    let config = load_config(profile)?;
+    let iterations = iterations.unwrap_or(config.bench_frames).max(1);
+    let (mut source, source_name) = match &folder {
+        Some(folder) => {
+            let frames = frame_paths(folder)?
+                .iter()
+                .map(|path| open_frame(path))
+                .collect::<Result<Vec<_>>>()?;
+            if frames.is_empty() {
+                bail!("No PNG or JPEG images in {}", folder.display());
+            }
+            (FrameSource::Files(frames), folder.display().to_string())
+        }
+        None => {
+            let capture = CaptureManager::new(
+                CaptureRegion::from_array(config.capture_region),
+                config.selected_monitor_index,
+            )
+            .map_err(|err| anyhow!("Failed to start screen capture: {err}"))?;
+            (FrameSource::Live(capture), "live region".to_string())
+        }
+    };
+    let judge = FrameJudge::new(&config)?;
+    let mut ocr = new_ocr(&config)?;
+
+    eprintln!("Running {iterations} iterations on {source_name}...");
+    let mut stats = LatencyStats::with_capacity(iterations);
+    for index in 0..iterations {
+        let start = Instant::now();
+        let captured;
+        let image = match &mut source {
+            FrameSource::Live(capture) => {
+                captured = capture
+                    .capture_region()
+                    .map_err(|err| anyhow!("Capture failed: {err}"))?;
+                &captured
+            }
+            FrameSource::Files(frames) => &frames[index % frames.len()],
+        };
+        let capture_us = start.elapsed().as_secs_f64() * 1_000_000.0;
+
+        let trace = ocr
+            .get_text_traced(image)
+            .map_err(|err| anyhow!("OCR failed: {err}"))?;
+        let match_start = Instant::now();
+        std::hint::black_box(judge.judge(&trace.text));
+        let match_us = match_start.elapsed().as_secs_f64() * 1_000_000.0;
+
+        stats.add(IterationTiming {
+            capture_us,
+            preprocess_us: trace.preprocess_us,
+            ocr_us: trace.ocr_us,
+            match_us,
+            audio_trigger_us: 0.0,
+            total_us: start.elapsed().as_secs_f64() * 1_000_000.0,
+        });
+    }
+
+    for line in stats.report_lines() {
+        println!("{line}");
+    }
+    if folder.is_some() {
+        println!("Capture is not measured when frames come from files.");
+    }
+
+    let result = BenchmarkResult {
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        source: source_name,
+        report: stats.report(),
+    };
+    if let Some(path) = compare {
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let baseline: BenchmarkResult = serde_json::from_str(&content)
+            .with_context(|| format!("{} is not a benchmark result", path.display()))?;
+        print_comparison(&baseline, &result);
+    }
+    if let Some(path) = json {
+        fs::write(&path, serde_json::to_string_pretty(&result)?)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        println!("Saved results to {}", path.display());
+    }
+    Ok(())
+}
+
+/// p95 of each stage against an earlier run
+fn print_comparison(baseline: &BenchmarkResult, current: &BenchmarkResult) {
+    println!(
+        "\np95 compared with v{} ({}):",
+        baseline.app_version, baseline.source
+    );
+    let before = baseline
+        .report
+        .stages()
+        .into_iter()
+        .chain([("TOTAL", baseline.report.total)]);
+    let after = current
+        .report
+        .stages()
+        .into_iter()
+        .chain([("TOTAL", current.report.total)]);
+    for ((name, old), (_, new)) in before.zip(after) {
+        let (old_ms, new_ms) = (old.p95_us / 1000.0, new.p95_us / 1000.0);
+        let change = if old_ms > 0.0 {
+            format!("{:+.0}%", (new_ms - old_ms) / old_ms * 100.0)
+        } else {
+            "-".to_string()
+        };
+        println!("  {name:<13} {old_ms:>8.2} ms -> {new_ms:>8.2} ms  {change}");
+    }
+}
+
+fn monitors() -> Result<()> {
+    let monitors = Monitor::all().map_err(|err| anyhow!("Failed to enumerate monitors: {err}"))?;
+    if monitors.is_empty() {
+        println!("No displays found");
+    }
+    for (index, monitor) in monitors.iter().enumerate() {
+        println!(
+            "{index}: {}x{} at ({}, {}) {}{}",
+            monitor.width().unwrap_or(0),
+            monitor.height().unwrap_or(0),
+            monitor.x().unwrap_or(0),
+            monitor.y().unwrap_or(0),
+            monitor.name().unwrap_or_else(|_| "Unknown".to_string()),
+            if monitor.is_primary().unwrap_or(false) {
+                " (primary)"
+            } else {
+                ""
+            }
+        );
+    }
+    Ok(())
+}
+
+fn convert(audio: &Path) -> Result<()> {
+    let wav = audio_converter::convert_to_wav(audio)
+        .map_err(|err| anyhow!("Failed to convert {}: {err}", audio.display()))?;
+    println!("{}", wav.display());
+    Ok(())
+}
+
+/// Outcome of one `check` item
+enum Check {
+    Pass(String),
+    Warn(String),
+    Fail(String),
+}
+
+fn check(profile: Option<&str>) -> Result<()> {
+    let mut results: Vec<(&str, Check)> = Vec::new();
+
+    let config = match config_file(profile) {
+        Ok(path) if !path.exists() => {
+            results.push((
+                "config",
+                Check::Pass(format!(
+                    "{} not created yet, defaults apply",
+                    path.display()
+                )),
+            ));
+            Some(Config::default())
+        }
+        Ok(path) => match load_config(profile) {
+            Ok(config) => {
+                results.push(("config", Check::Pass(path.display().to_string())));
+                Some(config)
+            }
+            Err(err) => {
+                results.push(("config", Check::Fail(format!("{err:#}"))));
+                None
+            }
+        },
+        Err(err) => {
+            results.push(("config", Check::Fail(format!("{err:#}"))));
+            None
+        }
+    };
+
+    if let Some(config) = &config {
+        let [_, _, width, height] = config.capture_region;
+        if width == 0 || height == 0 {
+            results.push(("region", Check::Warn("capture region not set".to_string())));
+        }
+        for entry in &config.music_list {
+            if !Path::new(&entry.path).exists() {
+                results.push(("music", Check::Warn(format!("{} is missing", entry.path))));
+            }
+        }
+    }
+
+    let teams = match load_teams() {
+        Ok(teams) => {
+            results.push((
+                "teams",
+                Check::Pass(format!(
+                    "{} teams in {} leagues",
+                    teams.iter_teams().count(),
+                    teams.get_leagues().len()
+                )),
+            ));
+            Some(teams)
+        }
+        Err(err) => {
+            results.push(("teams", Check::Fail(format!("{err:#}"))));
+            None
+        }
+    };
+    if let (Some(config), Some(teams)) = (&config, &teams) {
+        for profile in &config.team_profiles {
+            if teams
+                .find_team(&profile.team.league, &profile.team.team_key)
+                .is_none()
+            {
+                results.push((
+                    "teams",
+                    Check::Warn(format!(
+                        "{} ({}) is not in the team database",
+                        profile.team.display_name, profile.team.league
+                    )),
+                ));
+            }
+        }
+    }
+
+    let language = config
+        .as_ref()
+        .map(|config| config.selected_language)
+        .unwrap_or(detection::Language::English);
+    let phrases = match i18n_loader::override_path(language).filter(|path| path.exists()) {
+        Some(path) => match fs::read_to_string(&path)
+            .map_err(anyhow::Error::from)
+            .and_then(|content| {
+                i18n_loader::parse_phrases(language, &content).map_err(anyhow::Error::from)
+            }) {
+            Ok(_) => Check::Pass(format!("{} (override)", path.display())),
+            Err(err) => Check::Fail(format!("{}: {err}", path.display())),
+        },
+        None => Check::Pass(format!("built-in {} phrases", language.name())),
+    };
+    results.push(("phrases", phrases));
+
+    results.push((
+        "tessdata",
+        match OcrManager::new_with_options(0, false) {
+            Ok(_) => Check::Pass("Tesseract initialised with English data".to_string()),
+            Err(err) => Check::Fail(err.to_string()),
+        },
+    ));
+
+    let mut failures = 0;
+    for (item, result) in &results {
+        let (mark, detail) = match result {
+            Check::Pass(detail) => ("ok  ", detail),
+            Check::Warn(detail) => ("warn", detail),
+            Check::Fail(detail) => {
+                failures += 1;
+                ("FAIL", detail)
+            }
+        };
+        println!("[{mark}] {item:<9} {detail}");
+    }
+    if failures > 0 {
+        bail!("{failures} check(s) failed");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> impl Iterator<Item = String> + '_ {
+        line.split(' ').map(str::to_string)
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            parse_args(args("--profile Laptop detect frames --portable")).unwrap(),
+            Options {
+                profile: Some("Laptop".to_string()),
+                portable: true,
+                command: vec!["detect".to_string(), "frames".to_string()],
+            }
+        );
+        assert_eq!(
+            parse_args(args("check --profile=Streaming"))
+                .unwrap()
+                .profile,
+            Some("Streaming".to_string())
+        );
+        assert!(parse_args(args("ocr --profile")).is_err());
+    }
+}
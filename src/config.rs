@@ -2,11 +2,15 @@ use display_info::DisplayInfo;
 use serde::{Deserialize, Serialize};
 use std::env;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 // Import Language for i18n support
 use crate::detection::i18n::Language;
 
+use crate::app_paths;
+use crate::config_migrations::{self, MigrationReport};
+use crate::persistence::{self, LoadProblem};
+
 fn default_music_volume() -> f32 {
     1.0
 }
@@ -50,9 +54,112 @@ pub struct SelectedTeam {
     pub display_name: String,
 }
 
+/// Celebration settings for one monitored team
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TeamProfile {
+    pub team: SelectedTeam,
+
+    /// Indices into `music_list` played when this team scores
+    #[serde(default)]
+    pub goal_music_indices: Vec<usize>,
+
+    /// Ambiance sound played with this team's goal music
+    #[serde(default)]
+    pub ambiance_path: Option<String>,
+
+    /// Music volume (0.0 to 1.0)
+    #[serde(default = "default_music_volume")]
+    pub music_volume: f32,
+
+    /// Ambiance volume (0.0 to 1.0)
+    #[serde(default = "default_ambiance_volume")]
+    pub ambiance_volume: f32,
+
+    /// Sound played when this team concedes in a detected match
+    #[serde(default)]
+    pub conceded_sound_path: Option<String>,
+
+    /// Clubs whose matches against this team are derbies
+    #[serde(default)]
+    pub rivals: Vec<SelectedTeam>,
+
+    /// Celebration used instead when the current opponent is a rival
+    #[serde(default)]
+    pub derby_celebration: Option<CelebrationOverride>,
+
+    /// Celebrations for special competitions, checked before the derby one
+    #[serde(default)]
+    pub competition_overrides: Vec<CompetitionOverride>,
+}
+
+impl TeamProfile {
+    /// A profile with no rivals or special celebrations
+    pub fn new(
+        team: SelectedTeam,
+        goal_music_indices: Vec<usize>,
+        ambiance_path: Option<String>,
+        music_volume: f32,
+        ambiance_volume: f32,
+    ) -> Self {
+        Self {
+            team,
+            goal_music_indices,
+            ambiance_path,
+            music_volume,
+            ambiance_volume,
+            conceded_sound_path: None,
+            rivals: Vec::new(),
+            derby_celebration: None,
+            competition_overrides: Vec::new(),
+        }
+    }
+
+    /// Whether this profile belongs to `team`
+    pub fn is_for(&self, team: &SelectedTeam) -> bool {
+        self.team.league == team.league && self.team.team_key == team.team_key
+    }
+
+    /// Whether `team` is one of this team's rivals
+    pub fn is_rival(&self, team: &SelectedTeam) -> bool {
+        self.rivals
+            .iter()
+            .any(|rival| rival.league == team.league && rival.team_key == team.team_key)
+    }
+}
+
+/// Alternate playlist and ambiance for special matches
+///
+/// Empty fields fall back to the team's regular celebration.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct CelebrationOverride {
+    /// Indices into `music_list` played instead of the team's playlist
+    #[serde(default)]
+    pub goal_music_indices: Vec<usize>,
+
+    /// Ambiance sound played instead of the team's
+    #[serde(default)]
+    pub ambiance_path: Option<String>,
+}
+
+/// Celebration for matches in a competition, e.g. a cup final
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CompetitionOverride {
+    /// Text identifying the competition in `competition_region`, such as
+    /// "Champions League"; matched ignoring case and accents
+    pub competition: String,
+
+    #[serde(flatten)]
+    pub celebration: CelebrationOverride,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
+    /// Layout version of this file, see `config_migrations`
+    #[serde(default)]
+    pub schema_version: u32,
+
     /// Screen region to capture [x, y, width, height]
+    #[serde(default = "default_capture_region")]
     pub capture_region: [u32; 4],
 
     /// Binary threshold for OCR preprocessing (0-255)
@@ -74,7 +181,8 @@ pub struct Config {
     /// Index of the selected music file
     pub selected_music_index: Option<usize>,
 
-    /// Indices of music files selected for the goal playlist
+    /// Indices of music files selected for the goal playlist used when no
+    /// team is monitored; monitored teams have their own in `team_profiles`
     #[serde(default)]
     pub goal_music_indices: Vec<usize>,
 
@@ -102,9 +210,9 @@ pub struct Config {
     #[serde(default = "default_ambiance_length")]
     pub ambiance_length_ms: u64,
 
-    /// Selected team for conditional audio playback
+    /// Monitored teams, each with its own celebration
     #[serde(default)]
-    pub selected_team: Option<SelectedTeam>,
+    pub team_profiles: Vec<TeamProfile>,
 
     /// Automatically check for updates on startup
     #[serde(default = "default_auto_check_updates")]
@@ -125,6 +233,23 @@ pub struct Config {
     /// Custom goal detection phrases added by the user
     #[serde(default)]
     pub custom_goal_phrases: Vec<String>,
+
+    /// Minimum similarity (0.0-1.0) for a detected name to match a team
+    #[serde(default = "default_team_match_threshold")]
+    pub team_match_threshold: f32,
+
+    /// Seconds of detection history kept in memory by the flight recorder
+    #[serde(default = "default_flight_recorder_seconds")]
+    pub flight_recorder_seconds: u64,
+
+    /// Dump the flight recorder to disk automatically on every goal trigger
+    #[serde(default)]
+    pub dump_recorder_on_trigger: bool,
+
+    /// Screen region showing the competition name [x, y, width, height],
+    /// read for competition celebrations
+    #[serde(default)]
+    pub competition_region: Option<[u32; 4]>,
 }
 
 fn default_ambiance_enabled() -> bool {
@@ -147,9 +272,18 @@ fn default_selected_language() -> Language {
     Language::English
 }
 
+fn default_team_match_threshold() -> f32 {
+    crate::team_matcher::DEFAULT_MATCH_THRESHOLD
+}
+
+fn default_flight_recorder_seconds() -> u64 {
+    10
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
+            schema_version: config_migrations::CURRENT_SCHEMA_VERSION,
             capture_region: default_capture_region(),
             ocr_threshold: 0, // 0 = automatic Otsu thresholding, or set 1-255 for manual
             debounce_ms: 8000, // 8 seconds between goal sounds
@@ -164,36 +298,60 @@ impl Default for Config {
             ambiance_volume: default_ambiance_volume(),
             music_length_ms: default_music_length(),
             ambiance_length_ms: default_ambiance_length(),
-            selected_team: None,
+            team_profiles: Vec::new(),
             auto_check_updates: default_auto_check_updates(),
             skipped_version: None,
             selected_monitor_index: 0, // Primary monitor by default
             selected_language: default_selected_language(),
             custom_goal_phrases: Vec::new(),
+            team_match_threshold: default_team_match_threshold(),
+            flight_recorder_seconds: default_flight_recorder_seconds(),
+            dump_recorder_on_trigger: false,
+            competition_region: None,
         }
     }
 }
 
 impl Config {
-    /// Load configuration from the platform-specific config directory.
+    /// Load configuration from `path`, normally config.json in the
+    /// platform-specific config directory or a named profile beside it.
     /// Creates default config if file doesn't exist.
-    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
-        let config_path = Self::config_path()?;
-
-        if config_path.exists() {
-            let content = fs::read_to_string(&config_path)?;
-            let mut config: Config = serde_json::from_str(&content)?;
-
-            if config.capture_region == [0, 0, 200, 100] {
-                config.capture_region = default_capture_region();
-                config.save()?;
-                tracing::info!("✓ Migrated capture region to screen-based default");
+    ///
+    /// A file that doesn't parse is replaced by its newest readable backup,
+    /// or by the defaults if there is none, instead of failing.
+    ///
+    /// # Returns
+    /// The config and, if the file was unreadable, what went wrong
+    pub fn load_from(
+        path: &Path,
+    ) -> Result<(Self, Option<LoadProblem>), Box<dyn std::error::Error>> {
+        // Parsing stays free of side effects while backups are tried, so
+        // nothing is written until recovery is over
+        let recovered = persistence::load_with_recovery(path, |content| {
+            Self::parse_migrating(content).map(|(config, report)| {
+                (config, report.map(|report| (report, content.to_string())))
+            })
+        })?;
+        if let Some(recovered) = recovered {
+            let (config, migration) = recovered.value.unwrap_or_default();
+            if let Some((report, original)) = &migration {
+                // A config recovered from a backup already has that backup
+                // kept in the rotation; only the file itself is copied
+                if recovered.problem.is_none() {
+                    Self::keep_pre_migration_copy(path, original, report)?;
+                }
+            }
+            if migration.is_some() || recovered.problem.is_some() {
+                // Upgrade or replace the broken file so the next start is clean
+                config.save_to(path)?;
             }
 
-            tracing::info!("✓ Loaded config from: {}", config_path.display());
-            Ok(config)
-        } else {
-            // Try migrating from legacy location (next to executable)
+            tracing::info!("✓ Loaded config from: {}", path.display());
+            return Ok((config, recovered.problem));
+        }
+
+        // Try migrating from legacy location (next to executable)
+        if path == Self::config_path()? {
             let legacy_path = (|| -> Result<PathBuf, Box<dyn std::error::Error>> {
                 let exe_path = env::current_exe()?;
                 let exe_dir = exe_path
@@ -205,52 +363,102 @@ impl Config {
             if let Ok(legacy) = legacy_path {
                 if legacy.exists() {
                     if let Ok(content) = fs::read_to_string(&legacy) {
-                        if let Ok(config) = serde_json::from_str::<Config>(&content) {
+                        if let Ok((config, report)) = Self::parse_migrating(&content) {
+                            if let Some(report) = report {
+                                Self::keep_pre_migration_copy(&legacy, &content, &report)?;
+                            }
                             // Save migrated config to new path
-                            config.save()?;
+                            config.save_to(path)?;
                             tracing::info!(
                                 "✓ Migrated config from legacy path: {}",
                                 legacy.display()
                             );
-                            tracing::info!("✓ New config at: {}", config_path.display());
-                            return Ok(config);
+                            tracing::info!("✓ New config at: {}", path.display());
+                            return Ok((config, None));
                         }
                     }
                 }
             }
-
-            // Create default config
-            let config = Config::default();
-            config.save()?;
-            tracing::info!("✓ Created default config at: {}", config_path.display());
-            tracing::info!("  Edit this file to customize settings.");
-            Ok(config)
         }
+
+        // Create default config
+        let config = Config::default();
+        config.save_to(path)?;
+        tracing::info!("✓ Created default config at: {}", path.display());
+        tracing::info!("  Edit this file to customize settings.");
+        Ok((config, None))
+    }
+
+    /// Parse config JSON, upgrading an older schema in memory only, for
+    /// diagnostics and outside edits that must not touch the user's files
+    pub fn parse_read_only(content: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(Self::parse_migrating(content)?.0)
     }
 
-    /// Save configuration to disk
-    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
-        let config_path = Self::config_path()?;
+    /// Parse config JSON, upgrading it in memory first if it was written by
+    /// an older schema
+    ///
+    /// # Returns
+    /// The config and, if it was upgraded, what the migration changed
+    fn parse_migrating(
+        content: &str,
+    ) -> Result<(Self, Option<MigrationReport>), Box<dyn std::error::Error>> {
+        let mut raw: serde_json::Value = serde_json::from_str(content)?;
+        let version = config_migrations::schema_version(&raw);
+        if version > config_migrations::CURRENT_SCHEMA_VERSION {
+            tracing::warn!(
+                "Config schema v{} is newer than this build (v{}); unknown settings will be dropped on save",
+                version,
+                config_migrations::CURRENT_SCHEMA_VERSION
+            );
+        }
+
+        let report = config_migrations::migrate(&mut raw)?;
+        let config: Config = serde_json::from_value(raw)?;
+        Ok((config, report))
+    }
 
-        // Ensure parent directory exists
-        if let Some(parent) = config_path.parent() {
-            fs::create_dir_all(parent)?;
+    /// Keep `original`, the contents of `source` before `report`'s migration,
+    /// as `<name>.v<version>.bak` beside it
+    fn keep_pre_migration_copy(
+        source: &Path,
+        original: &str,
+        report: &MigrationReport,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let backup = Self::backup_path(source, report.from_version);
+        persistence::write_atomic(&backup, original.as_bytes())?;
+        for step in &report.applied {
+            tracing::info!("✓ Config migration: {}", step);
         }
+        tracing::info!(
+            "✓ Migrated config from schema v{} to v{} (backup at {})",
+            report.from_version,
+            report.to_version,
+            backup.display()
+        );
+        Ok(())
+    }
 
+    /// Where the pre-migration copy of `source` is kept
+    fn backup_path(source: &Path, version: u32) -> PathBuf {
+        let name = source
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "config.json".to_string());
+        source.with_file_name(format!("{}.v{}.bak", name, version))
+    }
+
+    /// Save configuration to `path`
+    pub fn save_to(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
         let json = serde_json::to_string_pretty(self)?;
-        fs::write(&config_path, json)?;
+        persistence::write_with_backups(path, json.as_bytes())?;
 
         Ok(())
     }
 
     /// Get the config file path in a user-writable config directory
     pub fn config_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
-        let base = dirs::config_dir().ok_or("Could not determine user config directory")?;
-        // Application-specific folder
-        let app_dir = base.join("FMGoalMusic");
-        // Ensure directory exists
-        fs::create_dir_all(&app_dir)?;
-        Ok(app_dir.join("config.json"))
+        Ok(app_paths::config_dir()?.join("config.json"))
     }
 
     /// Get the config directory path (for display purposes)
@@ -284,4 +492,114 @@ mod tests {
         assert_eq!(config.capture_region, deserialized.capture_region);
         assert_eq!(config.ocr_threshold, deserialized.ocr_threshold);
     }
+
+    #[test]
+    fn test_unversioned_config_loads_through_migrations() {
+        let json = r#"{
+            "capture_region": [0, 0, 200, 100],
+            "ocr_threshold": 0,
+            "debounce_ms": 8000,
+            "enable_morph_open": false,
+            "bench_frames": 500,
+            "selected_team": {"league": "Süper Lig", "team_key": "galatasaray", "display_name": "Galatasaray"},
+            "goal_music_indices": [1]
+        }"#;
+        let mut raw: serde_json::Value = serde_json::from_str(json).unwrap();
+        config_migrations::migrate(&mut raw).unwrap();
+        let config: Config = serde_json::from_value(raw).unwrap();
+
+        assert_eq!(
+            config.schema_version,
+            config_migrations::CURRENT_SCHEMA_VERSION
+        );
+        assert_eq!(config.capture_region, default_capture_region());
+        assert_eq!(config.team_profiles.len(), 1);
+        assert_eq!(config.team_profiles[0].goal_music_indices, vec![1]);
+    }
+
+    #[test]
+    fn test_backup_path_keeps_original_name() {
+        let backup = Config::backup_path(Path::new("/tmp/FMGoalMusic/config.json"), 0);
+        assert_eq!(backup, Path::new("/tmp/FMGoalMusic/config.json.v0.bak"));
+    }
+
+    #[test]
+    fn test_team_profile_defaults() {
+        let json = r#"{"team": {"league": "Süper Lig", "team_key": "galatasaray", "display_name": "Galatasaray"}}"#;
+        let profile: TeamProfile = serde_json::from_str(json).unwrap();
+
+        assert!(profile.goal_music_indices.is_empty());
+        assert_eq!(profile.music_volume, default_music_volume());
+        assert_eq!(profile.ambiance_volume, default_ambiance_volume());
+        assert!(profile.is_for(&profile.team.clone()));
+        assert!(profile.rivals.is_empty());
+        assert!(profile.derby_celebration.is_none());
+    }
+
+    #[test]
+    fn test_competition_override_round_trip() {
+        let json = r#"{"competition": "Champions League", "goal_music_indices": [2], "ambiance_path": "anthem.wav"}"#;
+        let cup: CompetitionOverride = serde_json::from_str(json).unwrap();
+        assert_eq!(cup.competition, "Champions League");
+        assert_eq!(cup.celebration.goal_music_indices, vec![2]);
+        assert_eq!(cup.celebration.ambiance_path.as_deref(), Some("anthem.wav"));
+
+        let serialized = serde_json::to_string(&cup).unwrap();
+        let deserialized: CompetitionOverride = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, cup);
+    }
+
+    const UNVERSIONED: &str = r#"{"ocr_threshold": 0, "debounce_ms": 5000, "enable_morph_open": false, "bench_frames": 500}"#;
+
+    fn temp_config(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "fm_goal_musics_config_{}_{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir.join("config.json")
+    }
+
+    #[test]
+    fn test_load_from_migrates_and_keeps_original() {
+        let path = temp_config("migrate");
+        fs::write(&path, UNVERSIONED).unwrap();
+
+        let (config, problem) = Config::load_from(&path).unwrap();
+        assert!(problem.is_none());
+        assert_eq!(config.debounce_ms, 5000);
+        assert_eq!(
+            fs::read_to_string(Config::backup_path(&path, 0)).unwrap(),
+            UNVERSIONED
+        );
+        let saved: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(
+            config_migrations::schema_version(&saved),
+            config_migrations::CURRENT_SCHEMA_VERSION
+        );
+    }
+
+    #[test]
+    fn test_recovering_an_old_backup_keeps_the_migration_copy() {
+        let path = temp_config("recover_old");
+        fs::write(Config::backup_path(&path, 0), "original").unwrap();
+        fs::write(&persistence::backup_paths(&path)[0], UNVERSIONED).unwrap();
+        fs::write(&path, "{\"truncat").unwrap();
+
+        let (config, problem) = Config::load_from(&path).unwrap();
+        assert_eq!(config.debounce_ms, 5000);
+        assert!(problem.unwrap().recovered_from.is_some());
+        assert_eq!(
+            fs::read_to_string(Config::backup_path(&path, 0)).unwrap(),
+            "original"
+        );
+        // The backup the config came from is still among the backups
+        assert_eq!(
+            fs::read_to_string(&persistence::backup_paths(&path)[1]).unwrap(),
+            UNVERSIONED
+        );
+    }
 }
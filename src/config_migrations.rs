@@ -0,0 +1,220 @@
+/// Upgrades for config.json written by older versions
+///
+/// Every config carries a `schema_version`. Files below the current version
+/// go through the migrations after their version, in order, as raw JSON, so
+/// a migration can move or reshape fields that the current `Config` no
+/// longer knows. Fields a migration leaves out fall back to their serde
+/// defaults. Configs from before versioning count as version 0.
+use serde_json::{json, Map, Value};
+
+/// Schema version written by this build
+pub const CURRENT_SCHEMA_VERSION: u32 = 2;
+
+/// Placeholder region the first releases saved before screen-based defaults
+const PLACEHOLDER_CAPTURE_REGION: [u64; 4] = [0, 0, 200, 100];
+
+/// One step of the chain, upgrading a config to version `to`
+struct Migration {
+    to: u32,
+    description: &'static str,
+    apply: fn(&mut Map<String, Value>),
+}
+
+/// Every migration, oldest first; append new ones with the next version
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        to: 1,
+        description: "replace the placeholder capture region with the screen-based default",
+        apply: drop_placeholder_capture_region,
+    },
+    Migration {
+        to: 2,
+        description: "turn the selected team into a team profile",
+        apply: selected_team_to_profile,
+    },
+];
+
+/// What `migrate` changed
+#[derive(Debug, Clone, PartialEq)]
+pub struct MigrationReport {
+    pub from_version: u32,
+    pub to_version: u32,
+    /// Descriptions of the migrations applied, in order
+    pub applied: Vec<&'static str>,
+}
+
+/// Version a raw config was written with
+pub fn schema_version(config: &Value) -> u32 {
+    config
+        .get("schema_version")
+        .and_then(Value::as_u64)
+        .map(|version| version.min(u32::MAX as u64) as u32)
+        .unwrap_or(0)
+}
+
+/// Bring a raw config up to `CURRENT_SCHEMA_VERSION`
+///
+/// # Returns
+/// The migrations applied, or `None` when the config is already current
+/// (or newer, in which case it is left untouched)
+pub fn migrate(config: &mut Value) -> Result<Option<MigrationReport>, String> {
+    let from_version = schema_version(config);
+    if from_version >= CURRENT_SCHEMA_VERSION {
+        return Ok(None);
+    }
+
+    let fields = config
+        .as_object_mut()
+        .ok_or("config.json does not contain a JSON object")?;
+    let mut applied = Vec::new();
+    for migration in MIGRATIONS.iter().filter(|m| m.to > from_version) {
+        (migration.apply)(fields);
+        applied.push(migration.description);
+    }
+    fields.insert("schema_version".to_string(), json!(CURRENT_SCHEMA_VERSION));
+
+    Ok(Some(MigrationReport {
+        from_version,
+        to_version: CURRENT_SCHEMA_VERSION,
+        applied,
+    }))
+}
+
+/// v1: drop the old `[0, 0, 200, 100]` placeholder so the default applies
+fn drop_placeholder_capture_region(config: &mut Map<String, Value>) {
+    let is_placeholder = config
+        .get("capture_region")
+        .and_then(Value::as_array)
+        .map(|region| {
+            region
+                .iter()
+                .map(Value::as_u64)
+                .eq(PLACEHOLDER_CAPTURE_REGION.iter().map(|&v| Some(v)))
+        })
+        .unwrap_or(false);
+    if is_placeholder {
+        config.remove("capture_region");
+    }
+}
+
+/// v2: the single `selected_team` becomes the first of `team_profiles`,
+/// taking a copy of the global playlist, ambiance and volumes with it
+fn selected_team_to_profile(config: &mut Map<String, Value>) {
+    let Some(team) = config.remove("selected_team") else {
+        return;
+    };
+    let has_profiles = config
+        .get("team_profiles")
+        .and_then(Value::as_array)
+        .map(|profiles| !profiles.is_empty())
+        .unwrap_or(false);
+    if !team.is_object() || has_profiles {
+        return;
+    }
+
+    let mut profile = Map::new();
+    profile.insert("team".to_string(), team);
+    for (from, to) in [
+        ("goal_music_indices", "goal_music_indices"),
+        ("goal_ambiance_path", "ambiance_path"),
+        ("music_volume", "music_volume"),
+        ("ambiance_volume", "ambiance_volume"),
+    ] {
+        if let Some(value) = config.get(from) {
+            profile.insert(to.to_string(), value.clone());
+        }
+    }
+    config.insert(
+        "team_profiles".to_string(),
+        Value::Array(vec![Value::Object(profile)]),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrations_are_ordered() {
+        let versions: Vec<u32> = MIGRATIONS.iter().map(|m| m.to).collect();
+        let expected: Vec<u32> = (1..=CURRENT_SCHEMA_VERSION).collect();
+        assert_eq!(versions, expected);
+    }
+
+    #[test]
+    fn test_unversioned_config_runs_every_migration() {
+        let mut config = json!({ "capture_region": [0, 0, 200, 100], "ocr_threshold": 0 });
+        let report = migrate(&mut config).unwrap().unwrap();
+
+        assert_eq!(report.from_version, 0);
+        assert_eq!(report.to_version, CURRENT_SCHEMA_VERSION);
+        assert_eq!(report.applied.len(), MIGRATIONS.len());
+        assert_eq!(schema_version(&config), CURRENT_SCHEMA_VERSION);
+        assert_eq!(config["ocr_threshold"], 0);
+    }
+
+    #[test]
+    fn test_current_and_newer_configs_are_untouched() {
+        let mut current = json!({ "schema_version": CURRENT_SCHEMA_VERSION });
+        assert!(migrate(&mut current).unwrap().is_none());
+
+        let mut newer = json!({ "schema_version": CURRENT_SCHEMA_VERSION + 1, "x": 1 });
+        let before = newer.clone();
+        assert!(migrate(&mut newer).unwrap().is_none());
+        assert_eq!(newer, before);
+    }
+
+    #[test]
+    fn test_non_object_config_is_rejected() {
+        assert!(migrate(&mut json!([1, 2, 3])).is_err());
+    }
+
+    #[test]
+    fn test_v1_drops_placeholder_capture_region() {
+        let mut placeholder = json!({ "capture_region": [0, 0, 200, 100] });
+        drop_placeholder_capture_region(placeholder.as_object_mut().unwrap());
+        assert!(placeholder.get("capture_region").is_none());
+
+        let mut custom = json!({ "capture_region": [0, 900, 1024, 50] });
+        drop_placeholder_capture_region(custom.as_object_mut().unwrap());
+        assert_eq!(custom["capture_region"], json!([0, 900, 1024, 50]));
+    }
+
+    #[test]
+    fn test_v2_moves_selected_team_into_profile() {
+        let mut config = json!({
+            "selected_team": { "league": "Süper Lig", "team_key": "galatasaray", "display_name": "Galatasaray" },
+            "goal_music_indices": [0, 2],
+            "goal_ambiance_path": "crowd.wav",
+            "music_volume": 0.8
+        });
+        selected_team_to_profile(config.as_object_mut().unwrap());
+
+        assert!(config.get("selected_team").is_none());
+        let profiles = config["team_profiles"].as_array().unwrap();
+        assert_eq!(profiles.len(), 1);
+        assert_eq!(profiles[0]["team"]["team_key"], "galatasaray");
+        assert_eq!(profiles[0]["goal_music_indices"], json!([0, 2]));
+        assert_eq!(profiles[0]["ambiance_path"], "crowd.wav");
+        assert_eq!(profiles[0]["music_volume"], 0.8);
+        // Missing settings are left to the profile's defaults
+        assert!(profiles[0].get("ambiance_volume").is_none());
+    }
+
+    #[test]
+    fn test_v2_keeps_existing_profiles_and_empty_selection() {
+        let mut with_profiles = json!({
+            "selected_team": { "league": "L", "team_key": "a", "display_name": "A" },
+            "team_profiles": [{ "team": { "league": "L", "team_key": "b", "display_name": "B" } }]
+        });
+        let profiles = with_profiles["team_profiles"].clone();
+        selected_team_to_profile(with_profiles.as_object_mut().unwrap());
+        assert_eq!(with_profiles["team_profiles"], profiles);
+        assert!(with_profiles.get("selected_team").is_none());
+
+        let mut no_team = json!({ "selected_team": null });
+        selected_team_to_profile(no_team.as_object_mut().unwrap());
+        assert!(no_team.get("team_profiles").is_none());
+        assert!(no_team.get("selected_team").is_none());
+    }
+}
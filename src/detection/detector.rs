@@ -38,9 +38,9 @@ pub struct DetectionContext {
     pub text: String,
     /// Detection timestamp
     pub timestamp: Instant,
-    /// Configured home team name (if any)
+    /// Home team of the current match (if known)
     pub home_team: Option<String>,
-    /// Configured away team name (if any)
+    /// Away team of the current match (if known)
     pub away_team: Option<String>,
 }
 
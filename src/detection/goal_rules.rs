@@ -0,0 +1,100 @@
+/// Order in which OCR text is recognised as a goal banner
+///
+/// The detection loop and the command-line diagnostics both go through
+/// `match_goal_rule`, so a frame is judged the same way in either.
+use std::fmt;
+
+use super::i18n::I18nPhrases;
+use crate::ocr::text_extraction;
+
+/// Rule that recognised a goal banner
+#[derive(Debug, Clone, PartialEq)]
+pub enum GoalRule {
+    /// A goal phrase of the selected language
+    LanguagePhrase(String),
+    /// One of the user's custom goal phrases
+    CustomPhrase(String),
+    /// The built-in "GOAL FOR" / "GOL" pattern
+    DefaultPattern,
+}
+
+impl fmt::Display for GoalRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GoalRule::LanguagePhrase(phrase) => write!(f, "language phrase '{phrase}'"),
+            GoalRule::CustomPhrase(phrase) => write!(f, "custom phrase '{phrase}'"),
+            GoalRule::DefaultPattern => write!(f, "default GOAL FOR/GOL pattern"),
+        }
+    }
+}
+
+/// First rule that recognises `text` as a goal banner
+///
+/// Rules are tried in this order:
+/// 1. Goal phrases of the selected language
+/// 2. Custom goal phrases
+/// 3. The default "GOAL FOR" / "GOL" fallback
+pub fn match_goal_rule(
+    text: &str,
+    phrases: &I18nPhrases,
+    custom_phrases: &[String],
+) -> Option<GoalRule> {
+    if text.trim().is_empty() {
+        return None;
+    }
+
+    let normalized = text.to_lowercase();
+    let find = |candidates: &[String]| {
+        candidates
+            .iter()
+            .find(|phrase| {
+                let phrase = phrase.to_lowercase();
+                !phrase.is_empty() && normalized.contains(&phrase)
+            })
+            .cloned()
+    };
+
+    if let Some(phrase) = find(&phrases.goal_phrases) {
+        return Some(GoalRule::LanguagePhrase(phrase));
+    }
+    if let Some(phrase) = find(custom_phrases) {
+        return Some(GoalRule::CustomPhrase(phrase));
+    }
+    text_extraction::contains_goal_text(text).then_some(GoalRule::DefaultPattern)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detection::i18n::Language;
+
+    #[test]
+    fn test_language_phrases_come_first() {
+        let phrases = I18nPhrases::new_hardcoded(Language::Turkish);
+        let custom = vec!["gooool".to_string()];
+
+        assert!(matches!(
+            match_goal_rule("GOL! GALATASARAY", &phrases, &custom),
+            Some(GoalRule::LanguagePhrase(_))
+        ));
+        assert_eq!(
+            match_goal_rule("Gooool Ajax", &phrases, &custom),
+            Some(GoalRule::CustomPhrase("gooool".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_default_pattern_and_no_goal() {
+        let phrases = I18nPhrases {
+            goal_phrases: Vec::new(),
+            ..I18nPhrases::new_hardcoded(Language::English)
+        };
+
+        assert_eq!(
+            match_goal_rule("GOAL FOR ARSENAL", &phrases, &[]),
+            Some(GoalRule::DefaultPattern)
+        );
+        assert_eq!(match_goal_rule("KICK OFF", &phrases, &[]), None);
+        assert_eq!(match_goal_rule("   ", &phrases, &[String::new()]), None);
+    }
+}
@@ -1,14 +1,17 @@
 /// I18n phrase loader from embedded JSON files
 ///
-/// Loads detection phrases from embedded JSON assets.
+/// Loads detection phrases from embedded JSON assets. A file with the same
+/// layout at `i18n/<code>.json` in the config directory overrides the
+/// embedded phrases of that language.
 use super::i18n::{I18nPhrases, Language};
+use crate::app_paths;
 use serde::Deserialize;
+use std::fs;
+use std::path::PathBuf;
 
 /// I18n JSON structure
 #[derive(Debug, Deserialize)]
 struct I18nJson {
-    language: String,
-    code: String,
     detection: DetectionPhrases,
 }
 
@@ -29,8 +32,36 @@ const DE_JSON: &str = include_str!("../../assets/i18n/de.json");
 const IT_JSON: &str = include_str!("../../assets/i18n/it.json");
 const PT_JSON: &str = include_str!("../../assets/i18n/pt.json");
 
-/// Load I18n phrases from embedded JSON
+/// User file overriding the embedded phrases of `language`
+pub fn override_path(language: Language) -> Option<PathBuf> {
+    app_paths::config_dir()
+        .ok()
+        .map(|dir| dir.join("i18n").join(format!("{}.json", language.code())))
+}
+
+/// Parse a phrase file in the layout of `assets/i18n/*.json`
+pub fn parse_phrases(language: Language, json: &str) -> Result<I18nPhrases, serde_json::Error> {
+    let i18n_json: I18nJson = serde_json::from_str(json)?;
+
+    Ok(I18nPhrases {
+        language,
+        goal_phrases: i18n_json.detection.goal_phrases,
+        kickoff_phrases: i18n_json.detection.kickoff_phrases,
+        match_end_phrases: i18n_json.detection.match_end_phrases,
+    })
+}
+
+/// Load I18n phrases from the user's override file or embedded JSON
 pub fn load_phrases(language: Language) -> Result<I18nPhrases, Box<dyn std::error::Error>> {
+    if let Some(path) = override_path(language) {
+        if let Ok(content) = fs::read_to_string(&path) {
+            match parse_phrases(language, &content) {
+                Ok(phrases) => return Ok(phrases),
+                Err(err) => tracing::warn!("Ignoring {}: {}", path.display(), err),
+            }
+        }
+    }
+
     let json_str = match language {
         Language::English => EN_JSON,
         Language::Turkish => TR_JSON,
@@ -41,14 +72,7 @@ pub fn load_phrases(language: Language) -> Result<I18nPhrases, Box<dyn std::erro
         Language::Portuguese => PT_JSON,
     };
 
-    let i18n_json: I18nJson = serde_json::from_str(json_str)?;
-
-    Ok(I18nPhrases {
-        language,
-        goal_phrases: i18n_json.detection.goal_phrases,
-        kickoff_phrases: i18n_json.detection.kickoff_phrases,
-        match_end_phrases: i18n_json.detection.match_end_phrases,
-    })
+    Ok(parse_phrases(language, json_str)?)
 }
 
 #[cfg(test)]
@@ -64,6 +88,17 @@ mod tests {
         assert!(!phrases.match_end_phrases.is_empty());
     }
 
+    #[test]
+    fn test_parse_override_file() {
+        let json = r#"{"language": "English", "code": "en", "detection": {
+            "goal_phrases": ["GOAL!!"], "kickoff_phrases": [], "match_end_phrases": []}}"#;
+        let phrases = parse_phrases(Language::English, json).unwrap();
+        assert_eq!(phrases.goal_phrases, vec!["GOAL!!".to_string()]);
+
+        let err = parse_phrases(Language::English, "{\"language\": ").unwrap_err();
+        assert_eq!(err.line(), 1);
+    }
+
     #[test]
     fn test_load_turkish() {
         let phrases = load_phrases(Language::Turkish).unwrap();
@@ -39,6 +39,7 @@
 /// ```
 pub mod detector;
 pub mod goal_detector;
+pub mod goal_rules;
 pub mod i18n;
 pub mod i18n_loader;
 pub mod kickoff_detector;
@@ -49,6 +50,7 @@ pub mod match_end_detector;
 // Re-export commonly used types
 pub use detector::{DetectionContext, DetectionResult, Detector};
 pub use goal_detector::GoalDetector;
+pub use goal_rules::{match_goal_rule, GoalRule};
 pub use i18n::{I18nPhrases, Language};
 pub use i18n_loader::load_phrases;
 pub use kickoff_detector::KickoffDetector;
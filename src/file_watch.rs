@@ -0,0 +1,105 @@
+/// Polling watcher for settings files edited outside the app
+///
+/// Compares each file's modification time and size with what was seen on
+/// the previous poll. Polling a handful of small files once a second is
+/// cheap and behaves the same on every platform and file system.
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// What identifies a version of a file; `None` when the file doesn't exist
+type Fingerprint = Option<(Option<SystemTime>, u64)>;
+
+fn fingerprint(path: &Path) -> Fingerprint {
+    let metadata = fs::metadata(path).ok()?;
+    Some((metadata.modified().ok(), metadata.len()))
+}
+
+#[derive(Debug, Default)]
+pub struct FileWatcher {
+    files: BTreeMap<PathBuf, Fingerprint>,
+}
+
+impl FileWatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start watching `path`; its current version doesn't count as a change
+    pub fn watch(&mut self, path: &Path) {
+        if !self.files.contains_key(path) {
+            self.files.insert(path.to_path_buf(), fingerprint(path));
+        }
+    }
+
+    /// Stop watching every file not in `keep`
+    pub fn retain(&mut self, keep: &[PathBuf]) {
+        self.files.retain(|path, _| keep.contains(path));
+    }
+
+    /// Files created, modified or deleted since the last call
+    pub fn changed(&mut self) -> Vec<PathBuf> {
+        let mut changed = Vec::new();
+        for (path, seen) in self.files.iter_mut() {
+            let current = fingerprint(path);
+            if current != *seen {
+                *seen = current;
+                changed.push(path.clone());
+            }
+        }
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "fm_goal_musics_watch_{}_{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_reports_each_change_once() {
+        let dir = temp_dir("changes");
+        let path = dir.join("teams.json");
+        fs::write(&path, "{}").unwrap();
+
+        let mut watcher = FileWatcher::new();
+        watcher.watch(&path);
+        assert!(watcher.changed().is_empty());
+
+        // Different size, so the change shows even on coarse mtime clocks
+        std::thread::sleep(Duration::from_millis(10));
+        fs::write(&path, "{\"Premier League\": {}}").unwrap();
+        assert_eq!(watcher.changed(), vec![path.clone()]);
+        assert!(watcher.changed().is_empty());
+
+        fs::remove_file(&path).unwrap();
+        assert_eq!(watcher.changed(), vec![path]);
+    }
+
+    #[test]
+    fn test_watches_files_created_later() {
+        let dir = temp_dir("created");
+        let path = dir.join("en.json");
+
+        let mut watcher = FileWatcher::new();
+        watcher.watch(&path);
+        fs::write(&path, "{}").unwrap();
+        assert_eq!(watcher.changed(), vec![path.clone()]);
+
+        watcher.retain(&[]);
+        fs::write(&path, "{ }").unwrap();
+        assert!(watcher.changed().is_empty());
+    }
+}
@@ -0,0 +1,353 @@
+use std::collections::VecDeque;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, Context, Result};
+use image::{GrayImage, RgbaImage};
+use serde::Serialize;
+
+use crate::app_paths;
+use crate::utils::{file_timestamp, IterationTiming};
+
+/// Upper bound on memory held by recorded images (128 MB)
+const MAX_RECORDED_BYTES: usize = 128 * 1024 * 1024;
+
+/// What the detection loop decided for a frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum FrameDecision {
+    /// OCR returned no text
+    NoText,
+    /// Text was read but no goal phrase matched
+    NoGoal,
+    /// A goal phrase matched but no monitored team did
+    TeamMismatch,
+    /// A goal was detected but suppressed by the debouncer
+    Debounced,
+    /// Goal music (or a conceded sound) was triggered
+    Triggered,
+}
+
+/// Everything the detection loop knew about one iteration
+#[derive(Clone)]
+pub struct RecordedFrame {
+    pub captured_at: SystemTime,
+    pub capture: RgbaImage,
+    pub binary: Option<GrayImage>,
+    pub text: String,
+    pub timing: IterationTiming,
+    pub decision: FrameDecision,
+    recorded_at: Instant,
+}
+
+impl RecordedFrame {
+    pub fn new(
+        capture: RgbaImage,
+        binary: Option<GrayImage>,
+        text: String,
+        timing: IterationTiming,
+        decision: FrameDecision,
+    ) -> Self {
+        Self {
+            captured_at: SystemTime::now(),
+            capture,
+            binary,
+            text,
+            timing,
+            decision,
+            recorded_at: Instant::now(),
+        }
+    }
+
+    fn size_bytes(&self) -> usize {
+        self.capture.as_raw().len() + self.binary.as_ref().map_or(0, |b| b.as_raw().len())
+    }
+}
+
+/// Rolling in-memory buffer of the last few seconds of detection frames
+///
+/// Frames older than the configured window are dropped, as are the oldest
+/// frames once the buffer exceeds its memory budget.
+pub struct FlightRecorder {
+    frames: VecDeque<RecordedFrame>,
+    window: Duration,
+    bytes: usize,
+}
+
+impl FlightRecorder {
+    /// Create a recorder keeping the last `window_secs` seconds of frames
+    pub fn new(window_secs: u64) -> Self {
+        Self {
+            frames: VecDeque::new(),
+            window: Duration::from_secs(window_secs.max(1)),
+            bytes: 0,
+        }
+    }
+
+    /// Add a frame, evicting frames that fall outside the window or budget
+    pub fn record(&mut self, frame: RecordedFrame) {
+        self.bytes += frame.size_bytes();
+        let now = frame.recorded_at;
+        self.frames.push_back(frame);
+
+        while let Some(oldest) = self.frames.front() {
+            let expired = now.duration_since(oldest.recorded_at) > self.window;
+            let over_budget = self.bytes > MAX_RECORDED_BYTES && self.frames.len() > 1;
+            if !expired && !over_budget {
+                break;
+            }
+            if let Some(removed) = self.frames.pop_front() {
+                self.bytes -= removed.size_bytes();
+            }
+        }
+    }
+
+    /// Copy of the frames currently held, oldest first
+    pub fn snapshot(&self) -> Vec<RecordedFrame> {
+        self.frames.iter().cloned().collect()
+    }
+
+    /// Copy of the frames recorded during the last `window`, oldest first
+    pub fn recent(&self, window: Duration) -> Vec<RecordedFrame> {
+        let now = Instant::now();
+        self.frames
+            .iter()
+            .filter(|frame| now.duration_since(frame.recorded_at) <= window)
+            .cloned()
+            .collect()
+    }
+
+    /// Frames leading up to (and including) the most recent trigger
+    ///
+    /// # Returns
+    /// `None` if no frame in the buffer triggered goal music
+    pub fn around_last_trigger(&self, before: Duration) -> Option<Vec<RecordedFrame>> {
+        let trigger_index = self
+            .frames
+            .iter()
+            .rposition(|frame| frame.decision == FrameDecision::Triggered)?;
+        let trigger_at = self.frames[trigger_index].recorded_at;
+
+        Some(
+            self.frames
+                .iter()
+                .take(trigger_index + 1)
+                .filter(|frame| trigger_at.duration_since(frame.recorded_at) <= before)
+                .cloned()
+                .collect(),
+        )
+    }
+
+    #[allow(dead_code)]
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+}
+
+#[derive(Serialize)]
+struct FrameSummary<'a> {
+    index: usize,
+    captured_at_ms: u128,
+    decision: FrameDecision,
+    text: &'a str,
+    capture_ms: f64,
+    preprocess_ms: f64,
+    ocr_ms: f64,
+    match_ms: f64,
+    audio_trigger_ms: f64,
+    total_ms: f64,
+    capture_file: String,
+    binary_file: Option<String>,
+}
+
+#[derive(Serialize)]
+struct DumpSummary<'a> {
+    reason: &'a str,
+    app_version: &'static str,
+    frames: Vec<FrameSummary<'a>>,
+}
+
+/// Directory holding all flight recorder dumps
+pub fn dumps_dir() -> Result<PathBuf> {
+    Ok(app_paths::config_dir()?.join("flight_recorder"))
+}
+
+/// Write frames to a new timestamped folder under `dumps_dir()`
+///
+/// # Returns
+/// The folder the dump was written to
+pub fn dump_frames(frames: &[RecordedFrame], reason: &str) -> Result<PathBuf> {
+    if frames.is_empty() {
+        return Err(anyhow!("Flight recorder is empty"));
+    }
+
+    let dir = unique_timestamped_dir(&dumps_dir()?);
+    write_frames(&dir, frames, reason)?;
+
+    tracing::info!(
+        "[flight-recorder] Dumped {} frames ({}) to {}",
+        frames.len(),
+        reason,
+        dir.display()
+    );
+
+    Ok(dir)
+}
+
+/// Write frames into `dir`, creating it if needed
+///
+/// Each frame is saved as `NNN_capture.png` (and `NNN_binary.png` when
+/// available); OCR text, timings and decisions go to `frames.json`.
+pub fn write_frames(dir: &Path, frames: &[RecordedFrame], reason: &str) -> Result<()> {
+    fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create dump folder {}", dir.display()))?;
+
+    let mut summaries = Vec::with_capacity(frames.len());
+    for (index, frame) in frames.iter().enumerate() {
+        let capture_file = format!("{index:03}_capture.png");
+        frame
+            .capture
+            .save(dir.join(&capture_file))
+            .with_context(|| format!("Failed to save {capture_file}"))?;
+
+        let binary_file = match &frame.binary {
+            Some(binary) => {
+                let name = format!("{index:03}_binary.png");
+                binary
+                    .save(dir.join(&name))
+                    .with_context(|| format!("Failed to save {name}"))?;
+                Some(name)
+            }
+            None => None,
+        };
+
+        summaries.push(FrameSummary {
+            index,
+            captured_at_ms: frame
+                .captured_at
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_millis())
+                .unwrap_or(0),
+            decision: frame.decision,
+            text: &frame.text,
+            capture_ms: frame.timing.capture_us / 1000.0,
+            preprocess_ms: frame.timing.preprocess_us / 1000.0,
+            ocr_ms: frame.timing.ocr_us / 1000.0,
+            match_ms: frame.timing.match_us / 1000.0,
+            audio_trigger_ms: frame.timing.audio_trigger_us / 1000.0,
+            total_ms: frame.timing.total_ms(),
+            capture_file,
+            binary_file,
+        });
+    }
+
+    let summary = DumpSummary {
+        reason,
+        app_version: env!("CARGO_PKG_VERSION"),
+        frames: summaries,
+    };
+    let json = serde_json::to_string_pretty(&summary)?;
+    fs::write(dir.join("frames.json"), json).context("Failed to write frames.json")?;
+
+    Ok(())
+}
+
+/// A not-yet-existing folder under `base` named after the current time
+pub fn unique_timestamped_dir(base: &Path) -> PathBuf {
+    let stamp = file_timestamp(SystemTime::now());
+    let mut dir = base.join(&stamp);
+    let mut suffix = 1;
+    while dir.exists() {
+        dir = base.join(format!("{stamp}-{suffix}"));
+        suffix += 1;
+    }
+    dir
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(width: u32, height: u32) -> RecordedFrame {
+        RecordedFrame::new(
+            RgbaImage::new(width, height),
+            None,
+            String::new(),
+            IterationTiming::new(),
+            FrameDecision::NoText,
+        )
+    }
+
+    #[test]
+    fn test_recorder_keeps_recent_frames() {
+        let mut recorder = FlightRecorder::new(10);
+        for _ in 0..5 {
+            recorder.record(frame(4, 4));
+        }
+        assert_eq!(recorder.len(), 5);
+        assert_eq!(recorder.snapshot().len(), 5);
+    }
+
+    #[test]
+    fn test_recorder_drops_expired_frames() {
+        let mut recorder = FlightRecorder::new(1);
+        let mut old = frame(4, 4);
+        old.recorded_at = Instant::now() - Duration::from_secs(5);
+        recorder.record(old);
+        recorder.record(frame(4, 4));
+        assert_eq!(recorder.len(), 1);
+    }
+
+    #[test]
+    fn test_recorder_respects_memory_budget() {
+        let mut recorder = FlightRecorder::new(60);
+        // 4096 x 4096 RGBA = 64 MB per frame
+        for _ in 0..3 {
+            recorder.record(frame(4096, 4096));
+        }
+        assert_eq!(recorder.len(), 2);
+    }
+
+    #[test]
+    fn test_around_last_trigger() {
+        let mut recorder = FlightRecorder::new(60);
+        assert!(recorder
+            .around_last_trigger(Duration::from_secs(3))
+            .is_none());
+
+        let mut old = frame(4, 4);
+        old.recorded_at = Instant::now() - Duration::from_secs(10);
+        recorder.record(old);
+        recorder.record(frame(4, 4));
+        let mut trigger = frame(4, 4);
+        trigger.decision = FrameDecision::Triggered;
+        recorder.record(trigger);
+        recorder.record(frame(4, 4));
+
+        let frames = recorder
+            .around_last_trigger(Duration::from_secs(3))
+            .unwrap();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[1].decision, FrameDecision::Triggered);
+    }
+
+    #[test]
+    fn test_recent_frames() {
+        let mut recorder = FlightRecorder::new(60);
+        let mut old = frame(4, 4);
+        old.recorded_at = Instant::now() - Duration::from_secs(10);
+        recorder.record(old);
+        recorder.record(frame(4, 4));
+        assert_eq!(recorder.recent(Duration::from_secs(5)).len(), 1);
+    }
+
+    #[test]
+    fn test_dump_empty_recorder_fails() {
+        assert!(dump_frames(&[], "test").is_err());
+    }
+}
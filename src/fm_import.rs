@@ -0,0 +1,435 @@
+/// Import teams from Football Manager "Print Screen" exports
+///
+/// FM can print any club or competition view to a text file (a `|`
+/// delimited table) or a web page (an HTML table). The first row holds the
+/// column headers; the club name column is required, short name,
+/// competition and nation columns are used when present. Name variations
+/// are generated so OCR matches the names skins actually show.
+use std::collections::BTreeMap;
+
+use anyhow::{bail, Result};
+use regex::Regex;
+
+use crate::slug::slugify;
+use crate::teams::{Team, TeamDatabase};
+
+/// League used when a row has neither a competition nor a nation
+pub const DEFAULT_IMPORT_LEAGUE: &str = "Imported";
+
+/// Club prefixes/suffixes that skins and banners often leave out
+const CLUB_AFFIXES: &[&str] = &[
+    "FC", "AFC", "CF", "SC", "SK", "FK", "AC", "AS", "SV", "BK", "IF", "CD", "SD", "KV", "1.",
+];
+
+/// Word abbreviations commonly used on scoreboards
+const WORD_ABBREVIATIONS: &[(&str, &str)] = &[
+    ("united", "Utd"),
+    ("manchester", "Man"),
+    ("athletic", "Ath"),
+    ("saint", "St"),
+    ("wanderers", "W"),
+    ("wednesday", "Wed"),
+    ("rovers", "R"),
+];
+
+/// A club row read from an FM export
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedClub {
+    pub name: String,
+    pub short_name: Option<String>,
+    pub competition: Option<String>,
+    pub nation: Option<String>,
+}
+
+impl ImportedClub {
+    /// League to file the club under: competition, else nation
+    pub fn league(&self) -> &str {
+        self.competition
+            .as_deref()
+            .or(self.nation.as_deref())
+            .unwrap_or(DEFAULT_IMPORT_LEAGUE)
+    }
+}
+
+/// Outcome of importing an FM export; entries are "League / Team"
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FmImportReport {
+    pub added: Vec<String>,
+    /// Clubs already in the database, left untouched
+    pub skipped: Vec<String>,
+}
+
+impl FmImportReport {
+    /// One-line summary for status messages
+    pub fn summary(&self) -> String {
+        format!(
+            "{} added, {} already present",
+            self.added.len(),
+            self.skipped.len()
+        )
+    }
+}
+
+/// Column positions found in the header row
+struct Columns {
+    name: usize,
+    short_name: Option<usize>,
+    competition: Option<usize>,
+    nation: Option<usize>,
+}
+
+impl Columns {
+    fn from_header(header: &[String]) -> Option<Self> {
+        let find = |names: &[&str]| {
+            header
+                .iter()
+                .position(|cell| names.contains(&cell.trim().to_lowercase().as_str()))
+        };
+        Some(Self {
+            name: find(&["club", "name", "team", "club name", "team name"])?,
+            short_name: find(&["short name", "short", "abbreviation"]),
+            competition: find(&["competition", "division", "league", "comp"]),
+            nation: find(&["nation", "country", "nat"]),
+        })
+    }
+
+    fn club(&self, row: &[String]) -> Option<ImportedClub> {
+        let cell = |index: Option<usize>| {
+            index
+                .and_then(|i| row.get(i))
+                .map(|value| value.trim())
+                .filter(|value| !value.is_empty() && *value != "-")
+                .map(str::to_string)
+        };
+        Some(ImportedClub {
+            name: cell(Some(self.name))?,
+            short_name: cell(self.short_name),
+            competition: cell(self.competition),
+            nation: cell(self.nation),
+        })
+    }
+}
+
+/// Parse an FM text or HTML export into club rows
+pub fn parse_export(content: &str) -> Result<Vec<ImportedClub>> {
+    let rows = if content.to_lowercase().contains("<table") {
+        html_rows(content)
+    } else {
+        text_rows(content)
+    };
+
+    let mut rows = rows.into_iter();
+    let columns = rows
+        .by_ref()
+        .find_map(|row| Columns::from_header(&row))
+        .ok_or_else(|| anyhow::anyhow!("No club name column found in export"))?;
+
+    let clubs: Vec<ImportedClub> = rows.filter_map(|row| columns.club(&row)).collect();
+    if clubs.is_empty() {
+        bail!("Export contains no clubs");
+    }
+    Ok(clubs)
+}
+
+/// Decode an export file's bytes (UTF-8, with or without BOM, or UTF-16 LE)
+pub fn decode_export(bytes: &[u8]) -> String {
+    if let Some(rest) = bytes.strip_prefix(&[0xFF, 0xFE]) {
+        let units: Vec<u16> = rest
+            .chunks_exact(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+            .collect();
+        return String::from_utf16_lossy(&units);
+    }
+    let bytes = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]).unwrap_or(bytes);
+    String::from_utf8_lossy(bytes).into_owned()
+}
+
+fn text_rows(content: &str) -> Vec<Vec<String>> {
+    content
+        .lines()
+        .filter(|line| {
+            line.chars()
+                .any(|c| !matches!(c, '|' | '-' | '+' | '=' | ' ' | '\t'))
+        })
+        .filter_map(|line| {
+            let separator = if line.contains('|') { '|' } else { '\t' };
+            let mut cells: Vec<String> = line
+                .split(separator)
+                .map(|cell| cell.trim().to_string())
+                .collect();
+            // Drop the empty cells produced by leading/trailing pipes
+            if cells.last().map(|c| c.is_empty()).unwrap_or(false) {
+                cells.pop();
+            }
+            if cells.first().map(|c| c.is_empty()).unwrap_or(false) {
+                cells.remove(0);
+            }
+            if cells.len() > 1 {
+                Some(cells)
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+fn html_rows(content: &str) -> Vec<Vec<String>> {
+    let row_re = Regex::new(r"(?is)<tr[^>]*>(.*?)</tr>").expect("valid row regex");
+    let cell_re = Regex::new(r"(?is)<t[hd][^>]*>(.*?)</t[hd]>").expect("valid cell regex");
+    let tag_re = Regex::new(r"(?s)<[^>]+>").expect("valid tag regex");
+
+    row_re
+        .captures_iter(content)
+        .map(|row| {
+            cell_re
+                .captures_iter(&row[1])
+                .map(|cell| decode_entities(tag_re.replace_all(&cell[1], " ").trim()))
+                .map(|cell| cell.split_whitespace().collect::<Vec<_>>().join(" "))
+                .collect::<Vec<_>>()
+        })
+        .filter(|cells| cells.len() > 1)
+        .collect()
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        result.push_str(&rest[..start]);
+        let after = &rest[start..];
+        let Some(end) = after.find(';').filter(|&end| end <= 10) else {
+            result.push('&');
+            rest = &after[1..];
+            continue;
+        };
+        let entity = &after[1..end];
+        let decoded = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some(' '),
+            _ => entity
+                .strip_prefix("#x")
+                .or_else(|| entity.strip_prefix("#X"))
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                .and_then(char::from_u32),
+        };
+        match decoded {
+            Some(ch) => {
+                result.push(ch);
+                rest = &after[end + 1..];
+            }
+            None => {
+                result.push('&');
+                rest = &after[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Generate OCR name variations for a club
+///
+/// Includes the full name, the short name, the name without common club
+/// affixes such as "FC" or "SK", scoreboard-style abbreviations ("Man Utd")
+/// and, for names of three or more words, their initials.
+pub fn generate_variations(name: &str, short_name: Option<&str>) -> Vec<String> {
+    let mut variations: Vec<String> = Vec::new();
+    let mut push = |variation: String| {
+        let variation = variation.split_whitespace().collect::<Vec<_>>().join(" ");
+        if variation.chars().count() >= 2
+            && !variations
+                .iter()
+                .any(|v| v.to_lowercase() == variation.to_lowercase())
+        {
+            variations.push(variation);
+        }
+    };
+
+    push(name.to_string());
+    if let Some(short) = short_name {
+        push(short.to_string());
+        push(strip_affixes(short));
+    }
+
+    let stripped = strip_affixes(name);
+    push(stripped.clone());
+
+    let words: Vec<&str> = stripped.split_whitespace().collect();
+    let abbreviated: Vec<&str> = words
+        .iter()
+        .map(|word| {
+            WORD_ABBREVIATIONS
+                .iter()
+                .find(|(full, _)| word.eq_ignore_ascii_case(full))
+                .map(|(_, short)| *short)
+                .unwrap_or(word)
+        })
+        .collect();
+    if abbreviated.len() > 1 && abbreviated != words {
+        push(abbreviated.join(" "));
+    }
+
+    let initials_source: Vec<&str> = stripped
+        .split(|c: char| c.is_whitespace() || c == '-')
+        .filter(|word| {
+            word.chars()
+                .next()
+                .map(char::is_alphabetic)
+                .unwrap_or(false)
+        })
+        .collect();
+    if initials_source.len() >= 3 {
+        push(
+            initials_source
+                .iter()
+                .filter_map(|word| word.chars().next())
+                .flat_map(char::to_uppercase)
+                .collect(),
+        );
+    }
+
+    variations
+}
+
+/// Remove leading/trailing club affixes ("FC Porto" -> "Porto")
+fn strip_affixes(name: &str) -> String {
+    let mut words: Vec<&str> = name.split_whitespace().collect();
+    let is_affix = |word: &str| {
+        CLUB_AFFIXES.iter().any(|affix| {
+            word.trim_matches('.')
+                .eq_ignore_ascii_case(affix.trim_matches('.'))
+        })
+    };
+    while words.len() > 1 && is_affix(words[0]) {
+        words.remove(0);
+    }
+    while words.len() > 1 && is_affix(words[words.len() - 1]) {
+        words.pop();
+    }
+    words.join(" ")
+}
+
+/// Add imported clubs to the database, leaving existing teams untouched
+pub fn import_clubs(database: &mut TeamDatabase, clubs: &[ImportedClub]) -> FmImportReport {
+    let mut report = FmImportReport::default();
+    let mut by_league: BTreeMap<&str, Vec<&ImportedClub>> = BTreeMap::new();
+    for club in clubs {
+        by_league.entry(club.league()).or_default().push(club);
+    }
+
+    for (league, clubs) in by_league {
+        for club in clubs {
+            let label = format!("{} / {}", league, club.name);
+            let key = slugify(&club.name);
+            if key.is_empty() || database.has_team(league, &key) {
+                report.skipped.push(label);
+                continue;
+            }
+            let team = Team {
+                display_name: club.name.clone(),
+                variations: generate_variations(&club.name, club.short_name.as_deref()),
+            };
+            match database.add_team(league.to_string(), key, team) {
+                Ok(()) => report.added.push(label),
+                Err(_) => report.skipped.push(label),
+            }
+        }
+    }
+
+    tracing::info!("[fm_import] Imported FM export: {}", report.summary());
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT_EXPORT: &str = "\
+| Club                | Short Name | Nation  | Division              |
+| ------------------- | ---------- | ------- | --------------------- |
+| Manchester United   | Man Utd    | England | English Premier Division |
+| FC Porto            | Porto      | Portugal| Liga Portugal         |
+| Galatasaray SK      | Galatasaray| Türkiye | -                     |
+";
+
+    #[test]
+    fn test_parse_text_export() {
+        let clubs = parse_export(TEXT_EXPORT).unwrap();
+        assert_eq!(clubs.len(), 3);
+        assert_eq!(clubs[0].name, "Manchester United");
+        assert_eq!(clubs[0].short_name.as_deref(), Some("Man Utd"));
+        assert_eq!(clubs[0].league(), "English Premier Division");
+        // Missing competition falls back to the nation
+        assert_eq!(clubs[2].league(), "Türkiye");
+    }
+
+    #[test]
+    fn test_parse_html_export() {
+        let html = r#"<html><body><table>
+            <tr><th>Name</th><th>Competition</th></tr>
+            <tr><td><a href="x">Brighton &amp; Hove Albion</a></td><td>Premier League</td></tr>
+            <tr><td>Atl&#233;tico Madrid</td><td>LaLiga</td></tr>
+        </table></body></html>"#;
+        let clubs = parse_export(html).unwrap();
+        assert_eq!(clubs.len(), 2);
+        assert_eq!(clubs[0].name, "Brighton & Hove Albion");
+        assert_eq!(clubs[1].name, "Atlético Madrid");
+        assert_eq!(clubs[1].league(), "LaLiga");
+        assert_eq!(clubs[1].short_name, None);
+    }
+
+    #[test]
+    fn test_parse_requires_name_column() {
+        assert!(parse_export("| Foo | Bar |\n| 1 | 2 |").is_err());
+        assert!(parse_export("| Club | Nation |\n").is_err());
+    }
+
+    #[test]
+    fn test_decode_export_handles_boms() {
+        assert_eq!(decode_export(b"\xEF\xBB\xBFClub"), "Club");
+        let utf16: Vec<u8> = [0xFF, 0xFE]
+            .into_iter()
+            .chain("Şişli".encode_utf16().flat_map(|u| u.to_le_bytes()))
+            .collect();
+        assert_eq!(decode_export(&utf16), "Şişli");
+    }
+
+    #[test]
+    fn test_generate_variations() {
+        let variations = generate_variations("Manchester United", Some("Man Utd"));
+        assert_eq!(variations, vec!["Manchester United", "Man Utd"]);
+
+        let variations = generate_variations("FC Porto", None);
+        assert!(variations.contains(&"Porto".to_string()));
+
+        let variations = generate_variations("Galatasaray SK", Some("Galatasaray"));
+        assert_eq!(variations, vec!["Galatasaray SK", "Galatasaray"]);
+
+        let variations = generate_variations("Paris Saint-Germain", Some("PSG"));
+        assert!(variations.contains(&"PSG".to_string()));
+
+        let variations = generate_variations("Wolverhampton Wanderers", Some("Wolves"));
+        assert!(variations.contains(&"Wolverhampton W".to_string()));
+    }
+
+    #[test]
+    fn test_import_clubs_skips_existing() {
+        let mut db = TeamDatabase::default();
+        let clubs = parse_export(TEXT_EXPORT).unwrap();
+
+        let report = import_clubs(&mut db, &clubs);
+        assert_eq!(report.added.len(), 3);
+        assert!(db.has_team("Liga Portugal", "FC_Porto"));
+        let porto = db.find_team("Liga Portugal", "FC_Porto").unwrap();
+        assert!(porto.variations.contains(&"Porto".to_string()));
+
+        let report = import_clubs(&mut db, &clubs);
+        assert!(report.added.is_empty());
+        assert_eq!(report.skipped.len(), 3);
+    }
+}
@@ -35,6 +35,11 @@ actions!(
         CheckForUpdates,
         // Application lifecycle
         QuitApp,
+        // Diagnostics
+        DumpFlightRecorder,
+        MarkFalsePositive,
+        MarkMissedGoal,
+        LearnVariation,
     ]
 );
 
@@ -1,29 +1,51 @@
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::thread;
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime};
 
 use anyhow::{anyhow, Context, Result};
 use crossbeam_channel::{unbounded, Receiver, Sender, TryRecvError};
-use dirs::config_dir;
-use image::DynamicImage;
+use image::{DynamicImage, RgbaImage};
 use parking_lot::Mutex;
 use rand::seq::SliceRandom;
 
+use crate::app_paths;
 use crate::audio::AudioManager;
 use crate::audio_converter;
 use crate::capture::{CaptureManager, CaptureRegion};
-use crate::config::{Config, MusicEntry as ConfigMusicEntry, SelectedTeam};
+use crate::config::{
+    CelebrationOverride, CompetitionOverride, Config, MusicEntry as ConfigMusicEntry, SelectedTeam,
+    TeamProfile,
+};
+use crate::config_migrations;
 use crate::detection::i18n::{I18nPhrases, Language};
+use crate::detection::i18n_loader;
+use crate::detection::{
+    match_goal_rule, DetectionContext, DetectionResult, Detector, GoalDetector, KickoffDetector,
+    MatchEndDetector,
+};
+use crate::file_watch::FileWatcher;
+use crate::flight_recorder::{self, FlightRecorder, FrameDecision, RecordedFrame};
+use crate::fm_import::{self, FmImportReport};
+use crate::messaging::{AudioSource, ConfigField, Event, EventBus};
+use crate::ocr::layout;
 use crate::ocr::text_extraction;
 use crate::ocr::OcrManager;
+use crate::persistence;
+use crate::profiles;
+use crate::sample_library::{self, SampleContext, SampleLabel};
+use crate::settings_writer::SettingsWriter;
 use crate::slug::slugify;
-use crate::state::{AppState, MusicEntry, ProcessState};
-use crate::team_matcher::TeamMatcher;
-use crate::teams::{Team, TeamDatabase};
+use crate::state::{
+    AppState, DetectionMetrics, MusicEntry, OcrDebugFrame, ProcessState, OCR_DEBUG_INTERVAL,
+};
+use crate::team_matcher::{self, Fixture, TeamIdentifier, TeamMatcher};
+use crate::team_pack::{ConflictResolution, ImportReport, TeamPack};
+use crate::teams::{is_safe_name, Team, TeamDatabase};
 use crate::update_checker::{self, UpdateCheckResult};
-use crate::utils::Debouncer;
+use crate::utils::{Debouncer, IterationTiming};
+use crate::variation_stats::VariationStats;
 use tracing::{error, info, warn};
 use xcap::Monitor;
 
@@ -33,12 +55,46 @@ pub struct MonitorSummary {
     pub label: String,
 }
 
-const DEFAULT_BENCH_FRAMES: usize = 500;
 const AUDIO_FADE_MS: u64 = 200;
+/// Horizontal padding around an auto-found goal line, in line heights
+const AUTO_REGION_PAD_X_LINES: u32 = 4;
+/// Vertical padding around an auto-found goal line, in line heights
+const AUTO_REGION_PAD_Y_LINES: u32 = 1;
+/// Frames before the last trigger saved when it is marked as a false positive
+const FALSE_POSITIVE_LOOKBACK: Duration = Duration::from_secs(3);
+/// Frames saved when the user reports a missed goal
+const MISSED_GOAL_LOOKBACK: Duration = Duration::from_secs(5);
+/// How often the competition region is read while monitoring
+const COMPETITION_POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// How often settings files are checked for edits made outside the app
+const RELOAD_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Settings file reloaded when edited on disk
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum WatchedFile {
+    Config,
+    Teams,
+    Phrases(Language),
+}
 
 enum DetectionCommand {
     Stop,
     StopAudio,
+    DumpRecorder,
+    LabelSample(SampleLabel),
+    /// Celebrate a goal now, for a monitored team or with the default music
+    ManualGoal(Option<SelectedTeam>),
+    /// Play one library track as goal music
+    PlayTrack {
+        name: String,
+        bytes: Arc<Vec<u8>>,
+    },
+    /// A team's name or variations changed in the database
+    UpdateTeamProfile {
+        from: SelectedTeam,
+        to: SelectedTeam,
+        team: Team,
+    },
 }
 
 #[derive(Clone)]
@@ -49,25 +105,86 @@ pub struct GuiController {
 struct ControllerInner {
     state: Arc<Mutex<AppState>>,
     team_database: Mutex<Option<TeamDatabase>>,
+    /// Shared with the detection thread; `None` when variation_stats.json
+    /// exists but can't be read, so it is never saved over
+    variation_stats: Arc<Mutex<Option<VariationStats>>>,
     detection_thread: Mutex<Option<thread::JoinHandle<()>>>,
     detection_cmd_tx: Mutex<Option<Sender<DetectionCommand>>>,
+    settings_writer: SettingsWriter,
+    /// Announces goals, matches, playback and state changes
+    events: EventBus,
 }
 
 impl GuiController {
-    pub fn new() -> Result<Self> {
+    /// Create the controller with the configuration profile `profile`, or
+    /// the one used last if `None`
+    pub fn new(profile: Option<String>) -> Result<Self> {
         let state = Arc::new(Mutex::new(AppState::default()));
-        let team_database = TeamDatabase::load().ok();
+        let mut load_problems = Vec::new();
+        let (team_database, merge_report) = match TeamDatabase::load_with_report() {
+            Ok((database, report, problem)) => {
+                load_problems.extend(problem);
+                (Some(database), Some(report))
+            }
+            Err(err) => {
+                warn!("Failed to load team database: {err}");
+                (None, None)
+            }
+        };
+
+        let variation_stats = match VariationStats::load() {
+            Ok(stats) => Some(stats),
+            Err(err) => {
+                warn!("[variations] Not recording match counts: {err:#}");
+                None
+            }
+        };
+
+        let profile = match profile {
+            Some(name) => match profiles::list()?
+                .into_iter()
+                .find(|existing| existing.eq_ignore_ascii_case(name.trim()))
+            {
+                Some(existing) => existing,
+                None => {
+                    let created = profiles::create(&name, &Config::default())?;
+                    info!("Created configuration profile '{created}'");
+                    created
+                }
+            },
+            None => profiles::active(),
+        };
+        match Config::load_from(&profiles::profile_path(&profile)?) {
+            Ok((config, problem)) => {
+                apply_config(&mut state.lock(), &config);
+                load_problems.extend(problem);
+            }
+            Err(err) => warn!("Failed to load config: {err}"),
+        }
+        if let Err(err) = profiles::set_active(&profile) {
+            warn!("Failed to remember profile '{profile}': {err}");
+        }
+        state.lock().active_profile = profile;
 
-        if let Ok(config) = Config::load() {
-            apply_config(&state, &config);
+        if let Some(report) = merge_report.filter(|report| !report.is_empty()) {
+            state.lock().status_message = format!("Team database updated: {}", report.summary());
         }
+        state.lock().load_problems = load_problems.iter().map(ToString::to_string).collect();
+
+        let writer_state = Arc::clone(&state);
+        let settings_writer = SettingsWriter::spawn(move |message| {
+            writer_state.lock().status_message = message;
+        });
 
         Ok(Self {
             inner: Arc::new(ControllerInner {
                 state,
                 team_database: Mutex::new(team_database),
+                variation_stats: Arc::new(Mutex::new(variation_stats)),
                 detection_thread: Mutex::new(None),
                 detection_cmd_tx: Mutex::new(None),
+                settings_writer,
+                events: EventBus::new(),
             }),
         })
     }
@@ -80,6 +197,15 @@ impl GuiController {
         self.inner.team_database.lock().clone()
     }
 
+    /// Bus that goals, matches, playback and state changes are published on
+    pub fn events(&self) -> EventBus {
+        self.inner.events.clone()
+    }
+
+    fn publish(&self, event: Event) {
+        self.inner.events.publish(event);
+    }
+
     pub fn status_message(&self) -> String {
         self.inner.state.lock().status_message.clone()
     }
@@ -117,7 +243,7 @@ impl GuiController {
 
     fn mark_start_failure(&self, message: impl Into<String>) {
         let mut state = self.inner.state.lock();
-        state.process_state = ProcessState::Stopped;
+        set_process_state(&mut state, &self.inner.events, ProcessState::Stopped);
         state.status_message = message.into();
     }
 
@@ -167,7 +293,81 @@ impl GuiController {
         Ok(preview_path)
     }
 
+    /// Start or stop publishing frames for the live OCR debug panel
+    pub fn set_ocr_debug(&self, enabled: bool) {
+        let mut state = self.inner.state.lock();
+        state.ocr_debug_enabled = enabled;
+        if !enabled {
+            state.ocr_debug_frame = None;
+        }
+    }
+
     pub fn capture_fullscreen_for_selection(&self) -> Result<RegionCapture> {
+        let (_, capture) = self.capture_selected_monitor()?;
+        Ok(capture)
+    }
+
+    /// Find the goal banner on a full screenshot and propose a capture region
+    ///
+    /// Runs OCR with word boxes over the selected monitor, looks for the goal
+    /// phrases of the selected language (plus custom phrases) and pads the
+    /// matching line. The proposal is not applied; the user confirms it.
+    pub fn auto_find_capture_region(&self) -> Result<RegionProposal> {
+        let (ocr_threshold, enable_morph_open, language, mut phrases) = {
+            let state = self.inner.state.lock();
+            (
+                state.ocr_threshold,
+                state.enable_morph_open,
+                state.selected_language,
+                state.custom_goal_phrases.clone(),
+            )
+        };
+        phrases.extend(I18nPhrases::new(language).goal_phrases);
+        phrases.extend(["GOAL FOR".to_string(), "GOL".to_string()]);
+
+        let (image, capture) = self.capture_selected_monitor()?;
+
+        let mut ocr_manager = OcrManager::new_with_options(ocr_threshold, enable_morph_open)
+            .map_err(|err| anyhow!("Failed to initialize OCR manager: {err}"))?;
+        let found = ocr_manager
+            .locate_phrase(&image, &phrases)
+            .map_err(|err| anyhow!("OCR failed: {err}"))?
+            .ok_or_else(|| {
+                anyhow!(
+                    "No {} goal text found on screen. Pause on a goal banner and try again.",
+                    language.name()
+                )
+            })?;
+
+        let line_height = found.bounds[3].max(1);
+        let physical_rect = layout::pad_rect(
+            found.bounds,
+            line_height * AUTO_REGION_PAD_X_LINES,
+            line_height * AUTO_REGION_PAD_Y_LINES,
+            capture.physical_size.0,
+            capture.physical_size.1,
+        );
+        let region = capture.to_logical(physical_rect);
+
+        info!(
+            "[auto-region] Found '{}' in line '{}' at {:?} (physical), proposing {:?}",
+            found.phrase, found.line_text, found.bounds, region
+        );
+
+        {
+            let mut state = self.inner.state.lock();
+            state.status_message = format!("Found '{}' on screen", found.line_text);
+        }
+
+        Ok(RegionProposal {
+            capture,
+            physical_rect,
+            region,
+            matched_text: found.line_text,
+        })
+    }
+
+    fn capture_selected_monitor(&self) -> Result<(RgbaImage, RegionCapture)> {
         let selected_monitor = {
             let state = self.inner.state.lock();
             state.selected_monitor_index
@@ -199,16 +399,19 @@ impl GuiController {
         };
 
         let path = region_selection_image_path()?;
-        DynamicImage::ImageRgba8(image)
+        image
             .save(&path)
             .map_err(|err| anyhow!("Failed to save capture snapshot: {err}"))?;
 
-        Ok(RegionCapture {
-            image_path: path,
-            physical_size: (physical_w, physical_h),
-            logical_size: (logical_w, logical_h),
-            device_scale,
-        })
+        Ok((
+            image,
+            RegionCapture {
+                image_path: path,
+                physical_size: (physical_w, physical_h),
+                logical_size: (logical_w, logical_h),
+                device_scale,
+            },
+        ))
     }
 
     pub fn add_music_file(&self, path: PathBuf) -> Result<()> {
@@ -247,12 +450,7 @@ impl GuiController {
                         state.selected_music_index = Some(sel - 1);
                     }
                 }
-                state.goal_music_indices.retain(|&i| i != index);
-                for idx_ref in &mut state.goal_music_indices {
-                    if *idx_ref > index {
-                        *idx_ref -= 1;
-                    }
-                }
+                state.forget_music_index(index);
                 if let Some(last) = state.last_played_music_index {
                     if last == index {
                         state.last_played_music_index = None;
@@ -271,6 +469,12 @@ impl GuiController {
     pub fn select_music(&self, index: Option<usize>) {
         let mut state = self.inner.state.lock();
         state.selected_music_index = index;
+        if let Some(entry) = index.and_then(|idx| state.music_list.get(idx)) {
+            self.publish(Event::MusicSelected {
+                path: entry.path.clone(),
+                name: entry.name.clone(),
+            });
+        }
         state.status_message = match index {
             Some(idx) => format!("Selected music #{idx}"),
             None => "No music selected".to_string(),
@@ -284,13 +488,14 @@ impl GuiController {
                 return Ok(());
             }
 
+            let playlist = state.celebration_mut().goal_music_indices;
             if in_playlist {
-                if !state.goal_music_indices.contains(&index) {
-                    state.goal_music_indices.push(index);
+                if !playlist.contains(&index) {
+                    playlist.push(index);
                 }
                 state.status_message = format!("Added track #{} to goal playlist", index + 1);
             } else {
-                state.goal_music_indices.retain(|&i| i != index);
+                playlist.retain(|&i| i != index);
                 if let Some(last) = state.last_played_music_index {
                     if last == index {
                         state.last_played_music_index = None;
@@ -305,27 +510,12 @@ impl GuiController {
     }
 
     pub fn set_league(&self, league: Option<String>) {
-        {
-            let mut state = self.inner.state.lock();
-            if league.is_none() {
-                state.selected_team = None;
-            }
-        }
-
         if let Some(team_db) = self.team_database() {
             let mut data = self.inner.state.lock();
             if let Some(league_name) = league {
                 if !team_db.get_leagues().contains(&league_name) {
                     data.status_message = format!("League '{league_name}' not found");
                 } else {
-                    if let Some(selected) = data
-                        .selected_team
-                        .as_mut()
-                        .filter(|team| team.league != league_name)
-                    {
-                        selected.league = league_name.clone();
-                        selected.team_key.clear();
-                    }
                     data.status_message = format!("League set to {league_name}");
                 }
             } else {
@@ -343,18 +533,198 @@ impl GuiController {
             .find_team(league, team_key)
             .ok_or_else(|| anyhow::anyhow!("team not found in database"))?;
 
+        let selected = SelectedTeam {
+            league: league.to_string(),
+            team_key: team_key.to_string(),
+            display_name: team.display_name.clone(),
+        };
+        {
+            let mut state = self.inner.state.lock();
+            state.switch_focused_team(selected.clone());
+            state.status_message = format!("Selected team {}", team.display_name);
+        }
+        self.publish(Event::TeamSelected { team: selected });
+
+        self.save_config()?;
+        Ok(())
+    }
+
+    /// Monitor another team alongside the current ones
+    ///
+    /// The new profile starts with the current playlist and ambiance.
+    pub fn add_team_profile(&self, league: &str, team_key: &str) -> Result<()> {
+        let team = self
+            .team_database()
+            .and_then(|db| db.find_team(league, team_key))
+            .ok_or_else(|| anyhow!("team not found in database"))?;
+
         {
             let mut state = self.inner.state.lock();
-            state.selected_team = Some(SelectedTeam {
+            state.focus_team(SelectedTeam {
                 league: league.to_string(),
                 team_key: team_key.to_string(),
                 display_name: team.display_name.clone(),
             });
-            state.status_message = format!("Selected team {}", team.display_name);
+            state.status_message = format!(
+                "Monitoring {} teams — editing {}",
+                state.team_profiles.len(),
+                team.display_name
+            );
         }
 
-        self.save_config()?;
-        Ok(())
+        self.save_config()
+    }
+
+    /// Show and edit another monitored team's celebration
+    pub fn focus_team_profile(&self, team: &SelectedTeam) -> Result<()> {
+        {
+            let mut state = self.inner.state.lock();
+            if !state
+                .team_profiles
+                .iter()
+                .any(|profile| profile.is_for(team))
+            {
+                return Err(anyhow!("{} is not monitored", team.display_name));
+            }
+            state.focus_team(team.clone());
+            state.status_message = format!("Editing celebration for {}", team.display_name);
+        }
+        self.save_config()
+    }
+
+    /// Mark a club as a rival of the selected team
+    pub fn add_rival(&self, rival: SelectedTeam) -> Result<()> {
+        {
+            let mut state = self.inner.state.lock();
+            let profile = state
+                .focused_profile_mut()
+                .ok_or_else(|| anyhow!("Select a team to add rivals to"))?;
+            if profile.is_for(&rival) {
+                return Err(anyhow!("A team can't be its own rival"));
+            }
+            if profile.is_rival(&rival) {
+                return Err(anyhow!(
+                    "{} is already a rival of {}",
+                    rival.display_name,
+                    profile.team.display_name
+                ));
+            }
+            let message = format!(
+                "{} is now a rival of {}",
+                rival.display_name, profile.team.display_name
+            );
+            profile.rivals.push(rival);
+            state.status_message = message;
+        }
+        self.save_config()
+    }
+
+    pub fn remove_rival(&self, rival: &SelectedTeam) -> Result<()> {
+        {
+            let mut state = self.inner.state.lock();
+            let profile = state
+                .focused_profile_mut()
+                .ok_or_else(|| anyhow!("Select a team to remove rivals from"))?;
+            profile
+                .rivals
+                .retain(|r| r.league != rival.league || r.team_key != rival.team_key);
+            state.status_message = format!("{} is no longer a rival", rival.display_name);
+        }
+        self.save_config()
+    }
+
+    /// Celebrate the selected team's goals differently in a competition
+    pub fn add_competition_override(&self, competition: String) -> Result<()> {
+        let competition = competition.trim().to_string();
+        if competition.is_empty() {
+            return Err(anyhow!("Enter the competition name as shown on screen"));
+        }
+        {
+            let mut state = self.inner.state.lock();
+            let profile = state
+                .focused_profile_mut()
+                .ok_or_else(|| anyhow!("Select a team to add competition celebrations to"))?;
+            if profile
+                .competition_overrides
+                .iter()
+                .any(|cup| cup.competition.eq_ignore_ascii_case(&competition))
+            {
+                return Err(anyhow!("'{}' already has a celebration", competition));
+            }
+            profile.competition_overrides.push(CompetitionOverride {
+                competition: competition.clone(),
+                celebration: CelebrationOverride::default(),
+            });
+            state.status_message = format!("Added a celebration for '{}'", competition);
+        }
+        self.save_config()
+    }
+
+    pub fn remove_competition_override(&self, competition: &str) -> Result<()> {
+        {
+            let mut state = self.inner.state.lock();
+            let profile = state
+                .focused_profile_mut()
+                .ok_or_else(|| anyhow!("Select a team to edit"))?;
+            profile
+                .competition_overrides
+                .retain(|cup| cup.competition != competition);
+            state.status_message = format!("Removed the celebration for '{}'", competition);
+        }
+        self.save_config()
+    }
+
+    /// Add or remove a track from a special celebration's playlist
+    pub fn set_special_playlist_membership(
+        &self,
+        special: &SpecialCelebration,
+        index: usize,
+        in_playlist: bool,
+    ) -> Result<()> {
+        self.edit_special_celebration(special, |celebration| {
+            if !in_playlist {
+                celebration.goal_music_indices.retain(|&i| i != index);
+            } else if !celebration.goal_music_indices.contains(&index) {
+                celebration.goal_music_indices.push(index);
+            }
+        })
+    }
+
+    pub fn set_special_ambiance_path(
+        &self,
+        special: &SpecialCelebration,
+        path: Option<PathBuf>,
+    ) -> Result<()> {
+        self.edit_special_celebration(special, |celebration| {
+            celebration.ambiance_path = path.map(|p| p.to_string_lossy().to_string());
+        })
+    }
+
+    fn edit_special_celebration(
+        &self,
+        special: &SpecialCelebration,
+        edit: impl FnOnce(&mut CelebrationOverride),
+    ) -> Result<()> {
+        {
+            let mut state = self.inner.state.lock();
+            let profile = state
+                .focused_profile_mut()
+                .ok_or_else(|| anyhow!("Select a team to edit"))?;
+            let celebration = match special {
+                SpecialCelebration::Derby => profile
+                    .derby_celebration
+                    .get_or_insert_with(Default::default),
+                SpecialCelebration::Competition(name) => profile
+                    .competition_overrides
+                    .iter_mut()
+                    .find(|cup| &cup.competition == name)
+                    .map(|cup| &mut cup.celebration)
+                    .ok_or_else(|| anyhow!("No celebration for '{}'", name))?,
+            };
+            edit(celebration);
+            state.status_message = format!("Updated the {} celebration", special.label());
+        }
+        self.save_config()
     }
 
     pub fn add_custom_team(
@@ -401,7 +771,7 @@ impl GuiController {
 
         {
             let mut state = self.inner.state.lock();
-            state.selected_team = Some(selected_team.clone());
+            state.switch_focused_team(selected_team.clone());
             state.status_message = format!("Added {team_name} to {league_name}");
         }
 
@@ -432,11 +802,7 @@ impl GuiController {
             return Err(anyhow!("Team logo must be a PNG file"));
         }
 
-        let base_dir = config_dir()
-            .ok_or_else(|| anyhow!("Could not determine user config directory"))?
-            .join("FMGoalMusic")
-            .join("teams")
-            .join(league_name);
+        let base_dir = team_logo_dir(league_name)?;
 
         fs::create_dir_all(&base_dir)
             .with_context(|| format!("Failed to create logo directory: {}", base_dir.display()))?;
@@ -461,154 +827,713 @@ impl GuiController {
         Ok(())
     }
 
+    /// Stop monitoring the selected team
+    ///
+    /// The next monitored team is selected; with none left, every goal is
+    /// celebrated again.
     pub fn clear_team_selection(&self) -> Result<()> {
         {
             let mut state = self.inner.state.lock();
-            state.selected_team = None;
-            state.status_message = "Team selection cleared".to_string();
+            state.status_message = match state.remove_focused_profile() {
+                Some(removed) => match &state.focused_team {
+                    Some(next) => format!(
+                        "Stopped monitoring {} — editing {}",
+                        removed.team.display_name, next.display_name
+                    ),
+                    None => "Team selection cleared".to_string(),
+                },
+                None => "Team selection cleared".to_string(),
+            };
         }
         self.save_config()?;
         Ok(())
     }
 
-    pub fn update_capture_region(&self, region: [u32; 4]) -> Result<()> {
-        {
-            let mut state = self.inner.state.lock();
-            state.capture_region = region;
-            state.status_message = format!(
-                "Capture region set to [{}, {}, {}, {}]",
-                region[0], region[1], region[2], region[3]
-            );
-        }
-        self.save_config()?;
-        Ok(())
+    /// Apply an edit to the team database and save it
+    fn edit_team_database<T>(
+        &self,
+        edit: impl FnOnce(&mut TeamDatabase) -> Result<T, String>,
+    ) -> Result<T> {
+        let mut guard = self.inner.team_database.lock();
+        let db = guard
+            .as_mut()
+            .ok_or_else(|| anyhow!("team database not available"))?;
+        let result = edit(db).map_err(|e| anyhow!(e))?;
+        db.save()
+            .map_err(|err| anyhow!("failed to save team database: {err}"))?;
+        Ok(result)
     }
 
-    pub fn set_monitor_index(&self, index: usize) -> Result<()> {
+    /// Keep the monitored teams and their rivals pointing at the right entries
+    /// after a database edit
+    ///
+    /// `retarget` maps a team to its replacement, or to `None` if the team no
+    /// longer exists; profiles and rivals of removed teams are dropped.
+    fn retarget_selected_team(
+        &self,
+        retarget: impl Fn(&SelectedTeam) -> Option<SelectedTeam>,
+    ) -> Result<()> {
         {
             let mut state = self.inner.state.lock();
-            state.selected_monitor_index = index;
-            state.status_message = format!("Monitor set to {}", index + 1);
+            let focused = state.focused_team.clone();
+            let focused_after = focused.as_ref().and_then(&retarget);
+
+            let mut changed = focused_after != focused;
+            let mut profiles = Vec::new();
+            for mut profile in state.team_profiles.clone() {
+                let rivals: Vec<SelectedTeam> =
+                    profile.rivals.iter().filter_map(&retarget).collect();
+                changed |= rivals != profile.rivals;
+                profile.rivals = rivals;
+                match retarget(&profile.team) {
+                    Some(team) => {
+                        changed |= team != profile.team;
+                        profile.team = team;
+                        profiles.push(profile);
+                    }
+                    None => changed = true,
+                }
+            }
+            if !changed {
+                return Ok(());
+            }
+
+            state.team_profiles = profiles;
+            state.focused_team = focused_after.or_else(|| {
+                state
+                    .team_profiles
+                    .first()
+                    .map(|profile| profile.team.clone())
+            });
         }
-        self.save_config()?;
-        Ok(())
+        self.save_config()
     }
 
-    pub fn set_status(&self, message: impl Into<String>) {
-        let mut state = self.inner.state.lock();
-        state.status_message = message.into();
-    }
+    /// Rename a team and replace its name variations
+    ///
+    /// The team key follows the new name, so the logo is renamed with it.
+    ///
+    /// # Returns
+    /// The team's identity after the update
+    pub fn update_team(
+        &self,
+        league: &str,
+        team_key: &str,
+        display_name: &str,
+        variations: Vec<String>,
+    ) -> Result<SelectedTeam> {
+        let new_key = self
+            .edit_team_database(|db| db.update_team(league, team_key, display_name, variations))?;
+        let display_name = display_name.trim().to_string();
+
+        if new_key != team_key {
+            let dir = team_logo_dir(league)?;
+            move_team_logo(
+                &dir.join(format!("{team_key}.png")),
+                &dir.join(format!("{new_key}.png")),
+            );
+            update_variation_stats(&self.inner.variation_stats, |stats| {
+                stats.rekey((league, team_key), (league, &new_key))
+            });
+        }
 
-    pub fn config_file_path(&self) -> Option<PathBuf> {
-        Config::config_path().ok()
-    }
+        let updated = SelectedTeam {
+            league: league.to_string(),
+            team_key: new_key,
+            display_name: display_name.clone(),
+        };
+        self.retarget_selected_team(|current| {
+            if current.league == league && current.team_key == team_key {
+                Some(updated.clone())
+            } else {
+                Some(current.clone())
+            }
+        })?;
 
-    pub fn logs_directory(&self) -> Option<PathBuf> {
-        config_dir().map(|dir| dir.join("FMGoalMusic").join("logs"))
+        let previous = SelectedTeam {
+            league: league.to_string(),
+            team_key: team_key.to_string(),
+            display_name: display_name.to_string(),
+        };
+        self.refresh_running_team_profile(&previous, &updated);
+        self.inner.state.lock().status_message = format!("Updated {display_name}");
+        Ok(updated)
     }
 
-    pub fn start_monitoring(&self) -> Result<()> {
-        self.shutdown_detection_runtime();
+    /// Remove a team and its custom logo
+    pub fn remove_team(&self, league: &str, team_key: &str) -> Result<()> {
+        let team = self.edit_team_database(|db| db.remove_team(league, team_key))?;
+        update_variation_stats(&self.inner.variation_stats, |stats| {
+            stats.forget(league, team_key)
+        });
 
-        let setup = {
-            let state = self.inner.state.lock();
-            if state.process_state.is_running() {
-                drop(state);
-                self.set_status("Detection already running");
-                return Ok(());
+        let logo = team_logo_dir(league)?.join(format!("{team_key}.png"));
+        if logo.exists() {
+            if let Err(err) = fs::remove_file(&logo) {
+                warn!("[logo] Failed to remove {}: {}", logo.display(), err);
             }
+        }
 
-            state
-                .validate_region()
-                .map_err(|err| anyhow!(err.to_string()))?;
-            state
-                .validate_music_selection()
-                .map_err(|err| anyhow!(err.to_string()))?;
-
-            let playlist_indices = state.goal_playlist_indices();
-            if playlist_indices.is_empty() {
-                return Err(anyhow!(
-                    "Select at least one music file before starting detection"
-                ));
+        self.retarget_selected_team(|current| {
+            if current.league == league && current.team_key == team_key {
+                None
+            } else {
+                Some(current.clone())
             }
+        })?;
 
-            let playlist_entries: Vec<MusicEntry> = playlist_indices
-                .iter()
-                .filter_map(|&idx| state.music_list.get(idx).cloned())
-                .collect();
+        self.inner.state.lock().status_message =
+            format!("Removed {} from {league}", team.display_name);
+        Ok(())
+    }
 
-            DetectionSetup {
-                playlist_indices,
-                playlist_entries,
-                capture_region: state.capture_region,
-                monitor_index: state.selected_monitor_index,
-                ocr_threshold: state.ocr_threshold,
-                enable_morph_open: state.enable_morph_open,
-                debounce_ms: state.debounce_ms,
-                selected_team: state.selected_team.clone(),
-                music_volume: state.music_volume,
-                ambiance_volume: state.ambiance_volume,
-                ambiance_path: state.goal_ambiance_path.clone(),
-                ambiance_enabled: state.ambiance_enabled,
-                music_length_ms: state.music_length_ms,
-                ambiance_length_ms: state.ambiance_length_ms,
-                custom_goal_phrases: state.custom_goal_phrases.clone(),
-                selected_language: state.selected_language,
+    /// Move a team to another league, taking its logo along
+    pub fn move_team(&self, from_league: &str, team_key: &str, to_league: &str) -> Result<()> {
+        self.edit_team_database(|db| db.move_team(from_league, team_key, to_league))?;
+        update_variation_stats(&self.inner.variation_stats, |stats| {
+            stats.rekey((from_league, team_key), (to_league, team_key))
+        });
+
+        move_team_logo(
+            &team_logo_dir(from_league)?.join(format!("{team_key}.png")),
+            &team_logo_dir(to_league)?.join(format!("{team_key}.png")),
+        );
+
+        self.retarget_selected_team(|current| {
+            let mut team = current.clone();
+            if team.league == from_league && team.team_key == team_key {
+                team.league = to_league.to_string();
             }
-        };
+            Some(team)
+        })?;
 
-        let mut music_bytes_list: Vec<Arc<Vec<u8>>> = Vec::new();
-        for entry in &setup.playlist_entries {
-            let bytes = match fs::read(&entry.path)
-                .with_context(|| format!("Failed to read audio {}", entry.path.display()))
-            {
-                Ok(bytes) => bytes,
-                Err(err) => {
-                    self.mark_start_failure(format!("{err:#}"));
-                    return Err(err);
-                }
-            };
-            music_bytes_list.push(Arc::new(bytes));
-        }
+        self.inner.state.lock().status_message = format!("Moved team to {to_league}");
+        Ok(())
+    }
 
-        let ambiance_bytes = if setup.ambiance_enabled {
-            if let Some(ref path) = setup.ambiance_path {
-                match fs::read(path).with_context(|| format!("Failed to read ambiance {}", path)) {
-                    Ok(bytes) => Some(Arc::new(bytes)),
-                    Err(err) => {
-                        self.mark_start_failure(format!("{err:#}"));
-                        return Err(err);
+    /// Rename a league, keeping its teams and logos
+    pub fn rename_league(&self, old_name: &str, new_name: &str) -> Result<()> {
+        let new_name = new_name.trim();
+        self.edit_team_database(|db| db.rename_league(old_name, new_name))?;
+        update_variation_stats(&self.inner.variation_stats, |stats| {
+            stats.rename_league(old_name, new_name)
+        });
+
+        match (team_logo_dir(old_name), team_logo_dir(new_name)) {
+            (Ok(old_dir), Ok(new_dir)) => {
+                if old_dir != new_dir && old_dir.exists() && !new_dir.exists() {
+                    if let Err(err) = fs::rename(&old_dir, &new_dir) {
+                        warn!(
+                            "[logo] Failed to move logos from {} to {}: {}",
+                            old_dir.display(),
+                            new_dir.display(),
+                            err
+                        );
                     }
                 }
-            } else {
-                None
             }
-        } else {
-            None
-        };
+            // A league named before names were checked has no logo folder
+            (Err(err), _) | (_, Err(err)) => warn!("[logo] Not moving logos: {err}"),
+        }
 
-        let team_profile = setup.selected_team.as_ref().and_then(|team| {
-            self.team_database()
-                .and_then(|db| db.find_team(&team.league, &team.team_key))
-        });
+        self.retarget_selected_team(|current| {
+            let mut team = current.clone();
+            if team.league == old_name {
+                team.league = new_name.to_string();
+            }
+            Some(team)
+        })?;
 
-        let track_name = if setup.playlist_entries.len() == 1 {
-            setup
-                .playlist_entries
-                .get(0)
-                .map(|e| e.name.clone())
-                .unwrap_or_else(|| "Unknown track".to_string())
-        } else {
-            format!("{} tracks (random playlist)", setup.playlist_entries.len())
+        self.inner.state.lock().status_message = format!("Renamed league {old_name} to {new_name}");
+        Ok(())
+    }
+
+    /// Remove a league with all of its teams and logos
+    pub fn remove_league(&self, league: &str) -> Result<()> {
+        let removed = self.edit_team_database(|db| db.remove_league(league))?;
+        update_variation_stats(&self.inner.variation_stats, |stats| {
+            stats.forget_league(league)
+        });
+
+        match team_logo_dir(league) {
+            Ok(dir) if dir.exists() => {
+                if let Err(err) = fs::remove_dir_all(&dir) {
+                    warn!("[logo] Failed to remove {}: {}", dir.display(), err);
+                }
+            }
+            Ok(_) => {}
+            Err(err) => warn!("[logo] Not removing logos: {err}"),
+        }
+
+        self.retarget_selected_team(|current| {
+            if current.league == league {
+                None
+            } else {
+                Some(current.clone())
+            }
+        })?;
+
+        self.inner.state.lock().status_message =
+            format!("Removed league {league} and its {removed} teams");
+        Ok(())
+    }
+
+    /// Add goal banner text as a new variation of a monitored team
+    ///
+    /// Uses `suggestion` when given, otherwise the pending near-miss
+    /// suggestion or, failing that, the last goal banner that did not match
+    /// (learned for the selected team).
+    pub fn learn_variation(&self, suggestion: Option<(SelectedTeam, String)>) -> Result<()> {
+        let (selected, text) = {
+            let state = self.inner.state.lock();
+            let (selected, text) = match suggestion.or_else(|| state.variation_suggestion.clone()) {
+                Some(suggestion) => suggestion,
+                None => {
+                    let selected = state
+                        .focused_team
+                        .clone()
+                        .ok_or_else(|| anyhow!("Select a team to learn name variations for"))?;
+                    let text = state
+                        .last_unmatched_team_text
+                        .clone()
+                        .ok_or_else(|| anyhow!("No unmatched goal banner to learn from"))?;
+                    (selected, text)
+                }
+            };
+            (selected, text.trim().to_string())
+        };
+
+        self.edit_team_database(|db| {
+            let team = db
+                .find_team(&selected.league, &selected.team_key)
+                .ok_or_else(|| format!("Team '{}' not found", selected.display_name))?;
+            if team
+                .variations
+                .iter()
+                .any(|variation| variation.eq_ignore_ascii_case(&text))
+            {
+                return Err(format!(
+                    "'{}' is already a variation of {}",
+                    text, team.display_name
+                ));
+            }
+            let mut variations = team.variations.clone();
+            variations.push(text.clone());
+            db.set_variations(&selected.league, &selected.team_key, variations)
+        })?;
+
+        info!(
+            "[variations] Learned '{}' for {} ({})",
+            text, selected.display_name, selected.league
+        );
+        self.refresh_running_team_profile(&selected, &selected);
+
+        let mut state = self.inner.state.lock();
+        state.variation_suggestion = None;
+        if state.last_unmatched_team_text.as_deref() == Some(text.as_str()) {
+            state.last_unmatched_team_text = None;
+        }
+        state.status_message = format!(
+            "Added '{}' as a variation of {}",
+            text, selected.display_name
+        );
+        Ok(())
+    }
+
+    /// Ignore the pending variation suggestion for the rest of the session
+    pub fn dismiss_variation_suggestion(&self) {
+        let mut state = self.inner.state.lock();
+        if let Some((_, text)) = state.variation_suggestion.take() {
+            state
+                .dismissed_variation_suggestions
+                .insert(text.to_lowercase());
+            state.status_message = format!("Won't suggest '{text}' again this session");
+        }
+    }
+
+    /// Match count of every variation of a team, in database order
+    pub fn variation_match_counts(&self, league: &str, team_key: &str) -> Vec<(String, u32)> {
+        let Some(team) = self
+            .team_database()
+            .and_then(|db| db.find_team(league, team_key))
+        else {
+            return Vec::new();
+        };
+        let stats = self.inner.variation_stats.lock();
+        team.variations
+            .into_iter()
+            .map(|variation| {
+                let matches = stats
+                    .as_ref()
+                    .map_or(0, |stats| stats.matches(league, team_key, &variation));
+                (variation, matches)
+            })
+            .collect()
+    }
+
+    /// Variations of a team that never matched while others did
+    pub fn stale_variations(&self, league: &str, team_key: &str) -> Vec<String> {
+        let Some(team) = self
+            .team_database()
+            .and_then(|db| db.find_team(league, team_key))
+        else {
+            return Vec::new();
+        };
+        self.inner
+            .variation_stats
+            .lock()
+            .as_ref()
+            .map(|stats| stats.stale_variations(league, team_key, &team))
+            .unwrap_or_default()
+    }
+
+    /// Remove a team's stale variations
+    ///
+    /// # Returns
+    /// The variations that were removed
+    pub fn prune_stale_variations(&self, league: &str, team_key: &str) -> Result<Vec<String>> {
+        let stale = self.stale_variations(league, team_key);
+        if stale.is_empty() {
+            return Ok(stale);
+        }
+
+        let display_name = self.edit_team_database(|db| {
+            let team = db
+                .find_team(league, team_key)
+                .ok_or_else(|| format!("Team '{}' not found", team_key))?;
+            let variations = team
+                .variations
+                .iter()
+                .filter(|variation| !stale.contains(variation))
+                .cloned()
+                .collect();
+            db.set_variations(league, team_key, variations)?;
+            Ok(team.display_name)
+        })?;
+
+        let team = SelectedTeam {
+            league: league.to_string(),
+            team_key: team_key.to_string(),
+            display_name: display_name.clone(),
+        };
+        self.refresh_running_team_profile(&team, &team);
+        self.inner.state.lock().status_message = format!(
+            "Removed {} unused variations of {}",
+            stale.len(),
+            display_name
+        );
+        Ok(stale)
+    }
+
+    /// Hand the running detection loop a team's current name and variations
+    ///
+    /// `from` is the team as the loop knows it, `to` its identity after the edit.
+    fn refresh_running_team_profile(&self, from: &SelectedTeam, to: &SelectedTeam) {
+        let Some(team) = self
+            .team_database()
+            .and_then(|db| db.find_team(&to.league, &to.team_key))
+        else {
+            return;
         };
+        if let Some(tx) = self.inner.detection_cmd_tx.lock().as_ref() {
+            let _ = tx.send(DetectionCommand::UpdateTeamProfile {
+                from: from.clone(),
+                to: to.clone(),
+                team,
+            });
+        }
+    }
+
+    /// Add clubs from a Football Manager text/HTML export to the team database
+    pub fn import_fm_export(&self, path: &Path) -> Result<FmImportReport> {
+        let bytes = fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        let clubs = fm_import::parse_export(&fm_import::decode_export(&bytes))?;
+        let report = self.edit_team_database(|db| Ok(fm_import::import_clubs(db, &clubs)))?;
+
+        self.inner.state.lock().status_message =
+            format!("Imported FM export: {}", report.summary());
+        Ok(report)
+    }
+
+    /// Read a team pack and report what importing it would do
+    pub fn preview_team_pack(&self, path: &Path) -> Result<TeamPackPreview> {
+        let pack = TeamPack::read(path)?;
+        let conflicts = self
+            .team_database()
+            .map(|db| pack.conflicts(&db).len())
+            .unwrap_or(0);
+        Ok(TeamPackPreview {
+            path: path.to_path_buf(),
+            name: pack.manifest.name.clone(),
+            team_count: pack.team_count(),
+            conflicts,
+        })
+    }
+
+    /// Import a team pack into the team database, installing its logos
+    pub fn import_team_pack(
+        &self,
+        path: &Path,
+        resolution: ConflictResolution,
+    ) -> Result<ImportReport> {
+        let pack = TeamPack::read(path)?;
+        let report = self.edit_team_database(|db| Ok(pack.import_into(db, resolution)))?;
+
+        let logo_root = team_logo_root()?;
+        pack.install_logos(&logo_root, report.added.iter().chain(&report.replaced))?;
+
+        // A replaced team may have a new display name
+        if let Some(db) = self.team_database() {
+            self.retarget_selected_team(|current| {
+                let mut team = current.clone();
+                if let Some(found) = db.find_team(&team.league, &team.team_key) {
+                    team.display_name = found.display_name;
+                }
+                Some(team)
+            })?;
+        }
+
+        self.inner.state.lock().status_message = format!(
+            "Imported team pack '{}': {}",
+            pack.manifest.name,
+            report.summary()
+        );
+        Ok(report)
+    }
+
+    /// Export teams, given as (league, team key), to a team pack file
+    ///
+    /// # Returns
+    /// Number of teams written to the pack
+    pub fn export_team_pack(
+        &self,
+        name: &str,
+        teams: &[(String, String)],
+        path: &Path,
+    ) -> Result<usize> {
+        let db = self
+            .team_database()
+            .ok_or_else(|| anyhow!("team database not available"))?;
+        let pack = TeamPack::from_database(
+            name,
+            &db,
+            teams
+                .iter()
+                .map(|(league, key)| (league.as_str(), key.as_str())),
+            &team_logo_root()?,
+        )?;
+        pack.write(path)?;
+
+        let count = pack.team_count();
+        self.inner.state.lock().status_message =
+            format!("Exported {count} teams to {}", path.display());
+        Ok(count)
+    }
+
+    pub fn update_capture_region(&self, region: [u32; 4]) -> Result<()> {
+        {
+            let mut state = self.inner.state.lock();
+            state.capture_region = region;
+            state.status_message = format!(
+                "Capture region set to [{}, {}, {}, {}]",
+                region[0], region[1], region[2], region[3]
+            );
+        }
+        self.publish(Event::RegionChanged { region });
+        self.save_config()?;
+        Ok(())
+    }
+
+    /// Set or clear the region showing the competition name
+    pub fn set_competition_region(&self, region: Option<[u32; 4]>) -> Result<()> {
+        {
+            let mut state = self.inner.state.lock();
+            state.competition_region = region;
+            state.status_message = match region {
+                Some(region) => format!(
+                    "Competition region set to [{}, {}, {}, {}]",
+                    region[0], region[1], region[2], region[3]
+                ),
+                None => "Competition region cleared".to_string(),
+            };
+        }
+        self.save_config()
+    }
 
+    pub fn set_monitor_index(&self, index: usize) -> Result<()> {
         {
             let mut state = self.inner.state.lock();
-            state.process_state = ProcessState::Running {
-                since: Instant::now(),
+            state.selected_monitor_index = index;
+            state.status_message = format!("Monitor set to {}", index + 1);
+        }
+        self.publish(Event::ConfigChanged {
+            field: ConfigField::SelectedMonitor,
+        });
+        self.save_config()?;
+        Ok(())
+    }
+
+    pub fn set_status(&self, message: impl Into<String>) {
+        let mut state = self.inner.state.lock();
+        state.status_message = message.into();
+    }
+
+    /// Config file of the profile in use
+    pub fn config_file_path(&self) -> Option<PathBuf> {
+        profiles::profile_path(&self.inner.state.lock().active_profile).ok()
+    }
+
+    pub fn logs_directory(&self) -> Option<PathBuf> {
+        app_paths::config_dir().ok().map(|dir| dir.join("logs"))
+    }
+
+    pub fn start_monitoring(&self) -> Result<()> {
+        self.shutdown_detection_runtime();
+
+        let setup = {
+            let state = self.inner.state.lock();
+            if state.process_state.is_running() {
+                drop(state);
+                self.set_status("Detection already running");
+                return Ok(());
+            }
+
+            state
+                .validate_region()
+                .map_err(|err| anyhow!(err.to_string()))?;
+            state
+                .validate_music_selection()
+                .map_err(|err| anyhow!(err.to_string()))?;
+
+            if state.goal_playlist_indices().is_empty() {
+                return Err(anyhow!(
+                    "Select at least one music file before starting detection"
+                ));
+            }
+
+            let mut team_profiles = Vec::new();
+            for profile in state.team_profiles.clone() {
+                let playlist_indices = state.playlist_indices(&profile.goal_music_indices);
+                if playlist_indices.is_empty() {
+                    return Err(anyhow!(
+                        "Select at least one music file for {}",
+                        profile.team.display_name
+                    ));
+                }
+                let celebration = CelebrationSetup::new(
+                    &state,
+                    playlist_indices,
+                    profile.music_volume,
+                    profile.ambiance_volume,
+                    profile.ambiance_path.clone(),
+                );
+                let derby = profile
+                    .derby_celebration
+                    .as_ref()
+                    .map(|derby| celebration.with_override(&state, derby));
+                let competitions = profile
+                    .competition_overrides
+                    .iter()
+                    .map(|cup| {
+                        (
+                            cup.competition.clone(),
+                            celebration.with_override(&state, &cup.celebration),
+                        )
+                    })
+                    .collect();
+                team_profiles.push(ProfileSetup {
+                    profile,
+                    celebration,
+                    derby,
+                    competitions,
+                });
+            }
+
+            // Manual goals without a team fall back to the first monitored
+            // team's celebration when no default playlist is set
+            let default_playlist = state.playlist_indices(&state.goal_music_indices);
+            let celebration = match team_profiles.first() {
+                Some(first) if default_playlist.is_empty() => first.celebration.clone(),
+                _ => CelebrationSetup::new(
+                    &state,
+                    default_playlist,
+                    state.music_volume,
+                    state.ambiance_volume,
+                    state.goal_ambiance_path.clone(),
+                ),
+            };
+
+            DetectionSetup {
+                celebration,
+                team_profiles,
+                capture_region: state.capture_region,
+                competition_region: state.competition_region,
+                monitor_index: state.selected_monitor_index,
+                ocr_threshold: state.ocr_threshold,
+                enable_morph_open: state.enable_morph_open,
+                debounce_ms: state.debounce_ms,
+                team_match_threshold: state.team_match_threshold,
+                ambiance_enabled: state.ambiance_enabled,
+                music_length_ms: state.music_length_ms,
+                ambiance_length_ms: state.ambiance_length_ms,
+                custom_goal_phrases: state.custom_goal_phrases.clone(),
+                selected_language: state.selected_language,
+                flight_recorder_seconds: state.flight_recorder_seconds,
+                dump_recorder_on_trigger: state.dump_recorder_on_trigger,
+                variation_stats: Arc::clone(&self.inner.variation_stats),
+            }
+        };
+
+        let team_database = self.team_database();
+        let (default_celebration, monitored_teams) =
+            match load_celebrations(&setup, team_database.as_ref()) {
+                Ok(loaded) => loaded,
+                Err(err) => {
+                    self.mark_start_failure(format!("{err:#}"));
+                    return Err(err);
+                }
             };
+
+        // Names the scoring club when no team is monitored, and tells the
+        // opponent's goals apart from near misses when one is
+        let team_identifier = team_database
+            .as_ref()
+            .map(|db| TeamIdentifier::with_threshold(db, setup.team_match_threshold));
+
+        let status_message = if monitored_teams.is_empty() {
+            format!(
+                "Monitoring goals — will play {}",
+                default_celebration.describe()
+            )
+        } else {
+            let names: Vec<&str> = monitored_teams
+                .iter()
+                .map(|monitored| monitored.team.display_name.as_str())
+                .collect();
+            format!("Monitoring goals for {}", names.join(", "))
+        };
+
+        {
+            let mut state = self.inner.state.lock();
+            set_process_state(
+                &mut state,
+                &self.inner.events,
+                ProcessState::Running {
+                    since: Instant::now(),
+                },
+            );
             state.detection_count = 0;
-            state.status_message = format!("Monitoring goals — will play '{}'", track_name);
+            state.detection_metrics = DetectionMetrics::default();
+            state.ocr_debug_frame = None;
+            state.current_track = None;
+            state.last_goal_at = None;
+            state.last_scoring_team = None;
+            state.goals_by_team.clear();
+            state.last_unmatched_team_text = None;
+            state.variation_suggestion = None;
+            state.current_match = None;
+            state.current_competition = None;
+            state.status_message = status_message;
         }
 
         let (cmd_tx, cmd_rx) = unbounded();
@@ -618,14 +1543,16 @@ impl GuiController {
         }
 
         let state_arc = Arc::clone(&self.inner.state);
+        let events = self.events();
         let handle = thread::spawn(move || {
             if let Err(err) = run_detection_loop(
                 state_arc,
                 cmd_rx,
                 setup,
-                music_bytes_list,
-                ambiance_bytes,
-                team_profile,
+                default_celebration,
+                monitored_teams,
+                team_identifier,
+                events,
             ) {
                 error!("Detection loop exited with error: {err:#}");
             }
@@ -640,7 +1567,7 @@ impl GuiController {
     pub fn stop_monitoring(&self) -> Result<()> {
         self.shutdown_detection_runtime();
         let mut state = self.inner.state.lock();
-        state.process_state = ProcessState::Stopped;
+        set_process_state(&mut state, &self.inner.events, ProcessState::Stopped);
         state.status_message = "Monitoring stopped".to_string();
         Ok(())
     }
@@ -656,6 +1583,118 @@ impl GuiController {
         Ok(())
     }
 
+    /// Celebrate a goal as if its banner had been detected
+    ///
+    /// `team` names a monitored team by display name or key; without it the
+    /// default celebration plays.
+    pub fn trigger_manual_goal(&self, team: Option<&str>) -> Result<()> {
+        let team = match team {
+            Some(name) => {
+                let state = self.inner.state.lock();
+                let profile = state
+                    .team_profiles
+                    .iter()
+                    .find(|profile| {
+                        profile.team.display_name.eq_ignore_ascii_case(name)
+                            || profile.team.team_key.eq_ignore_ascii_case(name)
+                    })
+                    .ok_or_else(|| anyhow!("'{name}' is not a monitored team"))?;
+                Some(profile.team.clone())
+            }
+            None => None,
+        };
+        self.send_detection_command(
+            DetectionCommand::ManualGoal(team),
+            "Start monitoring to trigger a goal",
+        )
+    }
+
+    /// Play the library track at `index` as goal music
+    pub fn play_library_track(&self, index: usize) -> Result<()> {
+        let entry = self
+            .inner
+            .state
+            .lock()
+            .music_list
+            .get(index)
+            .cloned()
+            .ok_or_else(|| anyhow!("No library track at index {index}"))?;
+        let bytes = fs::read(&entry.path)
+            .with_context(|| format!("Failed to read audio {}", entry.path.display()))?;
+        self.send_detection_command(
+            DetectionCommand::PlayTrack {
+                name: entry.name,
+                bytes: Arc::new(bytes),
+            },
+            "Start monitoring to play goal music",
+        )
+    }
+
+    fn send_detection_command(&self, command: DetectionCommand, not_running: &str) -> Result<()> {
+        let tx_slot = self.inner.detection_cmd_tx.lock();
+        let tx = tx_slot.as_ref().ok_or_else(|| anyhow!("{not_running}"))?;
+        tx.send(command)
+            .map_err(|e| anyhow!("Failed to send detection command: {}", e))
+    }
+
+    /// Ask the detection loop to write its flight recorder to disk
+    pub fn dump_flight_recorder(&self) -> Result<()> {
+        {
+            let tx_slot = self.inner.detection_cmd_tx.lock();
+            let tx = tx_slot
+                .as_ref()
+                .ok_or_else(|| anyhow!("Start monitoring to record detection frames"))?;
+            tx.send(DetectionCommand::DumpRecorder)
+                .map_err(|e| anyhow!("Failed to send dump command: {}", e))?;
+        }
+        self.set_status("Saving flight recorder...");
+        Ok(())
+    }
+
+    /// Mark the last goal trigger as wrong, stop its music and save it as a sample
+    pub fn mark_false_positive(&self) -> Result<()> {
+        self.send_sample_label(SampleLabel::FalsePositive)
+    }
+
+    /// Save the last few seconds as a sample containing a goal that was not detected
+    pub fn mark_missed_goal(&self) -> Result<()> {
+        self.send_sample_label(SampleLabel::MissedGoal)
+    }
+
+    fn send_sample_label(&self, label: SampleLabel) -> Result<()> {
+        {
+            let tx_slot = self.inner.detection_cmd_tx.lock();
+            let tx = tx_slot
+                .as_ref()
+                .ok_or_else(|| anyhow!("Start monitoring to label detection samples"))?;
+            tx.send(DetectionCommand::LabelSample(label))
+                .map_err(|e| anyhow!("Failed to send label command: {}", e))?;
+        }
+        self.set_status(format!("Saving {} sample...", label.description()));
+        Ok(())
+    }
+
+    pub fn sample_library_directory(&self) -> Option<PathBuf> {
+        sample_library::samples_dir().ok()
+    }
+
+    pub fn flight_recorder_directory(&self) -> Option<PathBuf> {
+        flight_recorder::dumps_dir().ok()
+    }
+
+    pub fn set_dump_recorder_on_trigger(&self, enabled: bool) -> Result<()> {
+        {
+            let mut state = self.inner.state.lock();
+            state.dump_recorder_on_trigger = enabled;
+            state.status_message = if enabled {
+                "Flight recorder will be dumped on every goal".to_string()
+            } else {
+                "Automatic flight recorder dumps disabled".to_string()
+            };
+        }
+        self.save_config()
+    }
+
     pub fn set_ambiance_enabled(&self, enabled: bool) -> Result<()> {
         {
             let mut state = self.inner.state.lock();
@@ -666,17 +1705,38 @@ impl GuiController {
                 "Ambiance disabled".to_string()
             };
         }
+        self.publish(Event::ConfigChanged {
+            field: ConfigField::AmbianceEnabled,
+        });
         self.save_config()
     }
 
     pub fn set_goal_ambiance_path(&self, path: Option<PathBuf>) -> Result<()> {
         {
             let mut state = self.inner.state.lock();
-            state.goal_ambiance_path = path.as_ref().map(|p| p.to_string_lossy().to_string());
-            state.status_message = match &state.goal_ambiance_path {
+            let path = path.map(|p| p.to_string_lossy().to_string());
+            state.status_message = match &path {
                 Some(p) => format!("Ambiance sound set to {}", p),
                 None => "Ambiance sound cleared".to_string(),
             };
+            *state.celebration_mut().ambiance_path = path;
+        }
+        self.save_config()
+    }
+
+    /// Set the sound played when the selected team concedes
+    pub fn set_conceded_sound_path(&self, path: Option<PathBuf>) -> Result<()> {
+        {
+            let mut state = self.inner.state.lock();
+            let profile = state
+                .focused_profile_mut()
+                .ok_or_else(|| anyhow!("Select a team to set its conceded sound"))?;
+            profile.conceded_sound_path = path.map(|p| p.to_string_lossy().to_string());
+            let message = match &profile.conceded_sound_path {
+                Some(p) => format!("{} conceding will play {p}", profile.team.display_name),
+                None => format!("Conceded sound for {} removed", profile.team.display_name),
+            };
+            state.status_message = message;
         }
         self.save_config()
     }
@@ -684,24 +1744,26 @@ impl GuiController {
     pub fn set_music_volume(&self, volume: f32) -> Result<()> {
         {
             let mut state = self.inner.state.lock();
-            state.music_volume = volume.clamp(0.0, 1.0);
-            state.status_message = format!(
-                "Music volume set to {}%",
-                (state.music_volume * 100.0).round()
-            );
+            let volume = volume.clamp(0.0, 1.0);
+            *state.celebration_mut().music_volume = volume;
+            state.status_message = format!("Music volume set to {}%", (volume * 100.0).round());
         }
+        self.publish(Event::ConfigChanged {
+            field: ConfigField::MusicVolume,
+        });
         self.save_config()
     }
 
     pub fn set_ambiance_volume(&self, volume: f32) -> Result<()> {
         {
             let mut state = self.inner.state.lock();
-            state.ambiance_volume = volume.clamp(0.0, 1.0);
-            state.status_message = format!(
-                "Ambiance volume set to {}%",
-                (state.ambiance_volume * 100.0).round()
-            );
+            let volume = volume.clamp(0.0, 1.0);
+            *state.celebration_mut().ambiance_volume = volume;
+            state.status_message = format!("Ambiance volume set to {}%", (volume * 100.0).round());
         }
+        self.publish(Event::ConfigChanged {
+            field: ConfigField::AmbianceVolume,
+        });
         self.save_config()
     }
 
@@ -714,6 +1776,9 @@ impl GuiController {
                 state.music_length_ms / 1000
             );
         }
+        self.publish(Event::ConfigChanged {
+            field: ConfigField::MusicLength,
+        });
         self.save_config()
     }
 
@@ -726,6 +1791,9 @@ impl GuiController {
                 state.ambiance_length_ms / 1000
             );
         }
+        self.publish(Event::ConfigChanged {
+            field: ConfigField::AmbianceLength,
+        });
         self.save_config()
     }
 
@@ -740,6 +1808,9 @@ impl GuiController {
                 state.status_message = format!("OCR threshold set to {}", clamped);
             }
         }
+        self.publish(Event::ConfigChanged {
+            field: ConfigField::OcrThreshold,
+        });
         self.save_config()
     }
 
@@ -748,12 +1819,28 @@ impl GuiController {
         self.set_ocr_threshold(current as i16 + delta)
     }
 
+    /// Set how closely detected text must match a team name (0.5-1.0)
+    pub fn set_team_match_threshold(&self, threshold: f32) -> Result<()> {
+        {
+            let mut state = self.inner.state.lock();
+            state.team_match_threshold = threshold.clamp(0.5, 1.0);
+            state.status_message = format!(
+                "Team match threshold set to {:.0}%",
+                state.team_match_threshold * 100.0
+            );
+        }
+        self.save_config()
+    }
+
     pub fn set_debounce_ms(&self, debounce_ms: u64) -> Result<()> {
         {
             let mut state = self.inner.state.lock();
             state.debounce_ms = debounce_ms.clamp(100, 60_000);
             state.status_message = format!("Debounce set to {} ms", state.debounce_ms);
         }
+        self.publish(Event::ConfigChanged {
+            field: ConfigField::DebounceMs,
+        });
         self.save_config()
     }
 
@@ -773,6 +1860,9 @@ impl GuiController {
                 "Morphological opening disabled".to_string()
             };
         }
+        self.publish(Event::ConfigChanged {
+            field: ConfigField::EnableMorphOpen,
+        });
         self.save_config()
     }
 
@@ -786,11 +1876,14 @@ impl GuiController {
                 "Auto update checks disabled".to_string()
             };
         }
+        self.publish(Event::ConfigChanged {
+            field: ConfigField::AutoCheckUpdates,
+        });
         self.save_config()
     }
 
     pub fn adjust_capture_region(&self, index: usize, delta: i32) -> Result<()> {
-        {
+        let region = {
             let mut state = self.inner.state.lock();
             if let Some(value) = state.capture_region.get_mut(index) {
                 let min_value = if index >= 2 { 1 } else { 0 };
@@ -804,7 +1897,9 @@ impl GuiController {
                     state.capture_region[3]
                 );
             }
-        }
+            state.capture_region
+        };
+        self.publish(Event::RegionChanged { region });
         self.save_config()
     }
 
@@ -900,14 +1995,248 @@ impl GuiController {
         });
     }
 
-    fn save_config(&self) -> Result<()> {
-        let state = self.inner.state.lock();
-        let config = Config {
+    pub fn profile_names(&self) -> Vec<String> {
+        profiles::list().unwrap_or_else(|err| {
+            warn!("Failed to list profiles: {err}");
+            vec![self.inner.state.lock().active_profile.clone()]
+        })
+    }
+
+    /// Replace every setting with those of the profile `name`
+    ///
+    /// Detection is stopped while the profile is applied and restarted with
+    /// its settings if it was running.
+    pub fn switch_profile(&self, name: &str) -> Result<()> {
+        self.flush_settings();
+        let path = profiles::profile_path(name)?;
+        if !path.exists() {
+            return Err(anyhow!("Profile '{name}' doesn't exist"));
+        }
+        let (config, problem) = Config::load_from(&path)
+            .map_err(|err| anyhow!("failed to load profile '{name}': {err}"))?;
+
+        // Only remembered once the settings are in place; a failed restart
+        // still leaves them applied
+        let restarted = self.replace_settings(&config, Some(name));
+        profiles::set_active(name)?;
+        restarted?;
+
+        let mut state = self.inner.state.lock();
+        state.load_problems.extend(problem.map(|p| p.to_string()));
+        state.status_message = format!("Switched to profile '{name}'");
+        Ok(())
+    }
+
+    /// Apply every setting in `config` at once, restarting detection with
+    /// them if it was running
+    ///
+    /// `profile`, when given, becomes the active profile under the same lock,
+    /// so no save in between writes one profile's settings into the other.
+    fn replace_settings(&self, config: &Config, profile: Option<&str>) -> Result<()> {
+        let was_running = self.inner.state.lock().process_state.is_running();
+        if was_running {
+            self.shutdown_detection_runtime();
+        }
+        {
+            let mut state = self.inner.state.lock();
+            apply_config(&mut state, config);
+            if let Some(profile) = profile {
+                state.active_profile = profile.to_string();
+            }
+            state.settings_generation += 1;
+        }
+
+        if was_running {
+            self.start_monitoring()
+                .context("settings applied, but detection could not restart")?;
+        }
+        Ok(())
+    }
+
+    /// Restart detection so it picks up changed teams or phrases
+    fn restart_detection_if_running(&self) -> Result<()> {
+        if !self.inner.state.lock().process_state.is_running() {
+            return Ok(());
+        }
+        self.shutdown_detection_runtime();
+        set_process_state(
+            &mut self.inner.state.lock(),
+            &self.inner.events,
+            ProcessState::Stopped,
+        );
+        self.start_monitoring()
+            .context("changes applied, but detection could not restart")
+    }
+
+    /// Watch the active config, teams.json and the phrase override of the
+    /// selected language, applying edits made outside the app
+    pub fn start_file_watcher(&self) {
+        let controller = self.clone();
+        thread::spawn(move || {
+            let mut watcher = FileWatcher::new();
+            loop {
+                let files = controller.watched_files();
+                let paths: Vec<PathBuf> = files.iter().map(|(path, _)| path.clone()).collect();
+                watcher.retain(&paths);
+                for path in &paths {
+                    watcher.watch(path);
+                }
+                for path in watcher.changed() {
+                    if let Some((_, file)) = files.iter().find(|(watched, _)| *watched == path) {
+                        controller.reload_file(*file, &path);
+                    }
+                }
+                thread::sleep(RELOAD_POLL_INTERVAL);
+            }
+        });
+    }
+
+    fn watched_files(&self) -> Vec<(PathBuf, WatchedFile)> {
+        let (profile, language) = {
+            let state = self.inner.state.lock();
+            (state.active_profile.clone(), state.selected_language)
+        };
+        let mut files = Vec::new();
+        if let Ok(path) = profiles::profile_path(&profile) {
+            files.push((path, WatchedFile::Config));
+        }
+        if let Ok(path) = TeamDatabase::database_path() {
+            files.push((path, WatchedFile::Teams));
+        }
+        if let Some(path) = i18n_loader::override_path(language) {
+            files.push((path, WatchedFile::Phrases(language)));
+        }
+        files
+    }
+
+    /// Apply a watched file changed on disk, unless the app wrote it itself
+    ///
+    /// Edits that don't parse are reported and the current settings kept.
+    fn reload_file(&self, file: WatchedFile, path: &Path) {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string());
+        let content = match fs::read_to_string(path) {
+            Ok(content) => Some(content),
+            // A removed phrase override falls back to the built-in phrases;
+            // other settings files are rewritten on the next save
+            Err(_) if matches!(file, WatchedFile::Phrases(_)) => None,
+            Err(_) => return,
+        };
+        if let Some(content) = &content {
+            if persistence::is_own_write(path, content.as_bytes()) {
+                return;
+            }
+        }
+
+        let result = match (file, content) {
+            (WatchedFile::Config, Some(content)) => Config::parse_read_only(&content)
+                .map_err(|err| anyhow!("{err}"))
+                .and_then(|config| {
+                    let applied = self.replace_settings(&config, None);
+                    // Supersede a batched save of the settings from before
+                    // the edit, which would otherwise write over it
+                    self.save_config()?;
+                    applied
+                }),
+            (WatchedFile::Teams, Some(content)) => serde_json::from_str::<TeamDatabase>(&content)
+                .map_err(anyhow::Error::from)
+                .and_then(|database| {
+                    *self.inner.team_database.lock() = Some(database);
+                    self.restart_detection_if_running()
+                }),
+            (WatchedFile::Phrases(language), content) => content
+                .map(|content| i18n_loader::parse_phrases(language, &content).map(|_| ()))
+                .unwrap_or(Ok(()))
+                .map_err(anyhow::Error::from)
+                .and_then(|()| self.restart_detection_if_running()),
+            (_, None) => return,
+        };
+
+        let mut state = self.inner.state.lock();
+        match result {
+            Ok(()) => {
+                info!("Reloaded {} after an outside edit", path.display());
+                state.status_message = format!("Reloaded {name} from disk");
+                state.settings_generation += 1;
+            }
+            Err(err) => {
+                warn!("Not reloading {}: {err:#}", path.display());
+                state.load_problems.push(format!(
+                    "{name} was edited but not reloaded ({err:#}); keeping the previous settings"
+                ));
+            }
+        }
+    }
+
+    /// Create a profile with default settings and switch to it
+    pub fn create_profile(&self, name: &str) -> Result<()> {
+        let name = profiles::create(name, &Config::default())?;
+        self.switch_profile(&name)
+    }
+
+    /// Copy the current settings into a new profile and switch to it
+    pub fn duplicate_profile(&self, name: &str) -> Result<()> {
+        let name = profiles::create(name, &self.current_config())?;
+        self.switch_profile(&name)
+    }
+
+    pub fn rename_active_profile(&self, name: &str) -> Result<()> {
+        let current = self.inner.state.lock().active_profile.clone();
+        self.flush_settings();
+        let name = profiles::rename(&current, name)?;
+        let mut state = self.inner.state.lock();
+        state.active_profile = name.clone();
+        state.status_message = format!("Renamed profile '{current}' to '{name}'");
+        Ok(())
+    }
+
+    /// Delete a profile, switching to the default one if it is in use
+    pub fn delete_profile(&self, name: &str) -> Result<()> {
+        let is_active = self.inner.state.lock().active_profile == name;
+        self.flush_settings();
+        profiles::delete(name)?;
+        if is_active {
+            self.switch_profile(profiles::DEFAULT_PROFILE)?;
+        }
+        self.set_status(format!("Deleted profile '{name}'"));
+        Ok(())
+    }
+
+    /// Queue the current settings to be written to the active profile
+    ///
+    /// Writes are batched on a background thread; failures show up as the
+    /// status message.
+    fn save_config(&self) -> Result<()> {
+        let config = self.current_config();
+        let profile = self.inner.state.lock().active_profile.clone();
+        self.inner
+            .settings_writer
+            .save(profiles::profile_path(&profile)?, config);
+        Ok(())
+    }
+
+    /// Write settings changes still waiting to be saved
+    pub fn flush_settings(&self) {
+        self.inner.settings_writer.flush();
+    }
+
+    /// Hide the settings file problems shown since startup
+    pub fn dismiss_load_problems(&self) {
+        self.inner.state.lock().load_problems.clear();
+    }
+
+    /// Settings in use, as they are stored in a profile
+    fn current_config(&self) -> Config {
+        let state = self.inner.state.lock();
+        Config {
+            schema_version: config_migrations::CURRENT_SCHEMA_VERSION,
             capture_region: state.capture_region,
             ocr_threshold: state.ocr_threshold,
             debounce_ms: state.debounce_ms,
             enable_morph_open: state.enable_morph_open,
-            bench_frames: DEFAULT_BENCH_FRAMES,
+            bench_frames: state.bench_frames,
             music_list: state
                 .music_list
                 .iter()
@@ -925,27 +2254,26 @@ impl GuiController {
             ambiance_volume: state.ambiance_volume,
             music_length_ms: state.music_length_ms,
             ambiance_length_ms: state.ambiance_length_ms,
-            selected_team: state.selected_team.clone(),
+            team_profiles: state.team_profiles.clone(),
+            team_match_threshold: state.team_match_threshold,
             auto_check_updates: state.auto_check_updates,
             skipped_version: state.skipped_version.clone(),
             selected_monitor_index: state.selected_monitor_index,
             selected_language: state.selected_language,
             custom_goal_phrases: state.custom_goal_phrases.clone(),
-        };
-        drop(state);
-
-        config
-            .save()
-            .map_err(|err| anyhow!("failed to save config: {err}"))
+            flight_recorder_seconds: state.flight_recorder_seconds,
+            dump_recorder_on_trigger: state.dump_recorder_on_trigger,
+            competition_region: state.competition_region,
+        }
     }
 }
 
-fn apply_config(state: &Arc<Mutex<AppState>>, config: &Config) {
-    let mut st = state.lock();
+fn apply_config(st: &mut AppState, config: &Config) {
     st.capture_region = config.capture_region;
     st.ocr_threshold = config.ocr_threshold;
     st.debounce_ms = config.debounce_ms;
     st.enable_morph_open = config.enable_morph_open;
+    st.bench_frames = config.bench_frames;
     st.music_list = config
         .music_list
         .iter()
@@ -964,34 +2292,290 @@ fn apply_config(state: &Arc<Mutex<AppState>>, config: &Config) {
     st.ambiance_volume = config.ambiance_volume;
     st.music_length_ms = config.music_length_ms;
     st.ambiance_length_ms = config.ambiance_length_ms;
-    st.selected_team = config.selected_team.clone();
+    st.team_profiles = config.team_profiles.clone();
+    // Keep showing the same team when a reload still monitors it
+    let focused = st
+        .focused_team
+        .take()
+        .filter(|team| config.team_profiles.iter().any(|p| p.is_for(team)));
+    st.focused_team = focused.or_else(|| {
+        config
+            .team_profiles
+            .first()
+            .map(|profile| profile.team.clone())
+    });
+    st.team_match_threshold = config.team_match_threshold.clamp(0.5, 1.0);
     st.auto_check_updates = config.auto_check_updates;
     st.skipped_version = config.skipped_version.clone();
     st.selected_monitor_index = config.selected_monitor_index;
     st.selected_language = config.selected_language;
     st.custom_goal_phrases = config.custom_goal_phrases.clone();
+    st.flight_recorder_seconds = config.flight_recorder_seconds;
+    st.dump_recorder_on_trigger = config.dump_recorder_on_trigger;
+    st.competition_region = config.competition_region;
     st.status_message = "Ready".to_string();
     st.process_state = ProcessState::Stopped;
     st.preview_image_path = None;
 }
 
 struct DetectionSetup {
-    playlist_indices: Vec<usize>,
-    playlist_entries: Vec<MusicEntry>,
+    /// Played for every goal when no team is monitored
+    celebration: CelebrationSetup,
+    team_profiles: Vec<ProfileSetup>,
     capture_region: [u32; 4],
+    competition_region: Option<[u32; 4]>,
     monitor_index: usize,
     ocr_threshold: u8,
     enable_morph_open: bool,
     debounce_ms: u64,
-    selected_team: Option<SelectedTeam>,
-    music_volume: f32,
-    ambiance_volume: f32,
-    ambiance_path: Option<String>,
+    team_match_threshold: f32,
     ambiance_enabled: bool,
     music_length_ms: u64,
     ambiance_length_ms: u64,
     custom_goal_phrases: Vec<String>,
     selected_language: Language,
+    flight_recorder_seconds: u64,
+    dump_recorder_on_trigger: bool,
+    /// Counts a goal's matched variation
+    variation_stats: Arc<Mutex<Option<VariationStats>>>,
+}
+
+/// Celebrations chosen for one monitored team
+struct ProfileSetup {
+    profile: TeamProfile,
+    celebration: CelebrationSetup,
+    /// Used when the current opponent is a rival
+    derby: Option<CelebrationSetup>,
+    /// Used in matches of these competitions, checked in order
+    competitions: Vec<(String, CelebrationSetup)>,
+}
+
+/// Announce a sound that could not be played
+fn publish_audio_error(events: &EventBus, err: &dyn std::fmt::Display) {
+    events.publish(Event::ErrorOccurred {
+        message: err.to_string(),
+        context: "audio".to_string(),
+    });
+}
+
+/// Move detection to `new_state`, announcing it if it changed
+fn set_process_state(state: &mut AppState, events: &EventBus, new_state: ProcessState) {
+    let old_state = state.process_state;
+    state.process_state = new_state;
+    if old_state.name() != new_state.name() {
+        events.publish(Event::ProcessStateChanged {
+            old_state,
+            new_state,
+        });
+    }
+}
+
+/// Goal music and ambiance chosen for one celebration
+#[derive(Clone)]
+struct CelebrationSetup {
+    playlist_indices: Vec<usize>,
+    playlist_entries: Vec<MusicEntry>,
+    music_volume: f32,
+    ambiance_volume: f32,
+    ambiance_path: Option<String>,
+}
+
+impl CelebrationSetup {
+    fn new(
+        state: &AppState,
+        playlist_indices: Vec<usize>,
+        music_volume: f32,
+        ambiance_volume: f32,
+        ambiance_path: Option<String>,
+    ) -> Self {
+        let playlist_entries = playlist_indices
+            .iter()
+            .filter_map(|&idx| state.music_list.get(idx).cloned())
+            .collect();
+        Self {
+            playlist_indices,
+            playlist_entries,
+            music_volume,
+            ambiance_volume,
+            ambiance_path,
+        }
+    }
+
+    /// This celebration with a special match's playlist or ambiance swapped in
+    fn with_override(&self, state: &AppState, special: &CelebrationOverride) -> Self {
+        let mut celebration = self.clone();
+        let playlist_indices = state.playlist_indices(&special.goal_music_indices);
+        if !special.goal_music_indices.is_empty() && !playlist_indices.is_empty() {
+            celebration = Self::new(
+                state,
+                playlist_indices,
+                self.music_volume,
+                self.ambiance_volume,
+                self.ambiance_path.clone(),
+            );
+        }
+        if special.ambiance_path.is_some() {
+            celebration.ambiance_path = special.ambiance_path.clone();
+        }
+        celebration
+    }
+
+    /// Read the celebration's audio files into memory
+    fn load(&self, ambiance_enabled: bool) -> Result<Celebration> {
+        let mut music_bytes = Vec::new();
+        for entry in &self.playlist_entries {
+            let bytes = fs::read(&entry.path)
+                .with_context(|| format!("Failed to read audio {}", entry.path.display()))?;
+            music_bytes.push(Arc::new(bytes));
+        }
+        if music_bytes.is_empty() {
+            return Err(anyhow!("No goal music tracks available in playlist"));
+        }
+
+        let ambiance_bytes = match &self.ambiance_path {
+            Some(path) if ambiance_enabled => {
+                Some(Arc::new(fs::read(path).with_context(|| {
+                    format!("Failed to read ambiance {}", path)
+                })?))
+            }
+            _ => None,
+        };
+
+        Ok(Celebration {
+            playlist_indices: self.playlist_indices.clone(),
+            playlist_entries: self.playlist_entries.clone(),
+            music_bytes,
+            ambiance_bytes,
+            music_volume: self.music_volume,
+            ambiance_volume: self.ambiance_volume,
+        })
+    }
+}
+
+/// Goal music and ambiance loaded for the detection loop
+struct Celebration {
+    playlist_indices: Vec<usize>,
+    playlist_entries: Vec<MusicEntry>,
+    music_bytes: Vec<Arc<Vec<u8>>>,
+    ambiance_bytes: Option<Arc<Vec<u8>>>,
+    music_volume: f32,
+    ambiance_volume: f32,
+}
+
+impl Celebration {
+    /// "'Track'" or "N tracks (random playlist)" for status messages
+    fn describe(&self) -> String {
+        if self.playlist_entries.len() == 1 {
+            format!("'{}'", self.playlist_entries[0].name)
+        } else {
+            format!("{} tracks (random playlist)", self.playlist_entries.len())
+        }
+    }
+}
+
+/// A team the detection loop celebrates, with its own audio
+struct MonitoredTeam {
+    team: SelectedTeam,
+    matcher: TeamMatcher,
+    celebration: Celebration,
+    /// Played when the other club of the current match scores
+    conceded_bytes: Option<Arc<Vec<u8>>>,
+    rivals: Vec<SelectedTeam>,
+    derby: Option<Celebration>,
+    competitions: Vec<(String, Celebration)>,
+}
+
+impl MonitoredTeam {
+    fn is(&self, team: &SelectedTeam) -> bool {
+        self.team.league == team.league && self.team.team_key == team.team_key
+    }
+
+    /// Celebration for a goal in the current match, with what made it special
+    ///
+    /// A competition celebration wins over the derby one, so a cup final
+    /// against a rival plays the cup final music.
+    fn celebration_for(
+        &self,
+        fixture: Option<&Fixture>,
+        competition: Option<&str>,
+    ) -> (&Celebration, Option<String>) {
+        if let Some((name, celebration)) = competition.and_then(|competition| {
+            self.competitions
+                .iter()
+                .find(|(name, _)| team_matcher::mentions(competition, name))
+        }) {
+            return (celebration, Some(name.clone()));
+        }
+
+        let opponent = fixture.and_then(|fixture| fixture.opponent_of(&self.team));
+        if let (Some(derby), Some(opponent)) = (&self.derby, opponent) {
+            let is_rival = self.rivals.iter().any(|rival| {
+                rival.league == opponent.league && rival.team_key == opponent.team_key
+            });
+            if is_rival {
+                return (
+                    derby,
+                    Some(format!("derby vs {}", opponent.team.display_name)),
+                );
+            }
+        }
+
+        (&self.celebration, None)
+    }
+}
+
+/// Load the default celebration and one per monitored team
+///
+/// Teams missing from the database are skipped with a warning.
+fn load_celebrations(
+    setup: &DetectionSetup,
+    team_database: Option<&TeamDatabase>,
+) -> Result<(Celebration, Vec<MonitoredTeam>)> {
+    let default_celebration = setup.celebration.load(setup.ambiance_enabled)?;
+
+    let mut monitored = Vec::new();
+    for ProfileSetup {
+        profile,
+        celebration,
+        derby,
+        competitions,
+    } in &setup.team_profiles
+    {
+        let Some(team) =
+            team_database.and_then(|db| db.find_team(&profile.team.league, &profile.team.team_key))
+        else {
+            warn!(
+                "{} is no longer in the team database, not monitoring it",
+                profile.team.display_name
+            );
+            continue;
+        };
+        let conceded_bytes = match &profile.conceded_sound_path {
+            Some(path) => {
+                Some(Arc::new(fs::read(path).with_context(|| {
+                    format!("Failed to read conceded sound {}", path)
+                })?))
+            }
+            None => None,
+        };
+        monitored.push(MonitoredTeam {
+            team: profile.team.clone(),
+            matcher: TeamMatcher::with_threshold(&team, setup.team_match_threshold),
+            celebration: celebration.load(setup.ambiance_enabled)?,
+            conceded_bytes,
+            rivals: profile.rivals.clone(),
+            derby: derby
+                .as_ref()
+                .map(|derby| derby.load(setup.ambiance_enabled))
+                .transpose()?,
+            competitions: competitions
+                .iter()
+                .map(|(name, cup)| Ok((name.clone(), cup.load(setup.ambiance_enabled)?)))
+                .collect::<Result<_>>()?,
+        });
+    }
+
+    Ok((default_celebration, monitored))
 }
 
 pub struct RegionCapture {
@@ -1001,90 +2585,471 @@ pub struct RegionCapture {
     pub device_scale: f32,
 }
 
+impl RegionCapture {
+    /// Convert a rectangle in screenshot (physical) pixels to a capture region
+    ///
+    /// Mirrors the conversion used by the manual region selector.
+    pub fn to_logical(&self, physical: [u32; 4]) -> [u32; 4] {
+        let scale = self.device_scale.max(0.0001);
+        let max_w = self.logical_size.0.max(1);
+        let max_h = self.logical_size.1.max(1);
+
+        let x = ((physical[0] as f32 / scale).round() as u32).min(max_w - 1);
+        let y = ((physical[1] as f32 / scale).round() as u32).min(max_h - 1);
+        let w = ((physical[2] as f32 / scale).round() as u32).clamp(1, max_w - x);
+        let h = ((physical[3] as f32 / scale).round() as u32).clamp(1, max_h - y);
+
+        [x, y, w, h]
+    }
+}
+
+/// Capture region proposed by `GuiController::auto_find_capture_region`
+pub struct RegionProposal {
+    pub capture: RegionCapture,
+    /// Padded rectangle in screenshot (physical) pixels
+    pub physical_rect: [u32; 4],
+    /// The same rectangle converted to a capture region
+    pub region: [u32; 4],
+    /// OCR text of the line that matched
+    pub matched_text: String,
+}
+
+/// A team's celebration for special matches
+#[derive(Clone, Debug, PartialEq)]
+pub enum SpecialCelebration {
+    /// Matches against a rival
+    Derby,
+    /// Matches in the named competition
+    Competition(String),
+}
+
+impl SpecialCelebration {
+    pub fn label(&self) -> String {
+        match self {
+            SpecialCelebration::Derby => "derby".to_string(),
+            SpecialCelebration::Competition(name) => format!("'{name}'"),
+        }
+    }
+}
+
+/// Summary of a team pack shown before importing it
+pub struct TeamPackPreview {
+    pub path: PathBuf,
+    pub name: String,
+    pub team_count: usize,
+    /// Pack teams that already exist in the database
+    pub conflicts: usize,
+}
+
 fn preview_image_path() -> Result<PathBuf> {
-    let base = config_dir().ok_or_else(|| anyhow!("Unable to locate config directory"))?;
-    let dir = base.join("FMGoalMusic").join("previews");
-    fs::create_dir_all(&dir).context("Failed to create preview directory")?;
+    let dir = app_paths::config_subdir("previews").context("Failed to create preview directory")?;
     Ok(dir.join("capture_preview.png"))
 }
 
 fn preview_image_path_with_generation(generation: u32) -> Result<PathBuf> {
-    let base = config_dir().ok_or_else(|| anyhow!("Unable to locate config directory"))?;
-    let dir = base.join("FMGoalMusic").join("previews");
-    fs::create_dir_all(&dir).context("Failed to create preview directory")?;
+    let dir = app_paths::config_subdir("previews").context("Failed to create preview directory")?;
     Ok(dir.join(format!("capture_preview_{}.png", generation)))
 }
 
 fn region_selection_image_path() -> Result<PathBuf> {
-    let base = config_dir().ok_or_else(|| anyhow!("Unable to locate config directory"))?;
-    let dir = base.join("FMGoalMusic").join("previews");
-    fs::create_dir_all(&dir).context("Failed to create preview directory")?;
+    let dir = app_paths::config_subdir("previews").context("Failed to create preview directory")?;
     Ok(dir.join("region_selection.png"))
 }
 
-fn contains_custom_goal_phrase(text: &str, phrases: &[String]) -> bool {
-    if phrases.is_empty() {
-        return false;
+/// Folder holding custom team logos, one subfolder per league
+fn team_logo_root() -> Result<PathBuf> {
+    Ok(app_paths::config_dir()?.join("teams"))
+}
+
+/// Folder holding the custom logos of a league's teams
+fn team_logo_dir(league: &str) -> Result<PathBuf> {
+    if !is_safe_name(league) {
+        return Err(anyhow!("Invalid league name '{league}'"));
+    }
+    Ok(team_logo_root()?.join(league))
+}
+
+/// Update and save variation match counts; failures are only logged
+///
+/// The lock is held while saving so concurrent updates are written in turn.
+fn update_variation_stats(
+    stats: &Mutex<Option<VariationStats>>,
+    update: impl FnOnce(&mut VariationStats),
+) {
+    let mut stats = stats.lock();
+    let Some(stats) = stats.as_mut() else {
+        return;
+    };
+    update(stats);
+    if let Err(err) = stats.save() {
+        warn!("[variations] Failed to save match counts: {err:#}");
+    }
+}
+
+/// Move a custom team logo if there is one; failures are only logged
+fn move_team_logo(from: &Path, to: &Path) {
+    if !from.exists() || from == to {
+        return;
+    }
+    let result = to
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|_| fs::rename(from, to));
+    if let Err(err) = result {
+        warn!(
+            "[logo] Failed to move {} to {}: {}",
+            from.display(),
+            to.display(),
+            err
+        );
+    }
+}
+
+/// Write recorder frames to disk on a background thread
+///
+/// Dumping encodes dozens of PNGs, so it must not stall the detection loop.
+fn spawn_recorder_dump(
+    state: &Arc<Mutex<AppState>>,
+    frames: Vec<RecordedFrame>,
+    reason: &'static str,
+    report_status: bool,
+) {
+    let state = Arc::clone(state);
+    thread::spawn(move || {
+        let message = match flight_recorder::dump_frames(&frames, reason) {
+            Ok(dir) => format!("Flight recorder saved to {}", dir.display()),
+            Err(err) => {
+                warn!("Flight recorder dump failed: {err:#}");
+                format!("Flight recorder dump failed: {err:#}")
+            }
+        };
+        if report_status {
+            state.lock().status_message = message;
+        }
+    });
+}
+
+/// Save a labelled sample on a background thread
+fn spawn_sample_save(
+    state: &Arc<Mutex<AppState>>,
+    label: SampleLabel,
+    frames: Vec<RecordedFrame>,
+    context: SampleContext,
+) {
+    let state = Arc::clone(state);
+    thread::spawn(move || {
+        let message = match sample_library::save_sample(label, &frames, context) {
+            Ok(dir) => format!(
+                "Saved {} sample ({} frames) to {}",
+                label.description(),
+                frames.len(),
+                dir.display()
+            ),
+            Err(err) => {
+                warn!("Saving {} sample failed: {err:#}", label.description());
+                format!("Saving {} sample failed: {err:#}", label.description())
+            }
+        };
+        state.lock().status_message = message;
+    });
+}
+
+/// Remember the team text of a goal banner that matched no monitored team
+///
+/// Text that looks like an unknown spelling of a monitored team, and is not
+/// another club from the database, is offered as a new variation of it.
+fn note_unmatched_banner(
+    state: &Arc<Mutex<AppState>>,
+    text: &str,
+    monitored_teams: &[MonitoredTeam],
+    identifier: Option<&TeamIdentifier>,
+) {
+    let team_text = banner_team_text(text);
+    if team_text.is_empty() {
+        return;
+    }
+
+    let mut st = state.lock();
+    if st.last_unmatched_team_text.as_deref() == Some(team_text.as_str()) {
+        return;
+    }
+    st.last_unmatched_team_text = Some(team_text.clone());
+
+    let Some(near_miss) = monitored_teams
+        .iter()
+        .find(|monitored| monitored.matcher.is_near_miss(&team_text))
+    else {
+        return;
+    };
+    let other_club = identifier
+        .and_then(|identifier| identifier.identify(text))
+        .map(|found| !near_miss.is(&found.to_selected_team()))
+        .unwrap_or(false);
+
+    if !other_club
+        && !st
+            .dismissed_variation_suggestions
+            .contains(&team_text.to_lowercase())
+    {
+        info!(
+            "Goal banner '{}' nearly matched {}",
+            team_text, near_miss.team.display_name
+        );
+        st.variation_suggestion = Some((near_miss.team.clone(), team_text));
+    }
+}
+
+/// Team part of a goal banner, in title case when OCR read it all caps
+fn banner_team_text(text: &str) -> String {
+    let team_text = text_extraction::extract_team_name(text).unwrap_or_else(|| text.to_string());
+    let team_text = team_text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if team_text.chars().any(char::is_lowercase) {
+        return team_text;
+    }
+    team_text
+        .split(' ')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first
+                    .to_uppercase()
+                    .chain(chars.flat_map(char::to_lowercase))
+                    .collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Follow the fixture of the match being played from non-goal banners
+///
+/// A kickoff starts a new match and full time ends it; in between, the first
+/// line naming two database clubs (kickoff banner or scoreboard) sets the
+/// home and away teams.
+///
+/// # Returns
+/// The match event to announce, when a match starts or ends
+fn track_fixture(
+    state: &Arc<Mutex<AppState>>,
+    text: &str,
+    kickoff_detector: &KickoffDetector,
+    match_end_detector: &MatchEndDetector,
+    identifier: Option<&TeamIdentifier>,
+    fixture: &mut Option<Fixture>,
+    match_over: &mut bool,
+) -> Option<Event> {
+    let context = DetectionContext::new(text.to_string());
+    let kickoff = matches!(
+        kickoff_detector.detect(&context),
+        DetectionResult::Kickoff { .. }
+    );
+    if kickoff {
+        *match_over = false;
+    } else if let DetectionResult::MatchEnd {
+        home_score,
+        away_score,
+        ..
+    } = match_end_detector.detect(&context)
+    {
+        let newly_over = !*match_over;
+        *match_over = true;
+        if let Some(ended) = fixture.take() {
+            info!("Full time in {}", ended.label());
+            state.lock().current_match = None;
+        }
+        return newly_over.then(|| Event::MatchEnded {
+            timestamp: SystemTime::now(),
+            home_score,
+            away_score,
+        });
+    } else if fixture.is_some() || *match_over {
+        return None;
+    }
+
+    let Some(found) = identifier.and_then(|identifier| identifier.identify_fixture(text)) else {
+        // A kickoff without club names still ends the previous fixture
+        if kickoff && fixture.take().is_some() {
+            info!("Kickoff detected, looking for the new fixture");
+            state.lock().current_match = None;
+        }
+        return None;
+    };
+    let unchanged = fixture
+        .as_ref()
+        .map(|current| current.label() == found.label())
+        .unwrap_or(false);
+    if unchanged {
+        return None;
+    }
+
+    info!("Current match: {}", found.label());
+    {
+        let mut st = state.lock();
+        st.current_match = Some((found.home.to_selected_team(), found.away.to_selected_team()));
+        st.status_message = format!("Now playing: {}", found.label());
+    }
+    let started = Event::MatchStarted {
+        home: Some(found.home.to_selected_team()),
+        away: Some(found.away.to_selected_team()),
+        timestamp: SystemTime::now(),
+    };
+    *fixture = Some(found);
+    Some(started)
+}
+
+/// Club of the current match that a goal banner names
+///
+/// `GoalDetector` resolves the banner against the fixture's display names and
+/// generic "Home"/"Away" wording; name variations are the fallback.
+fn fixture_scorer(
+    goal_detector: &GoalDetector,
+    fixture: &Fixture,
+    text: &str,
+) -> Option<SelectedTeam> {
+    let context = DetectionContext::new(text.to_string()).with_teams(
+        Some(fixture.home.team.display_name.clone()),
+        Some(fixture.away.team.display_name.clone()),
+    );
+    let named = match goal_detector.detect(&context) {
+        DetectionResult::Goal {
+            team_name: Some(name),
+            ..
+        } => fixture.side(&name),
+        _ => None,
+    };
+    named
+        .or_else(|| fixture.scorer(text))
+        .map(|side| side.to_selected_team())
+}
+
+/// Read the competition name and publish it when it changes
+///
+/// Blank reads keep the last name, since banners and replays can cover the
+/// competition region for a while.
+fn read_competition(
+    state: &Arc<Mutex<AppState>>,
+    capture: &mut CaptureManager,
+    ocr_manager: &mut OcrManager,
+    competition: &mut Option<String>,
+) {
+    let text = match capture.capture_region() {
+        Ok(image) => match ocr_manager.get_text(&image) {
+            Ok(text) => text.split_whitespace().collect::<Vec<_>>().join(" "),
+            Err(err) => {
+                warn!("Competition OCR error: {err}");
+                return;
+            }
+        },
+        Err(err) => {
+            warn!("Competition capture error: {err}");
+            return;
+        }
+    };
+    if text.is_empty() || competition.as_deref() == Some(text.as_str()) {
+        return;
     }
 
-    let normalized = text.to_lowercase();
-    phrases.iter().any(|p| {
-        let p_norm = p.to_lowercase();
-        !p_norm.is_empty() && normalized.contains(&p_norm)
-    })
+    info!("Competition: {}", text);
+    state.lock().current_competition = Some(text.clone());
+    *competition = Some(text);
+}
+
+/// Monitored team a goal banner is for
+///
+/// A monitored club the current match credits with the goal counts even when
+/// the banner itself doesn't match; otherwise the team whose variations
+/// match the banner best wins.
+fn monitored_scorer(
+    monitored_teams: &[MonitoredTeam],
+    text: &str,
+    fixture_scorer: Option<&SelectedTeam>,
+) -> Option<usize> {
+    if let Some(index) =
+        fixture_scorer.and_then(|scorer| monitored_teams.iter().position(|m| m.is(scorer)))
+    {
+        return Some(index);
+    }
+    monitored_teams
+        .iter()
+        .enumerate()
+        .filter(|(_, monitored)| monitored.matcher.matches(text))
+        .map(|(index, monitored)| (index, monitored.matcher.score(text)))
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(index, _)| index)
+}
+
+/// Monitored team of the current match that `scorer` scored against
+fn monitored_conceder(
+    monitored_teams: &[MonitoredTeam],
+    fixture: Option<&Fixture>,
+    scorer: Option<&SelectedTeam>,
+) -> Option<usize> {
+    let opponent = fixture?.opponent_of(scorer?)?.to_selected_team();
+    monitored_teams.iter().position(|m| m.is(&opponent))
 }
 
 fn run_detection_loop(
     state: Arc<Mutex<AppState>>,
     cmd_rx: Receiver<DetectionCommand>,
     setup: DetectionSetup,
-    music_bytes_list: Vec<Arc<Vec<u8>>>,
-    ambiance_bytes: Option<Arc<Vec<u8>>>,
-    team_profile: Option<Team>,
+    default_celebration: Celebration,
+    mut monitored_teams: Vec<MonitoredTeam>,
+    team_identifier: Option<TeamIdentifier>,
+    events: EventBus,
 ) -> Result<()> {
     let DetectionSetup {
-        playlist_indices,
-        playlist_entries,
+        celebration: _,
+        team_profiles: _,
         capture_region,
+        competition_region,
         monitor_index,
         ocr_threshold,
         enable_morph_open,
         debounce_ms,
-        selected_team,
-        music_volume,
-        ambiance_volume,
-        ambiance_path: _,
-        ambiance_enabled,
+        team_match_threshold,
+        ambiance_enabled: _,
         music_length_ms,
         ambiance_length_ms,
         custom_goal_phrases,
         selected_language,
+        flight_recorder_seconds,
+        dump_recorder_on_trigger,
+        variation_stats,
     } = setup;
 
-    if playlist_entries.is_empty() || playlist_indices.is_empty() || music_bytes_list.is_empty() {
-        return Err(anyhow!("No goal music tracks available in playlist"));
-    }
-
-    let first_bytes = music_bytes_list
-        .get(0)
+    let first_bytes = monitored_teams
+        .first()
+        .map(|monitored| &monitored.celebration)
+        .unwrap_or(&default_celebration)
+        .music_bytes
+        .first()
         .cloned()
         .ok_or_else(|| anyhow!("No audio data for goal playlist"))?;
 
     let mut audio_manager = AudioManager::from_preloaded(first_bytes)
         .map_err(|err| anyhow!("Failed to initialize audio output: {err}"))?;
-    audio_manager.set_volume(music_volume);
-
-    let ambiance_manager = if ambiance_enabled {
-        if let Some(bytes) = ambiance_bytes {
-            let manager = AudioManager::from_preloaded(bytes)
-                .map_err(|err| anyhow!("Failed to initialize ambiance audio: {err}"))?;
-            manager.set_volume(ambiance_volume);
-            Some(manager)
-        } else {
-            None
-        }
-    } else {
-        None
+
+    // One ambiance output shared by every celebration that has a sound,
+    // including derby and competition ones
+    let first_ambiance = std::iter::once(&default_celebration)
+        .chain(monitored_teams.iter().flat_map(|monitored| {
+            std::iter::once(&monitored.celebration)
+                .chain(monitored.derby.as_ref())
+                .chain(
+                    monitored
+                        .competitions
+                        .iter()
+                        .map(|(_, celebration)| celebration),
+                )
+        }))
+        .find_map(|celebration| celebration.ambiance_bytes.clone());
+    let mut ambiance_manager = match first_ambiance {
+        Some(bytes) => Some(
+            AudioManager::from_preloaded(bytes)
+                .map_err(|err| anyhow!("Failed to initialize ambiance audio: {err}"))?,
+        ),
+        None => None,
     };
 
     let mut capture_manager =
@@ -1094,12 +3059,58 @@ fn run_detection_loop(
     let mut ocr_manager = OcrManager::new_with_options(ocr_threshold, enable_morph_open)
         .map_err(|err| anyhow!("Failed to initialize OCR manager: {err}"))?;
 
-    let team_matcher = team_profile.as_ref().map(|team| TeamMatcher::new(team));
-    if let Some(team) = &team_profile {
-        info!("Team-specific monitoring enabled for {}", team.display_name);
+    // The competition name only matters to teams with competition celebrations
+    let wants_competition = monitored_teams
+        .iter()
+        .any(|monitored| !monitored.competitions.is_empty());
+    let mut competition_capture = match competition_region {
+        Some(region) if wants_competition => {
+            match CaptureManager::new(CaptureRegion::from_array(region), monitor_index) {
+                Ok(manager) => Some(manager),
+                Err(err) => {
+                    warn!("Competition region unavailable: {err}");
+                    None
+                }
+            }
+        }
+        _ => None,
+    };
+    let mut competition: Option<String> = None;
+    let mut last_competition_read: Option<Instant> = None;
+    let mut last_debug_frame: Option<Instant> = None;
+    let mut debug_sequence: u64 = 0;
+    // Set by a manual goal request and celebrated on the next frame
+    let mut pending_manual_goal: Option<Option<SelectedTeam>> = None;
+    // Sounds started here, so their end can be announced
+    let mut music_playing = false;
+    let mut ambiance_playing = false;
+
+    for monitored in &monitored_teams {
+        info!(
+            "Team-specific monitoring enabled for {}",
+            monitored.team.display_name
+        );
     }
 
+    let i18n_phrases = I18nPhrases::new(selected_language);
+    let goal_detector = GoalDetector::new(i18n_phrases.clone());
+    let kickoff_detector = KickoffDetector::new(i18n_phrases.clone());
+    let match_end_detector = MatchEndDetector::new(i18n_phrases.clone());
+    let mut fixture: Option<Fixture> = None;
+    // Set at full time so the final scoreboard doesn't start a new fixture
+    let mut match_over = false;
+
     let mut debouncer = Debouncer::new(debounce_ms.max(100));
+    let mut recorder = FlightRecorder::new(flight_recorder_seconds);
+    let monitored_names: Vec<&str> = monitored_teams
+        .iter()
+        .map(|monitored| monitored.team.display_name.as_str())
+        .collect();
+    let sample_context = SampleContext {
+        language: selected_language.code().to_string(),
+        team: (!monitored_names.is_empty()).then(|| monitored_names.join(", ")),
+        custom_goal_phrases: custom_goal_phrases.clone(),
+    };
 
     loop {
         match cmd_rx.try_recv() {
@@ -1108,9 +3119,17 @@ fn run_detection_loop(
                 if let Some(ref ambiance) = ambiance_manager {
                     ambiance.stop();
                 }
+                let stopped = [
+                    (music_playing, AudioSource::GoalMusic),
+                    (ambiance_playing, AudioSource::Ambiance),
+                ];
+                for (_, source) in stopped.into_iter().filter(|(playing, _)| *playing) {
+                    events.publish(Event::AudioPlaybackFinished { source });
+                }
                 let mut st = state.lock();
-                st.process_state = ProcessState::Stopped;
+                set_process_state(&mut st, &events, ProcessState::Stopped);
                 st.status_message = "Monitoring stopped".to_string();
+                st.current_track = None;
                 return Ok(());
             }
             Ok(DetectionCommand::StopAudio) => {
@@ -1119,144 +3138,475 @@ fn run_detection_loop(
                     ambiance.stop();
                 }
             }
+            Ok(DetectionCommand::DumpRecorder) => {
+                spawn_recorder_dump(&state, recorder.snapshot(), "manual", true);
+            }
+            Ok(DetectionCommand::LabelSample(label)) => {
+                let frames = match label {
+                    SampleLabel::FalsePositive => {
+                        audio_manager.stop();
+                        if let Some(ref ambiance) = ambiance_manager {
+                            ambiance.stop();
+                        }
+                        recorder.around_last_trigger(FALSE_POSITIVE_LOOKBACK)
+                    }
+                    SampleLabel::MissedGoal => Some(recorder.recent(MISSED_GOAL_LOOKBACK)),
+                };
+                match frames {
+                    Some(frames) => {
+                        spawn_sample_save(&state, label, frames, sample_context.clone())
+                    }
+                    None => {
+                        state.lock().status_message =
+                            "No recent goal trigger to mark as wrong".to_string();
+                    }
+                }
+            }
+            Ok(DetectionCommand::ManualGoal(team)) => {
+                info!(
+                    "Manual goal requested for {}",
+                    team.as_ref()
+                        .map_or("the default celebration", |team| team.display_name.as_str())
+                );
+                pending_manual_goal = Some(team);
+            }
+            Ok(DetectionCommand::PlayTrack { name, bytes }) => {
+                audio_manager.set_audio_data(bytes);
+                let music_volume = state.lock().celebration().music_volume;
+                audio_manager.set_volume(music_volume);
+                let result = if music_length_ms > 0 {
+                    audio_manager.play_sound_with_fade_and_limit(AUDIO_FADE_MS, music_length_ms)
+                } else {
+                    audio_manager.play_sound_with_fade(AUDIO_FADE_MS)
+                };
+                let mut st = state.lock();
+                match result {
+                    Ok(()) => {
+                        music_playing = true;
+                        events.publish(Event::AudioPlaybackStarted {
+                            source: AudioSource::GoalMusic,
+                            name: Some(name.clone()),
+                        });
+                        st.status_message = format!("Playing '{name}'");
+                        st.current_track = Some(name);
+                    }
+                    Err(err) => {
+                        st.status_message = format!("Failed to play music: {err}");
+                        publish_audio_error(&events, &err);
+                    }
+                }
+            }
+            Ok(DetectionCommand::UpdateTeamProfile { from, to, team }) => {
+                if let Some(monitored) = monitored_teams.iter_mut().find(|m| m.is(&from)) {
+                    monitored.team = to;
+                    monitored.matcher = TeamMatcher::with_threshold(&team, team_match_threshold);
+                    info!("Team variations updated for {}", team.display_name);
+                }
+            }
             Err(TryRecvError::Empty) => {}
             Err(TryRecvError::Disconnected) => {
                 let mut st = state.lock();
-                st.process_state = ProcessState::Stopped;
+                set_process_state(&mut st, &events, ProcessState::Stopped);
                 st.status_message = "Monitoring stopped".to_string();
                 return Ok(());
             }
         }
 
+        if let Some(capture) = competition_capture.as_mut() {
+            let due = last_competition_read
+                .map(|at| at.elapsed() >= COMPETITION_POLL_INTERVAL)
+                .unwrap_or(true);
+            if due {
+                last_competition_read = Some(Instant::now());
+                read_competition(&state, capture, &mut ocr_manager, &mut competition);
+            }
+        }
+
+        let iteration_start = Instant::now();
         let image = match capture_manager.capture_region() {
             Ok(img) => img,
             Err(err) => {
                 let mut st = state.lock();
-                st.process_state = ProcessState::Stopped;
+                set_process_state(&mut st, &events, ProcessState::Stopped);
                 st.status_message = format!("Capture error: {err}");
+                events.publish(Event::ErrorOccurred {
+                    message: err.to_string(),
+                    context: "capture".to_string(),
+                });
                 return Err(anyhow!("Capture error: {err}"));
             }
         };
 
-        // Run OCR once and apply detection rules in the following order
-        // (for both team-selected and non-team modes):
-        // 1) Language goal phrases (if any)
-        // 2) Custom goal phrases (if any)
-        // 3) Default GOL/GOAL FOR detection
-        let text = match ocr_manager.get_text(&image) {
-            Ok(t) => t,
+        // Run OCR once; the goal rules apply the same way with or without
+        // monitored teams
+        let capture_us = iteration_start.elapsed().as_secs_f64() * 1_000_000.0;
+        let (text, binary, preprocess_us, ocr_us) = match ocr_manager.get_text_traced(&image) {
+            Ok(trace) => (
+                trace.text,
+                Some(trace.binary),
+                trace.preprocess_us,
+                trace.ocr_us,
+            ),
             Err(err) => {
                 warn!("OCR error: {err}");
-                String::new()
+                (String::new(), None, 0.0, 0.0)
             }
         };
 
-        let i18n_phrases = I18nPhrases::new(selected_language);
-        let has_language_phrases = !i18n_phrases.goal_phrases.is_empty();
-        let has_custom_phrases = !custom_goal_phrases.is_empty();
-
-        let mut goal_detected = false;
-
-        if !text.is_empty() {
-            // 1) Language phrases (if configured)
-            if has_language_phrases && i18n_phrases.contains_goal_phrase(&text) {
-                goal_detected = true;
-            } else if has_custom_phrases && contains_custom_goal_phrase(&text, &custom_goal_phrases)
-            {
-                // 2) Custom phrases (if any)
-                goal_detected = true;
-            } else if text_extraction::contains_goal_text(&text) {
-                // 3) Default GOL/GOAL FOR fallback
-                goal_detected = true;
+        let match_start = Instant::now();
+        let goal_rule = match_goal_rule(&text, &i18n_phrases, &custom_goal_phrases);
+        let goal_detected = goal_rule.is_some();
+
+        if !goal_detected && !text.is_empty() {
+            if let Some(event) = track_fixture(
+                &state,
+                &text,
+                &kickoff_detector,
+                &match_end_detector,
+                team_identifier.as_ref(),
+                &mut fixture,
+                &mut match_over,
+            ) {
+                events.publish(event);
             }
         }
 
-        let should_play = if let Some(matcher) = &team_matcher {
-            if goal_detected && matcher.matches(&text) {
-                info!("Goal detected for selected team from text: {}", text);
-                true
-            } else {
-                false
+        // Side of the current match the goal banner names, if the fixture is known
+        let fixture_scorer = match &fixture {
+            Some(fixture) if goal_detected => fixture_scorer(&goal_detector, fixture, &text),
+            _ => None,
+        };
+
+        let manual_goal = pending_manual_goal.take();
+        let scorer_index = match &manual_goal {
+            Some(team) => team
+                .as_ref()
+                .and_then(|team| monitored_teams.iter().position(|m| m.is(team))),
+            None if goal_detected => {
+                monitored_scorer(&monitored_teams, &text, fixture_scorer.as_ref())
             }
+            None => None,
+        };
+        // A monitored team with a conceded sound that the goal was scored against
+        let conceding_index = if manual_goal.is_none() && goal_detected && scorer_index.is_none() {
+            monitored_conceder(&monitored_teams, fixture.as_ref(), fixture_scorer.as_ref())
+                .filter(|&index| monitored_teams[index].conceded_bytes.is_some())
         } else {
+            None
+        };
+
+        let should_play = if manual_goal.is_some() {
+            true
+        } else if monitored_teams.is_empty() {
             goal_detected
+        } else if let Some(index) = scorer_index {
+            info!(
+                "Goal detected for {} from text: {}",
+                monitored_teams[index].team.display_name, text
+            );
+            true
+        } else {
+            if goal_detected && fixture_scorer.is_none() {
+                note_unmatched_banner(&state, &text, &monitored_teams, team_identifier.as_ref());
+            }
+            false
+        };
+
+        let mut decision = if text.is_empty() {
+            FrameDecision::NoText
+        } else if !goal_detected {
+            FrameDecision::NoGoal
+        } else if !should_play && conceding_index.is_none() {
+            FrameDecision::TeamMismatch
+        } else {
+            FrameDecision::Debounced
         };
+        let match_us = match_start.elapsed().as_secs_f64() * 1_000_000.0;
+        let mut audio_trigger_us = 0.0;
 
-        if should_play && debouncer.should_trigger() {
-            if let Some(ref ambiance) = ambiance_manager {
-                let result = if ambiance_length_ms > 0 {
-                    ambiance.play_sound_with_fade_and_limit(AUDIO_FADE_MS, ambiance_length_ms)
+        if manual_goal.is_some() {
+            // A manual goal plays right away and restarts the debounce time
+            debouncer.reset();
+        }
+        if (should_play || conceding_index.is_some()) && debouncer.should_trigger() {
+            decision = FrameDecision::Triggered;
+            let trigger_start = Instant::now();
+
+            if let Some(index) = conceding_index {
+                let conceding = &monitored_teams[index];
+                if let Some(bytes) = &conceding.conceded_bytes {
+                    audio_manager.set_audio_data(Arc::clone(bytes));
+                }
+                audio_manager.set_volume(conceding.celebration.music_volume);
+                let result = if music_length_ms > 0 {
+                    audio_manager.play_sound_with_fade_and_limit(AUDIO_FADE_MS, music_length_ms)
                 } else {
-                    ambiance.play_sound_with_fade(AUDIO_FADE_MS)
+                    audio_manager.play_sound_with_fade(AUDIO_FADE_MS)
                 };
+
+                let mut st = state.lock();
                 if let Err(err) = result {
-                    warn!("Failed to play ambiance: {err}");
+                    st.status_message = format!("Failed to play conceded sound: {err}");
+                    publish_audio_error(&events, &err);
+                } else {
+                    music_playing = true;
+                    events.publish(Event::GoalDetected {
+                        team: fixture_scorer.clone(),
+                        match_score: None,
+                        timestamp: SystemTime::now(),
+                    });
+                    events.publish(Event::AudioPlaybackStarted {
+                        source: AudioSource::GoalMusic,
+                        name: None,
+                    });
+                    if let Some(scorer) = &fixture_scorer {
+                        *st.goals_by_team
+                            .entry(scorer.display_name.clone())
+                            .or_insert(0) += 1;
+                    }
+                    st.last_scoring_team = fixture_scorer.clone();
+                    st.last_goal_at = Some(SystemTime::now());
+                    st.status_message = format!("{} conceded", conceding.team.display_name);
+                    info!("{} conceded a goal", conceding.team.display_name);
                 }
-            }
-            let playlist_len = playlist_indices.len();
-            let chosen_pos = if playlist_len <= 1 {
-                0
+                audio_trigger_us = trigger_start.elapsed().as_secs_f64() * 1_000_000.0;
             } else {
-                let last_played = {
-                    let st = state.lock();
-                    st.last_played_music_index
+                let (celebration, occasion) = match scorer_index {
+                    Some(index) => monitored_teams[index]
+                        .celebration_for(fixture.as_ref(), competition.as_deref()),
+                    None => (&default_celebration, None),
                 };
+                if let Some(occasion) = &occasion {
+                    info!("Playing the {} celebration", occasion);
+                }
 
-                let mut positions: Vec<usize> = (0..playlist_len).collect();
-                if let Some(last) = last_played {
-                    positions.retain(|&pos| playlist_indices[pos] != last);
-                    if positions.is_empty() {
-                        positions = (0..playlist_len).collect();
+                let mut played_ambiance = false;
+                if let (Some(ambiance), Some(bytes)) =
+                    (ambiance_manager.as_mut(), &celebration.ambiance_bytes)
+                {
+                    ambiance.set_audio_data(Arc::clone(bytes));
+                    ambiance.set_volume(celebration.ambiance_volume);
+                    let result = if ambiance_length_ms > 0 {
+                        ambiance.play_sound_with_fade_and_limit(AUDIO_FADE_MS, ambiance_length_ms)
+                    } else {
+                        ambiance.play_sound_with_fade(AUDIO_FADE_MS)
+                    };
+                    match result {
+                        Ok(()) => {
+                            played_ambiance = true;
+                            ambiance_playing = true;
+                            events.publish(Event::AudioPlaybackStarted {
+                                source: AudioSource::Ambiance,
+                                name: None,
+                            });
+                        }
+                        Err(err) => {
+                            warn!("Failed to play ambiance: {err}");
+                            publish_audio_error(&events, &err);
+                        }
                     }
                 }
+                let playlist_indices = &celebration.playlist_indices;
+                let playlist_len = playlist_indices.len();
+                let chosen_pos = if playlist_len <= 1 {
+                    0
+                } else {
+                    let last_played = {
+                        let st = state.lock();
+                        st.last_played_music_index
+                    };
+
+                    let mut positions: Vec<usize> = (0..playlist_len).collect();
+                    if let Some(last) = last_played {
+                        positions.retain(|&pos| playlist_indices[pos] != last);
+                        if positions.is_empty() {
+                            positions = (0..playlist_len).collect();
+                        }
+                    }
 
-                let mut rng = rand::thread_rng();
-                *positions.choose(&mut rng).unwrap_or(&0)
-            };
-
-            if let Some(bytes) = music_bytes_list.get(chosen_pos) {
-                audio_manager.set_audio_data(Arc::clone(bytes));
-            }
+                    let mut rng = rand::thread_rng();
+                    *positions.choose(&mut rng).unwrap_or(&0)
+                };
 
-            let selected_name = playlist_entries
-                .get(chosen_pos)
-                .map(|e| e.name.clone())
-                .unwrap_or_else(|| "Unknown track".to_string());
+                if let Some(bytes) = celebration.music_bytes.get(chosen_pos) {
+                    audio_manager.set_audio_data(Arc::clone(bytes));
+                }
+                audio_manager.set_volume(celebration.music_volume);
 
-            let music_result = if music_length_ms > 0 {
-                audio_manager.play_sound_with_fade_and_limit(AUDIO_FADE_MS, music_length_ms)
-            } else {
-                audio_manager.play_sound_with_fade(AUDIO_FADE_MS)
-            };
+                let selected_name = celebration
+                    .playlist_entries
+                    .get(chosen_pos)
+                    .map(|e| e.name.clone())
+                    .unwrap_or_else(|| "Unknown track".to_string());
 
-            if let Err(err) = music_result {
-                let mut st = state.lock();
-                st.status_message = format!("Failed to play music: {err}");
-            } else {
-                let mut st = state.lock();
-                st.detection_count += 1;
-                if let Some(original_idx) = playlist_indices.get(chosen_pos) {
-                    st.last_played_music_index = Some(*original_idx);
-                }
-                let ambiance_note = if ambiance_manager.is_some() {
-                    " + crowd cheer"
+                let music_result = if music_length_ms > 0 {
+                    audio_manager.play_sound_with_fade_and_limit(AUDIO_FADE_MS, music_length_ms)
                 } else {
-                    ""
+                    audio_manager.play_sound_with_fade(AUDIO_FADE_MS)
                 };
-                st.status_message = format!(
-                    "Goal detected! Played '{}'{} (total: {})",
-                    selected_name, ambiance_note, st.detection_count
-                );
 
-                if let Some(team) = &selected_team {
-                    info!(
-                        "Goal #{} for {} ({}) detected",
-                        st.detection_count, team.display_name, team.league
+                let (scoring_team, matched_variation, match_score) =
+                    if let Some(index) = scorer_index {
+                        let monitored = &monitored_teams[index];
+                        let variation = monitored
+                            .matcher
+                            .best_variation(&text)
+                            .filter(|_| manual_goal.is_none())
+                            .map(|(variation, _)| variation.to_string());
+                        (Some(monitored.team.clone()), variation, Some(1.0))
+                    } else if manual_goal.is_some() {
+                        (None, None, None)
+                    } else if let Some(scorer) = fixture_scorer.clone() {
+                        info!(
+                            "Goal attributed to {} from the current match",
+                            scorer.display_name
+                        );
+                        (Some(scorer), None, None)
+                    } else {
+                        team_identifier
+                            .as_ref()
+                            .and_then(|identifier| identifier.identify(&text))
+                            .map(|found| {
+                                info!(
+                                    "Identified scoring team {} ({}) with score {:.2}",
+                                    found.team.display_name, found.league, found.score
+                                );
+                                let variation = TeamMatcher::new(&found.team)
+                                    .best_variation(&text)
+                                    .map(|(variation, _)| variation.to_string());
+                                (Some(found.to_selected_team()), variation, Some(found.score))
+                            })
+                            .unwrap_or((None, None, None))
+                    };
+                if let (Some(team), Some(variation)) = (&scoring_team, &matched_variation) {
+                    update_variation_stats(&variation_stats, |stats| {
+                        stats.record_match(&team.league, &team.team_key, variation)
+                    });
+                }
+
+                if let Err(err) = music_result {
+                    let mut st = state.lock();
+                    st.status_message = format!("Failed to play music: {err}");
+                    publish_audio_error(&events, &err);
+                } else {
+                    music_playing = true;
+                    events.publish(Event::GoalDetected {
+                        team: scoring_team.clone(),
+                        match_score,
+                        timestamp: SystemTime::now(),
+                    });
+                    events.publish(Event::AudioPlaybackStarted {
+                        source: AudioSource::GoalMusic,
+                        name: Some(selected_name.clone()),
+                    });
+                    let mut st = state.lock();
+                    st.detection_count += 1;
+                    if let Some(team) = &scoring_team {
+                        *st.goals_by_team
+                            .entry(team.display_name.clone())
+                            .or_insert(0) += 1;
+                    }
+                    st.last_scoring_team = scoring_team.clone();
+                    if let Some(original_idx) = playlist_indices.get(chosen_pos) {
+                        st.last_played_music_index = Some(*original_idx);
+                    }
+                    st.current_track = Some(selected_name.clone());
+                    st.last_goal_at = Some(SystemTime::now());
+                    let ambiance_note = if played_ambiance {
+                        " + crowd cheer"
+                    } else {
+                        ""
+                    };
+                    let headline = match (&scoring_team, &occasion) {
+                        (Some(team), Some(occasion)) => {
+                            format!("Goal for {} ({})!", team.display_name, occasion)
+                        }
+                        (Some(team), None) => format!("Goal for {}!", team.display_name),
+                        (None, _) => "Goal detected!".to_string(),
+                    };
+                    st.status_message = format!(
+                        "{} Played '{}'{} (total: {})",
+                        headline, selected_name, ambiance_note, st.detection_count
                     );
+
+                    if let Some(team) = &scoring_team {
+                        info!(
+                            "Goal #{} for {} ({}) detected",
+                            st.detection_count, team.display_name, team.league
+                        );
+                    }
                 }
+                audio_trigger_us = trigger_start.elapsed().as_secs_f64() * 1_000_000.0;
             }
         }
 
+        let timing = IterationTiming {
+            capture_us,
+            preprocess_us,
+            ocr_us,
+            match_us,
+            audio_trigger_us,
+            total_us: iteration_start.elapsed().as_secs_f64() * 1_000_000.0,
+        };
+        // A frame without a binarised image is one OCR failed on
+        let ocr_failed = binary.is_none();
+        {
+            let mut st = state.lock();
+            st.detection_metrics
+                .record(iteration_start, timing, decision, ocr_failed);
+            if music_playing && !audio_manager.is_playing() {
+                music_playing = false;
+                st.current_track = None;
+                events.publish(Event::AudioPlaybackFinished {
+                    source: AudioSource::GoalMusic,
+                });
+            }
+            let ambiance_busy = ambiance_manager
+                .as_ref()
+                .is_some_and(|ambiance| ambiance.is_playing());
+            if ambiance_playing && !ambiance_busy {
+                ambiance_playing = false;
+                events.publish(Event::AudioPlaybackFinished {
+                    source: AudioSource::Ambiance,
+                });
+            }
+        }
+
+        let debug_due = last_debug_frame
+            .map(|at| at.elapsed() >= OCR_DEBUG_INTERVAL)
+            .unwrap_or(true);
+        if debug_due && state.lock().ocr_debug_enabled {
+            last_debug_frame = Some(Instant::now());
+            debug_sequence += 1;
+            let team = match (scorer_index, conceding_index) {
+                (Some(index), _) => Some(monitored_teams[index].team.display_name.clone()),
+                (None, Some(index)) => Some(format!(
+                    "{} (conceded)",
+                    monitored_teams[index].team.display_name
+                )),
+                (None, None) => fixture_scorer
+                    .as_ref()
+                    .map(|scorer| scorer.display_name.clone()),
+            };
+            match OcrDebugFrame::new(
+                debug_sequence,
+                &image,
+                binary.as_ref(),
+                text.clone(),
+                goal_rule.as_ref().map(|rule| rule.to_string()),
+                team,
+                decision,
+            ) {
+                Ok(frame) => state.lock().ocr_debug_frame = Some(frame),
+                Err(err) => warn!("Failed to encode OCR debug frame: {err}"),
+            }
+        }
+        recorder.record(RecordedFrame::new(image, binary, text, timing, decision));
+
+        if decision == FrameDecision::Triggered && dump_recorder_on_trigger {
+            spawn_recorder_dump(&state, recorder.snapshot(), "goal trigger", false);
+        }
+
         thread::sleep(Duration::from_millis(16));
     }
 }
@@ -0,0 +1,157 @@
+//! WebSocket stream of application events
+//!
+//! Overlays, bots and dashboards connect to `ws://127.0.0.1:<events_port>/`
+//! with the remote control token and receive every `messaging::Event` as a
+//! versioned JSON message (see `EventMessage`). Off by default, like the HTTP
+//! control API whose settings it shares.
+
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use crossbeam_channel::RecvTimeoutError;
+use tungstenite::handshake::server::{ErrorResponse, Request, Response};
+use tungstenite::http::StatusCode;
+use tungstenite::{accept_hdr, Message};
+
+use super::controller::GuiController;
+use super::remote_control::{is_authorized, RemoteControlConfig};
+use crate::messaging::{EventBus, EventMessage};
+
+/// Quiet time after which clients are pinged, so dead connections are noticed
+const PING_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How long a client may take to finish the handshake, and how long a send
+/// may block before the client is dropped
+const IO_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Start the event stream on a background thread if it is enabled
+pub fn start_event_stream(controller: &GuiController) -> Result<()> {
+    let config = RemoteControlConfig::load()
+        .map_err(|err| anyhow!("Failed to load remote control settings: {err}"))?;
+    if !config.events_enabled {
+        return Ok(());
+    }
+
+    let listener = TcpListener::bind(("127.0.0.1", config.events_port)).map_err(|err| {
+        anyhow!(
+            "Failed to listen on 127.0.0.1:{}: {err}",
+            config.events_port
+        )
+    })?;
+    tracing::info!(
+        "Event stream listening on ws://127.0.0.1:{}",
+        config.events_port
+    );
+    serve(listener, controller.events(), config.token)
+}
+
+fn serve(listener: TcpListener, events: EventBus, token: String) -> Result<()> {
+    thread::Builder::new()
+        .name("event-stream".to_string())
+        .spawn(move || {
+            for stream in listener.incoming() {
+                match stream {
+                    Ok(stream) => {
+                        let events = events.clone();
+                        let token = token.clone();
+                        thread::spawn(move || stream_to_client(stream, &events, &token));
+                    }
+                    Err(err) => tracing::warn!("Event stream connection failed: {err}"),
+                }
+            }
+        })?;
+    Ok(())
+}
+
+/// Send every event to one client until it goes away
+fn stream_to_client(stream: TcpStream, events: &EventBus, token: &str) {
+    // A client that never completes the handshake must not hold its thread
+    // and subscription forever
+    let timeouts = stream
+        .set_read_timeout(Some(IO_TIMEOUT))
+        .and_then(|()| stream.set_write_timeout(Some(IO_TIMEOUT)));
+    if let Err(err) = timeouts {
+        tracing::warn!("Event stream connection dropped: {err}");
+        return;
+    }
+
+    // Subscribe before the handshake so nothing published after the client
+    // connected is missed
+    let (rx, id) = events.subscribe();
+
+    let check_token = |request: &Request, response: Response| -> Result<Response, ErrorResponse> {
+        let authorization = request
+            .headers()
+            .get("Authorization")
+            .and_then(|value| value.to_str().ok());
+        if is_authorized(authorization, request.uri().query(), token) {
+            Ok(response)
+        } else {
+            let mut rejection = ErrorResponse::new(Some("Missing or wrong token".to_string()));
+            *rejection.status_mut() = StatusCode::UNAUTHORIZED;
+            Err(rejection)
+        }
+    };
+
+    match accept_hdr(stream, check_token) {
+        Ok(mut socket) => {
+            loop {
+                let message = match rx.recv_timeout(PING_INTERVAL) {
+                    Ok(event) => match EventMessage::new(&event).to_json() {
+                        Ok(json) => Message::Text(json.into()),
+                        Err(err) => {
+                            tracing::warn!("Failed to serialize {event:?}: {err}");
+                            continue;
+                        }
+                    },
+                    Err(RecvTimeoutError::Timeout) => Message::Ping(Vec::new().into()),
+                    Err(RecvTimeoutError::Disconnected) => break,
+                };
+                if socket.send(message).is_err() {
+                    break;
+                }
+            }
+            let _ = socket.close(None);
+        }
+        Err(err) => tracing::debug!("Event stream handshake failed: {err}"),
+    }
+    events.unsubscribe(id);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messaging::Event;
+
+    fn start_test_server() -> (String, EventBus) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let events = EventBus::new();
+        serve(listener, events.clone(), "secret".to_string()).unwrap();
+        (address, events)
+    }
+
+    #[test]
+    fn test_events_are_streamed_as_json() {
+        let (address, events) = start_test_server();
+        let (mut socket, _) =
+            tungstenite::connect(format!("ws://{address}/?token=secret")).unwrap();
+
+        events.publish(Event::Shutdown);
+        let message = socket.read().unwrap();
+        let json: serde_json::Value = serde_json::from_str(message.to_text().unwrap()).unwrap();
+
+        assert_eq!(json["version"], crate::messaging::EVENT_SCHEMA_VERSION);
+        assert_eq!(json["event"]["type"], "shutdown");
+    }
+
+    #[test]
+    fn test_connections_without_token_are_refused() {
+        let (address, _events) = start_test_server();
+
+        assert!(tungstenite::connect(format!("ws://{address}/")).is_err());
+        assert!(tungstenite::connect(format!("ws://{address}/?token=wrong")).is_err());
+    }
+}
@@ -16,13 +16,17 @@ use super::controller::GuiController;
 pub enum GlobalHotkeyId {
     ToggleMonitoring = 1,
     StopGoalMusic = 2,
+    DumpFlightRecorder = 3,
+    MarkFalsePositive = 4,
+    MarkMissedGoal = 5,
+    LearnVariation = 6,
 }
 
 /// Manages system-wide keyboard shortcuts
 pub struct GlobalHotkeySystem {
     manager: GlobalHotKeyManager,
     controller: GuiController,
-    hotkeys: Vec<HotKey>,
+    hotkeys: Vec<(GlobalHotkeyId, HotKey)>,
 }
 
 /// Send-safe handler that processes global hotkey events on a background thread.
@@ -35,6 +39,10 @@ pub struct GlobalHotkeyHandler {
     controller: GuiController,
     toggle_id: u32,
     stop_id: u32,
+    dump_id: u32,
+    false_positive_id: u32,
+    missed_goal_id: u32,
+    learn_variation_id: u32,
 }
 
 impl GlobalHotkeySystem {
@@ -43,6 +51,10 @@ impl GlobalHotkeySystem {
     /// Uses more unique key combinations to avoid conflicts:
     /// - Cmd/Ctrl + Shift + 1: Toggle monitoring
     /// - Cmd/Ctrl + Shift + S: Stop goal music
+    /// - Cmd/Ctrl + Shift + D: Save the detection flight recorder
+    /// - Cmd/Ctrl + Shift + F: Mark the last goal as a false positive
+    /// - Cmd/Ctrl + Shift + M: Mark a missed goal
+    /// - Cmd/Ctrl + Shift + L: Learn the last goal banner as a team variation
     pub fn new(controller: GuiController) -> Result<Self> {
         let manager = GlobalHotKeyManager::new()
             .map_err(|e| anyhow!("Failed to create global hotkey manager: {}", e))?;
@@ -53,7 +65,7 @@ impl GlobalHotkeySystem {
             hotkeys: Vec::new(),
         };
 
-        // Register the two critical global hotkeys
+        // Register the critical global hotkeys
         system.register_hotkeys()?;
 
         Ok(system)
@@ -61,16 +73,25 @@ impl GlobalHotkeySystem {
 
     /// Create a lightweight handler that can be sent across threads safely.
     pub fn create_handler(&self) -> GlobalHotkeyHandler {
-        let toggle_id = self.hotkeys.get(0).map(|h| h.id()).unwrap_or(0);
-        let stop_id = self.hotkeys.get(1).map(|h| h.id()).unwrap_or(0);
-
         GlobalHotkeyHandler {
             controller: self.controller.clone(),
-            toggle_id,
-            stop_id,
+            toggle_id: self.registered_id(GlobalHotkeyId::ToggleMonitoring),
+            stop_id: self.registered_id(GlobalHotkeyId::StopGoalMusic),
+            dump_id: self.registered_id(GlobalHotkeyId::DumpFlightRecorder),
+            false_positive_id: self.registered_id(GlobalHotkeyId::MarkFalsePositive),
+            missed_goal_id: self.registered_id(GlobalHotkeyId::MarkMissedGoal),
+            learn_variation_id: self.registered_id(GlobalHotkeyId::LearnVariation),
         }
     }
 
+    /// System ID of a registered hotkey, or 0 if it could not be registered
+    fn registered_id(&self, id: GlobalHotkeyId) -> u32 {
+        self.hotkeys
+            .iter()
+            .find(|(registered, _)| *registered == id)
+            .map_or(0, |(_, hotkey)| hotkey.id())
+    }
+
     /// Register global hotkeys with the system
     fn register_hotkeys(&mut self) -> Result<()> {
         // Platform-specific modifier (Cmd on macOS, Ctrl on Windows/Linux)
@@ -86,28 +107,75 @@ impl GlobalHotkeySystem {
         // Hotkey 2: Cmd/Ctrl + Shift + S - Stop Goal Music
         let stop_music = HotKey::new(Some(platform_modifier | Modifiers::SHIFT), Code::KeyS);
 
+        // Hotkey 3: Cmd/Ctrl + Shift + D - Dump Flight Recorder
+        let dump_recorder = HotKey::new(Some(platform_modifier | Modifiers::SHIFT), Code::KeyD);
+
+        // Hotkey 4: Cmd/Ctrl + Shift + F - Mark False Positive
+        let false_positive = HotKey::new(Some(platform_modifier | Modifiers::SHIFT), Code::KeyF);
+
+        // Hotkey 5: Cmd/Ctrl + Shift + M - Mark Missed Goal
+        let missed_goal = HotKey::new(Some(platform_modifier | Modifiers::SHIFT), Code::KeyM);
+
+        // Hotkey 6: Cmd/Ctrl + Shift + L - Learn Team Variation
+        let learn_variation = HotKey::new(Some(platform_modifier | Modifiers::SHIFT), Code::KeyL);
+
         // Register with the system
         self.manager
             .register(toggle_monitoring)
             .map_err(|e| anyhow!("Failed to register toggle monitoring hotkey: {}", e))?;
+        self.hotkeys
+            .push((GlobalHotkeyId::ToggleMonitoring, toggle_monitoring));
 
         self.manager
             .register(stop_music)
             .map_err(|e| anyhow!("Failed to register stop music hotkey: {}", e))?;
-
-        self.hotkeys.push(toggle_monitoring);
-        self.hotkeys.push(stop_music);
+        self.hotkeys
+            .push((GlobalHotkeyId::StopGoalMusic, stop_music));
+
+        // The rest are conveniences: another app owning one of these combos
+        // must not cost the user the two above
+        for (id, hotkey, name) in [
+            (
+                GlobalHotkeyId::DumpFlightRecorder,
+                dump_recorder,
+                "flight recorder",
+            ),
+            (
+                GlobalHotkeyId::MarkFalsePositive,
+                false_positive,
+                "false positive",
+            ),
+            (GlobalHotkeyId::MarkMissedGoal, missed_goal, "missed goal"),
+            (
+                GlobalHotkeyId::LearnVariation,
+                learn_variation,
+                "learn variation",
+            ),
+        ] {
+            match self.manager.register(hotkey) {
+                Ok(()) => self.hotkeys.push((id, hotkey)),
+                Err(e) => tracing::warn!("Failed to register {} hotkey: {}", name, e),
+            }
+        }
 
         tracing::info!("✓ Global hotkeys registered:");
         #[cfg(target_os = "macos")]
         {
             tracing::info!("  ⌘⇧1 - Toggle goal monitoring");
             tracing::info!("  ⌘⇧S - Stop goal music");
+            tracing::info!("  ⌘⇧D - Save flight recorder");
+            tracing::info!("  ⌘⇧F - Mark last goal as false positive");
+            tracing::info!("  ⌘⇧M - Mark missed goal");
+            tracing::info!("  ⌘⇧L - Learn team variation from last goal banner");
         }
         #[cfg(not(target_os = "macos"))]
         {
             tracing::info!("  Ctrl+Shift+1 - Toggle goal monitoring");
             tracing::info!("  Ctrl+Shift+S - Stop goal music");
+            tracing::info!("  Ctrl+Shift+D - Save flight recorder");
+            tracing::info!("  Ctrl+Shift+F - Mark last goal as false positive");
+            tracing::info!("  Ctrl+Shift+M - Mark missed goal");
+            tracing::info!("  Ctrl+Shift+L - Learn team variation from last goal banner");
         }
 
         Ok(())
@@ -120,6 +188,13 @@ impl GlobalHotkeySystem {
             vec![
                 ("⌘⇧1", "Toggle goal monitoring (works globally)"),
                 ("⌘⇧S", "Stop goal music (works globally)"),
+                ("⌘⇧D", "Save flight recorder (works globally)"),
+                ("⌘⇧F", "Mark last goal as false positive (works globally)"),
+                ("⌘⇧M", "Mark missed goal (works globally)"),
+                (
+                    "⌘⇧L",
+                    "Learn team variation from last goal banner (works globally)",
+                ),
             ]
         }
         #[cfg(not(target_os = "macos"))]
@@ -127,6 +202,16 @@ impl GlobalHotkeySystem {
             vec![
                 ("Ctrl+Shift+1", "Toggle goal monitoring (works globally)"),
                 ("Ctrl+Shift+S", "Stop goal music (works globally)"),
+                ("Ctrl+Shift+D", "Save flight recorder (works globally)"),
+                (
+                    "Ctrl+Shift+F",
+                    "Mark last goal as false positive (works globally)",
+                ),
+                ("Ctrl+Shift+M", "Mark missed goal (works globally)"),
+                (
+                    "Ctrl+Shift+L",
+                    "Learn team variation from last goal banner (works globally)",
+                ),
             ]
         }
     }
@@ -135,7 +220,7 @@ impl GlobalHotkeySystem {
 impl Drop for GlobalHotkeySystem {
     fn drop(&mut self) {
         // Unregister all hotkeys on drop
-        for hotkey in &self.hotkeys {
+        for (_, hotkey) in &self.hotkeys {
             if let Err(e) = self.manager.unregister(*hotkey) {
                 tracing::error!("Failed to unregister hotkey: {}", e);
             }
@@ -151,6 +236,14 @@ impl GlobalHotkeyHandler {
             Some(GlobalHotkeyId::ToggleMonitoring)
         } else if event.id == self.stop_id {
             Some(GlobalHotkeyId::StopGoalMusic)
+        } else if event.id == self.dump_id {
+            Some(GlobalHotkeyId::DumpFlightRecorder)
+        } else if event.id == self.false_positive_id {
+            Some(GlobalHotkeyId::MarkFalsePositive)
+        } else if event.id == self.missed_goal_id {
+            Some(GlobalHotkeyId::MarkMissedGoal)
+        } else if event.id == self.learn_variation_id {
+            Some(GlobalHotkeyId::LearnVariation)
         } else {
             None
         };
@@ -168,6 +261,10 @@ impl GlobalHotkeyHandler {
         match hotkey_id {
             GlobalHotkeyId::ToggleMonitoring => self.handle_toggle_monitoring(),
             GlobalHotkeyId::StopGoalMusic => self.handle_stop_goal_music(),
+            GlobalHotkeyId::DumpFlightRecorder => self.handle_dump_flight_recorder(),
+            GlobalHotkeyId::MarkFalsePositive => self.handle_mark_false_positive(),
+            GlobalHotkeyId::MarkMissedGoal => self.handle_mark_missed_goal(),
+            GlobalHotkeyId::LearnVariation => self.handle_learn_variation(),
         }
     }
 
@@ -199,6 +296,38 @@ impl GlobalHotkeyHandler {
             tracing::info!("✓ Goal music stopped via global hotkey");
         }
     }
+
+    fn handle_dump_flight_recorder(&self) {
+        if let Err(err) = self.controller.dump_flight_recorder() {
+            tracing::error!("Failed to dump flight recorder via global hotkey: {}", err);
+        } else {
+            tracing::info!("✓ Flight recorder dump requested via global hotkey");
+        }
+    }
+
+    fn handle_mark_false_positive(&self) {
+        if let Err(err) = self.controller.mark_false_positive() {
+            tracing::error!("Failed to mark false positive via global hotkey: {}", err);
+        } else {
+            tracing::info!("✓ False positive marked via global hotkey");
+        }
+    }
+
+    fn handle_mark_missed_goal(&self) {
+        if let Err(err) = self.controller.mark_missed_goal() {
+            tracing::error!("Failed to mark missed goal via global hotkey: {}", err);
+        } else {
+            tracing::info!("✓ Missed goal marked via global hotkey");
+        }
+    }
+
+    fn handle_learn_variation(&self) {
+        if let Err(err) = self.controller.learn_variation(None) {
+            tracing::error!("Failed to learn team variation via global hotkey: {}", err);
+        } else {
+            tracing::info!("✓ Team variation learned via global hotkey");
+        }
+    }
 }
 
 /// Start listening for global hotkey events in a background thread.
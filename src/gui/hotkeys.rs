@@ -11,6 +11,9 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 
+use crate::app_paths;
+use crate::persistence;
+
 /// Represents a single key combination
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Keybinding {
@@ -106,6 +109,10 @@ pub enum ActionId {
     OpenSettings,
     CheckForUpdates,
     QuitApp,
+    DumpFlightRecorder,
+    MarkFalsePositive,
+    MarkMissedGoal,
+    LearnVariation,
 }
 
 impl ActionId {
@@ -129,6 +136,10 @@ impl ActionId {
             ActionId::OpenSettings => "Open settings",
             ActionId::CheckForUpdates => "Check for updates",
             ActionId::QuitApp => "Quit application",
+            ActionId::DumpFlightRecorder => "Save recent detection frames to disk",
+            ActionId::MarkFalsePositive => "Mark last goal as wrong and stop music",
+            ActionId::MarkMissedGoal => "Mark a goal that was not detected",
+            ActionId::LearnVariation => "Add last goal banner text as a team variation",
         }
     }
 
@@ -138,7 +149,11 @@ impl ActionId {
             ActionId::ToggleMonitoring
             | ActionId::StopGoalMusic
             | ActionId::OpenRegionSelector
-            | ActionId::CapturePreview => "Monitoring",
+            | ActionId::CapturePreview
+            | ActionId::DumpFlightRecorder
+            | ActionId::MarkFalsePositive
+            | ActionId::MarkMissedGoal
+            | ActionId::LearnVariation => "Monitoring",
             ActionId::PreviewPlayPause | ActionId::AddMusicFile | ActionId::RemoveMusicFile => {
                 "Music Library"
             }
@@ -171,6 +186,10 @@ impl ActionId {
             ActionId::OpenSettings,
             ActionId::CheckForUpdates,
             ActionId::QuitApp,
+            ActionId::DumpFlightRecorder,
+            ActionId::MarkFalsePositive,
+            ActionId::MarkMissedGoal,
+            ActionId::LearnVariation,
         ]
     }
 }
@@ -232,27 +251,49 @@ impl Default for HotkeyConfig {
         bindings.insert(ActionId::CheckForUpdates, Keybinding::ctrl_cmd("u"));
         bindings.insert(ActionId::QuitApp, Keybinding::ctrl_cmd("q"));
 
+        // Diagnostics
+        bindings.insert(ActionId::DumpFlightRecorder, Keybinding::ctrl_cmd("d"));
+        bindings.insert(
+            ActionId::MarkFalsePositive,
+            Keybinding::new("f", true, false, true), // Ctrl/Cmd + Alt + F
+        );
+        bindings.insert(
+            ActionId::MarkMissedGoal,
+            Keybinding::new("m", true, false, true), // Ctrl/Cmd + Alt + M
+        );
+        bindings.insert(
+            ActionId::LearnVariation,
+            Keybinding::new("l", true, false, true), // Ctrl/Cmd + Alt + L
+        );
+
         Self { bindings }
     }
 }
 
 impl HotkeyConfig {
     /// Load hotkey configuration from disk, creating default if it doesn't exist
+    ///
+    /// An unreadable hotkeys.json is replaced by its newest readable backup,
+    /// or by the default bindings if there is none.
     pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
         let config_path = Self::config_path()?;
 
-        if config_path.exists() {
-            let content = fs::read_to_string(&config_path)?;
-            let loaded: HotkeyConfig = serde_json::from_str(&content)?;
-
+        let recovered = persistence::load_with_recovery(&config_path, |content| {
+            serde_json::from_str::<HotkeyConfig>(content)
+        })?;
+        if let Some(recovered) = recovered {
             // Merge loaded bindings over defaults so new actions (like QuitApp)
             // automatically get default shortcuts if they were not present
             // when the config file was first created.
             let mut config = HotkeyConfig::default();
-            for (action, binding) in loaded.bindings {
+            for (action, binding) in recovered.value.into_iter().flat_map(|l| l.bindings) {
                 config.bindings.insert(action, binding);
             }
 
+            if let Some(problem) = recovered.problem {
+                tracing::warn!("{}", problem);
+                config.save()?;
+            }
             tracing::info!("✓ Loaded hotkey config from: {}", config_path.display());
             Ok(config)
         } else {
@@ -277,17 +318,14 @@ impl HotkeyConfig {
         }
 
         let json = serde_json::to_string_pretty(self)?;
-        fs::write(&config_path, json)?;
+        persistence::write_with_backups(&config_path, json.as_bytes())?;
 
         Ok(())
     }
 
     /// Get the hotkey config file path
     pub fn config_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
-        let base = dirs::config_dir().ok_or("Could not determine user config directory")?;
-        let app_dir = base.join("FMGoalMusic");
-        fs::create_dir_all(&app_dir)?;
-        Ok(app_dir.join("hotkeys.json"))
+        Ok(app_paths::config_dir()?.join("hotkeys.json"))
     }
 
     /// Get keybinding for a specific action
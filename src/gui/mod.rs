@@ -1,21 +1,27 @@
 mod actions;
 mod controller;
+mod event_stream;
 mod global_hotkeys;
 mod hotkeys;
+mod remote_control;
 mod state;
 mod theme;
 mod view;
 
 use actions::*;
 use controller::GuiController;
+use event_stream::start_event_stream;
 use global_hotkeys::{start_global_hotkey_listener, GlobalHotkeySystem};
 use gpui::{
     px, size, AnyView, App, AppContext, Application, Bounds, KeyBinding, WindowBounds,
     WindowOptions,
 };
 use hotkeys::{ActionId, HotkeyConfig};
+use remote_control::start_remote_control;
 use view::MainView;
 
+use crate::messaging::Event;
+
 /// Register keyboard shortcuts from the hotkey configuration
 fn register_keybindings(cx: &mut App) {
     let config = HotkeyConfig::load().unwrap_or_default();
@@ -144,18 +150,59 @@ fn register_keybindings(cx: &mut App) {
                     Some("main_view"),
                 )]);
             }
+            ActionId::DumpFlightRecorder => {
+                cx.bind_keys([KeyBinding::new(
+                    keystroke.as_str(),
+                    DumpFlightRecorder,
+                    Some("main_view"),
+                )]);
+            }
+            ActionId::MarkFalsePositive => {
+                cx.bind_keys([KeyBinding::new(
+                    keystroke.as_str(),
+                    MarkFalsePositive,
+                    Some("main_view"),
+                )]);
+            }
+            ActionId::MarkMissedGoal => {
+                cx.bind_keys([KeyBinding::new(
+                    keystroke.as_str(),
+                    MarkMissedGoal,
+                    Some("main_view"),
+                )]);
+            }
+            ActionId::LearnVariation => {
+                cx.bind_keys([KeyBinding::new(
+                    keystroke.as_str(),
+                    LearnVariation,
+                    Some("main_view"),
+                )]);
+            }
         }
     }
 }
 
-pub fn run() -> anyhow::Result<()> {
-    let controller = GuiController::new()?;
+/// Open the main window, using the configuration profile `profile` or the
+/// one used last if `None`
+pub fn run(profile: Option<String>) -> anyhow::Result<()> {
+    let controller = GuiController::new(profile)?;
+    controller.start_file_watcher();
 
     // Initialize global hotkeys (work system-wide, even when app is not focused)
     let global_hotkeys = GlobalHotkeySystem::new(controller.clone())?;
     let handler = global_hotkeys.create_handler();
     start_global_hotkey_listener(handler)?;
 
+    // Optional HTTP control for Stream Deck and scripts; the app works without it
+    if let Err(err) = start_remote_control(controller.clone()) {
+        tracing::warn!("Remote control API not started: {err:#}");
+        controller.set_status(format!("Remote control API not started: {err:#}"));
+    }
+    if let Err(err) = start_event_stream(&controller) {
+        tracing::warn!("Event stream not started: {err:#}");
+        controller.set_status(format!("Event stream not started: {err:#}"));
+    }
+
     let application = Application::new();
 
     application.run(move |cx: &mut App| {
@@ -165,6 +212,15 @@ pub fn run() -> anyhow::Result<()> {
         // Register keyboard shortcuts (only work when app is focused)
         register_keybindings(cx);
 
+        // Settings saves are batched; write the last ones before exiting
+        let quit_controller = controller.clone();
+        cx.on_app_quit(move |_| {
+            quit_controller.events().publish(Event::Shutdown);
+            quit_controller.flush_settings();
+            async {}
+        })
+        .detach();
+
         let bounds = Bounds::centered(None, size(px(1180.0), px(760.0)), cx);
         let controller = controller.clone();
 
@@ -186,5 +242,6 @@ pub fn run() -> anyhow::Result<()> {
         cx.activate(true);
     });
 
+    controller.flush_settings();
     Ok(())
 }
@@ -0,0 +1,467 @@
+//! Local HTTP control and status API
+//!
+//! Lets Stream Deck, Touch Portal and scripts drive the app while FM runs
+//! fullscreen. The server is off by default, only listens on 127.0.0.1 and
+//! rejects requests without the token from `remote_control.json`. Every
+//! request goes through the same `GuiController` methods as the window.
+
+use std::fmt;
+use std::fs;
+use std::io::Read;
+use std::path::PathBuf;
+use std::thread;
+use std::time::UNIX_EPOCH;
+
+use anyhow::{anyhow, Result};
+use rand::Rng;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use super::controller::GuiController;
+use crate::app_paths;
+use crate::persistence;
+
+/// Port used when `remote_control.json` doesn't name one
+pub const DEFAULT_PORT: u16 = 7373;
+
+/// Port of the WebSocket event stream when `remote_control.json` doesn't name one
+pub const DEFAULT_EVENTS_PORT: u16 = 7374;
+
+/// Largest request body read, in bytes
+const MAX_BODY_BYTES: u64 = 16 * 1024;
+
+/// Settings of the control API, kept in `remote_control.json` beside the
+/// config so they don't change with the profile
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RemoteControlConfig {
+    /// Start the HTTP server with the app
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Port on 127.0.0.1 to listen on
+    #[serde(default = "default_port")]
+    pub port: u16,
+
+    /// Secret every request has to present
+    #[serde(default)]
+    pub token: String,
+
+    /// Start the WebSocket event stream with the app
+    #[serde(default)]
+    pub events_enabled: bool,
+
+    /// Port on 127.0.0.1 the event stream listens on
+    #[serde(default = "default_events_port")]
+    pub events_port: u16,
+}
+
+fn default_port() -> u16 {
+    DEFAULT_PORT
+}
+
+fn default_events_port() -> u16 {
+    DEFAULT_EVENTS_PORT
+}
+
+impl Default for RemoteControlConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: DEFAULT_PORT,
+            token: generate_token(),
+            events_enabled: false,
+            events_port: DEFAULT_EVENTS_PORT,
+        }
+    }
+}
+
+impl RemoteControlConfig {
+    /// Load the settings, creating the file with a fresh token if needed
+    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        let path = Self::config_path()?;
+        let recovered = persistence::load_with_recovery(&path, |content| {
+            serde_json::from_str::<RemoteControlConfig>(content)
+        })?;
+
+        let (mut config, mut changed) = match recovered {
+            Some(recovered) => {
+                if let Some(problem) = &recovered.problem {
+                    tracing::warn!("{}", problem);
+                }
+                let changed = recovered.problem.is_some() || recovered.value.is_none();
+                (recovered.value.unwrap_or_default(), changed)
+            }
+            None => (Self::default(), true),
+        };
+        if config.token.trim().is_empty() {
+            config.token = generate_token();
+            changed = true;
+        }
+        if changed {
+            config.save()?;
+        }
+        Ok(config)
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let path = Self::config_path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self)?;
+        persistence::write_with_backups(&path, json.as_bytes())?;
+        Ok(())
+    }
+
+    pub fn config_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+        Ok(app_paths::config_dir()?.join("remote_control.json"))
+    }
+}
+
+/// 32 random hex digits
+fn generate_token() -> String {
+    let bytes: [u8; 16] = rand::thread_rng().gen();
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// A request the API understands
+#[derive(Debug, Clone, PartialEq)]
+enum ApiRequest {
+    Status,
+    Tracks,
+    StartMonitoring,
+    StopMonitoring,
+    StopMusic,
+    Goal {
+        team: Option<String>,
+    },
+    PlayTrack(usize),
+    SetVolume {
+        music: Option<f32>,
+        ambiance: Option<f32>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct ApiError {
+    status: u16,
+    message: String,
+}
+
+impl ApiError {
+    fn new(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.status, self.message)
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct GoalBody {
+    team: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct VolumeBody {
+    music: Option<f32>,
+    ambiance: Option<f32>,
+}
+
+/// Work out what a request asks for
+fn parse_request(method: &Method, path: &str, body: &str) -> Result<ApiRequest, ApiError> {
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    let request = match (method, segments.as_slice()) {
+        (Method::Get, ["status"]) => ApiRequest::Status,
+        (Method::Get, ["tracks"]) => ApiRequest::Tracks,
+        (Method::Post, ["monitoring", "start"]) => ApiRequest::StartMonitoring,
+        (Method::Post, ["monitoring", "stop"]) => ApiRequest::StopMonitoring,
+        (Method::Post, ["music", "stop"]) => ApiRequest::StopMusic,
+        (Method::Post, ["goal"]) => {
+            let body: GoalBody = parse_body(body)?;
+            ApiRequest::Goal {
+                team: body.team.filter(|team| !team.trim().is_empty()),
+            }
+        }
+        (Method::Post, ["tracks", index, "play"]) => {
+            let index = index
+                .parse()
+                .map_err(|_| ApiError::new(400, format!("'{index}' is not a track index")))?;
+            ApiRequest::PlayTrack(index)
+        }
+        (Method::Post, ["volume"]) => {
+            let body: VolumeBody = parse_body(body)?;
+            for volume in [body.music, body.ambiance].into_iter().flatten() {
+                if !(0.0..=1.0).contains(&volume) {
+                    return Err(ApiError::new(400, "Volumes go from 0.0 to 1.0"));
+                }
+            }
+            if body.music.is_none() && body.ambiance.is_none() {
+                return Err(ApiError::new(400, "Give 'music' and/or 'ambiance'"));
+            }
+            ApiRequest::SetVolume {
+                music: body.music,
+                ambiance: body.ambiance,
+            }
+        }
+        (_, ["status" | "tracks" | "goal" | "volume"])
+        | (_, ["monitoring", "start" | "stop"])
+        | (_, ["music", "stop"])
+        | (_, ["tracks", _, "play"]) => {
+            return Err(ApiError::new(405, format!("{method} is not allowed here")))
+        }
+        _ => return Err(ApiError::new(404, format!("No endpoint at {path}"))),
+    };
+    Ok(request)
+}
+
+/// An empty body parses as the default
+fn parse_body<T: Default + DeserializeOwned>(body: &str) -> Result<T, ApiError> {
+    if body.trim().is_empty() {
+        return Ok(T::default());
+    }
+    serde_json::from_str(body).map_err(|err| ApiError::new(400, format!("Invalid JSON: {err}")))
+}
+
+/// Whether the request carries `token`, as `Authorization: Bearer <token>`
+/// or, for tools that can't set headers, as `?token=<token>`
+pub(super) fn is_authorized(authorization: Option<&str>, query: Option<&str>, token: &str) -> bool {
+    if token.is_empty() {
+        return false;
+    }
+    let from_header = authorization.and_then(|value| value.trim().strip_prefix("Bearer "));
+    let from_query = query.and_then(|query| {
+        query
+            .split('&')
+            .find_map(|pair| pair.strip_prefix("token="))
+    });
+    [from_header, from_query]
+        .into_iter()
+        .flatten()
+        .any(|candidate| constant_time_eq(candidate.trim().as_bytes(), token.as_bytes()))
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+/// Run a request against the controller
+fn execute(controller: &GuiController, request: ApiRequest) -> Result<Value> {
+    match request {
+        ApiRequest::Status => Ok(status(controller)),
+        ApiRequest::Tracks => {
+            let state = controller.state();
+            let guard = state.lock();
+            let tracks: Vec<Value> = guard
+                .music_list
+                .iter()
+                .enumerate()
+                .map(|(index, entry)| json!({ "index": index, "name": entry.name }))
+                .collect();
+            Ok(json!({ "tracks": tracks }))
+        }
+        ApiRequest::StartMonitoring => {
+            controller.start_monitoring()?;
+            Ok(status(controller))
+        }
+        ApiRequest::StopMonitoring => {
+            controller.stop_monitoring()?;
+            Ok(status(controller))
+        }
+        ApiRequest::StopMusic => {
+            controller.stop_goal_music()?;
+            Ok(json!({ "ok": true }))
+        }
+        ApiRequest::Goal { team } => {
+            controller.trigger_manual_goal(team.as_deref())?;
+            Ok(json!({ "ok": true }))
+        }
+        ApiRequest::PlayTrack(index) => {
+            controller.play_library_track(index)?;
+            Ok(json!({ "ok": true }))
+        }
+        ApiRequest::SetVolume { music, ambiance } => {
+            if let Some(volume) = music {
+                controller.set_music_volume(volume)?;
+            }
+            if let Some(volume) = ambiance {
+                controller.set_ambiance_volume(volume)?;
+            }
+            Ok(status(controller))
+        }
+    }
+}
+
+fn status(controller: &GuiController) -> Value {
+    let state = controller.state();
+    let guard = state.lock();
+    // Volumes of the celebration shown in the app, which /volume changes
+    let celebration = guard.celebration();
+    let last_goal = guard.last_goal_at.map(|at| {
+        json!({
+            "team": guard.last_scoring_team.as_ref().map(|team| &team.display_name),
+            "at": at.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0),
+        })
+    });
+    json!({
+        "state": guard.process_state.name(),
+        "running_secs": guard.process_state.running_duration().map(|d| d.as_secs()),
+        "detection_count": guard.detection_count,
+        "last_goal": last_goal,
+        "current_track": guard.current_track,
+        "music_volume": celebration.music_volume,
+        "ambiance_volume": celebration.ambiance_volume,
+        "profile": guard.active_profile,
+        "status_message": guard.status_message,
+    })
+}
+
+/// Start the HTTP server on a background thread if it is enabled
+pub fn start_remote_control(controller: GuiController) -> Result<()> {
+    let config = RemoteControlConfig::load()
+        .map_err(|err| anyhow!("Failed to load remote control settings: {err}"))?;
+    if !config.enabled {
+        return Ok(());
+    }
+
+    let server = Server::http(("127.0.0.1", config.port))
+        .map_err(|err| anyhow!("Failed to listen on 127.0.0.1:{}: {err}", config.port))?;
+    tracing::info!("Remote control API listening on 127.0.0.1:{}", config.port);
+
+    thread::Builder::new()
+        .name("remote-control".to_string())
+        .spawn(move || {
+            for request in server.incoming_requests() {
+                handle(&controller, &config.token, request);
+            }
+        })?;
+    Ok(())
+}
+
+fn handle(controller: &GuiController, token: &str, mut request: Request) {
+    let url = request.url().to_string();
+    let (path, query) = match url.split_once('?') {
+        Some((path, query)) => (path, Some(query)),
+        None => (url.as_str(), None),
+    };
+    let authorization = request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv("Authorization"))
+        .map(|header| header.value.as_str().to_string());
+
+    let mut body = String::new();
+    let read = request
+        .as_reader()
+        .take(MAX_BODY_BYTES)
+        .read_to_string(&mut body);
+
+    let result = if !is_authorized(authorization.as_deref(), query, token) {
+        Err(ApiError::new(401, "Missing or wrong token"))
+    } else if let Err(err) = read {
+        Err(ApiError::new(400, format!("Unreadable body: {err}")))
+    } else {
+        parse_request(request.method(), path, &body).and_then(|api_request| {
+            execute(controller, api_request).map_err(|err| ApiError::new(409, format!("{err:#}")))
+        })
+    };
+
+    let (status, body) = match result {
+        Ok(value) => (200, value),
+        Err(err) => {
+            tracing::debug!("Remote control {} {}: {}", request.method(), path, err);
+            (err.status, json!({ "error": err.message }))
+        }
+    };
+    let response = Response::from_string(body.to_string())
+        .with_status_code(status)
+        .with_header(
+            Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..])
+                .expect("static header is valid"),
+        );
+    if let Err(err) = request.respond(response) {
+        tracing::warn!("Failed to answer remote control request: {err}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_request_routes() {
+        assert_eq!(
+            parse_request(&Method::Get, "/status", ""),
+            Ok(ApiRequest::Status)
+        );
+        assert_eq!(
+            parse_request(&Method::Post, "/monitoring/start", ""),
+            Ok(ApiRequest::StartMonitoring)
+        );
+        assert_eq!(
+            parse_request(&Method::Post, "/goal", r#"{"team": "Arsenal"}"#),
+            Ok(ApiRequest::Goal {
+                team: Some("Arsenal".to_string())
+            })
+        );
+        assert_eq!(
+            parse_request(&Method::Post, "/goal", ""),
+            Ok(ApiRequest::Goal { team: None })
+        );
+        assert_eq!(
+            parse_request(&Method::Post, "/tracks/2/play", ""),
+            Ok(ApiRequest::PlayTrack(2))
+        );
+        assert_eq!(
+            parse_request(&Method::Post, "/volume", r#"{"music": 0.5}"#),
+            Ok(ApiRequest::SetVolume {
+                music: Some(0.5),
+                ambiance: None
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_request_errors() {
+        let error_status =
+            |method, path, body| parse_request(&method, path, body).unwrap_err().status;
+
+        assert_eq!(error_status(Method::Post, "/status", ""), 405);
+        assert_eq!(error_status(Method::Get, "/monitoring/start", ""), 405);
+        assert_eq!(error_status(Method::Get, "/nothing", ""), 404);
+        assert_eq!(error_status(Method::Post, "/tracks/x/play", ""), 400);
+        assert_eq!(error_status(Method::Post, "/goal", "{"), 400);
+        assert_eq!(
+            error_status(Method::Post, "/volume", r#"{"music": 2.0}"#),
+            400
+        );
+        assert_eq!(error_status(Method::Post, "/volume", "{}"), 400);
+    }
+
+    #[test]
+    fn test_token_is_required() {
+        let token = "abc123";
+
+        assert!(is_authorized(Some("Bearer abc123"), None, token));
+        assert!(is_authorized(None, Some("x=1&token=abc123"), token));
+        assert!(!is_authorized(Some("Bearer abc124"), None, token));
+        assert!(!is_authorized(Some("abc123"), None, token));
+        assert!(!is_authorized(None, None, token));
+        assert!(!is_authorized(None, Some("token="), ""));
+    }
+
+    #[test]
+    fn test_generated_tokens_differ() {
+        let token = generate_token();
+        assert_eq!(token.len(), 32);
+        assert_ne!(token, generate_token());
+    }
+}
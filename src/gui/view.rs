@@ -2,12 +2,13 @@ use std::{
     fs,
     path::{Path, PathBuf},
     sync::Arc,
+    time::Duration,
 };
 
 use gpui::prelude::FluentBuilder;
 use gpui::{
     div, img, px, AnyElement, AppContext, Bounds, ClickEvent, Context, CursorStyle, Element,
-    Entity, FocusHandle, Focusable, Image as GpuiImage, ImageFormat, InteractiveElement,
+    Entity, FocusHandle, Focusable, Hsla, Image as GpuiImage, ImageFormat, InteractiveElement,
     IntoElement, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent, ObjectFit,
     ParentElement, Pixels, Point, Render, SharedString, Styled, StyledImage, Subscription, Window,
 };
@@ -22,11 +23,20 @@ use gpui_component::{
 };
 
 use super::actions::{self, *};
-use super::controller::{GuiController, RegionCapture};
+use super::controller::{
+    GuiController, RegionCapture, RegionProposal, SpecialCelebration, TeamPackPreview,
+};
 use super::hotkeys::{ActionId, HotkeyConfig};
 use super::state::AppTab;
+use crate::app_paths;
 use crate::audio::AudioManager;
-use crate::state::{MusicEntry, ProcessState};
+use crate::config::{CelebrationOverride, SelectedTeam, TeamProfile};
+use crate::flight_recorder::FrameDecision;
+use crate::profiles;
+use crate::slug::slugify;
+use crate::state::{DetectionMetrics, MusicEntry, OcrDebugFrame, ProcessState, Stage};
+use crate::team_pack::{ConflictResolution, PACK_EXTENSION};
+use crate::teams::TeamDatabase;
 
 struct PreviewSound {
     manager: AudioManager,
@@ -50,7 +60,31 @@ struct AddTeamForm {
     logo_preview: Option<Arc<GpuiImage>>,
 }
 
+struct EditTeamForm {
+    expanded: bool,
+    /// Team currently loaded into the inputs
+    editing: Option<SelectedTeam>,
+    error: Option<String>,
+    team_name_input: Entity<InputState>,
+    variations_input: Entity<InputState>,
+    league_name_input: Entity<InputState>,
+    /// How often each variation of the edited team matched a goal banner
+    match_counts: Vec<(String, u32)>,
+    /// Variations that never matched although the team is matched regularly
+    stale_variations: Vec<String>,
+}
+
+/// Setting a region selection is applied to
+#[derive(Clone, Copy, PartialEq)]
+enum RegionTarget {
+    /// Where goal banners appear
+    Goal,
+    /// Where the competition name is shown
+    Competition,
+}
+
 struct RegionSelection {
+    target: RegionTarget,
     image_path: PathBuf,
     physical_size: (u32, u32),
     logical_size: (u32, u32),
@@ -67,6 +101,7 @@ impl RegionSelection {
         let physical_w = capture.physical_size.0.max(1) as f32;
         let render_scale = (960.0 / physical_w).min(1.0);
         Self {
+            target: RegionTarget::Goal,
             image_path: capture.image_path,
             physical_size: capture.physical_size,
             logical_size: capture.logical_size,
@@ -79,6 +114,20 @@ impl RegionSelection {
         }
     }
 
+    /// Open the selector with the proposed rectangle already drawn
+    fn from_proposal(proposal: RegionProposal) -> Self {
+        let mut selection = Self::from_capture(proposal.capture);
+        let [x, y, w, h] = proposal.physical_rect;
+        let scale = selection.render_scale;
+        selection.drag_start = Some(Point::new(px(x as f32 * scale), px(y as f32 * scale)));
+        selection.drag_current = Some(Point::new(
+            px((x + w) as f32 * scale),
+            px((y + h) as f32 * scale),
+        ));
+        selection.points_local = true;
+        selection
+    }
+
     fn display_size(&self) -> (f32, f32) {
         (
             self.physical_size.0 as f32 * self.render_scale,
@@ -273,8 +322,11 @@ pub struct MainView {
     ambiance_length_slider: Entity<SliderState>,
     ocr_slider: Entity<SliderState>,
     debounce_slider: Entity<SliderState>,
+    team_match_slider: Entity<SliderState>,
     language_select: Entity<SelectState<Vec<LanguageOption>>>,
     custom_phrase_input: Entity<InputState>,
+    competition_input: Entity<InputState>,
+    profile_name_input: Entity<InputState>,
     subscriptions: Vec<Subscription>,
     music_preview: Option<PreviewSound>,
     music_preview_playing: bool,
@@ -282,12 +334,22 @@ pub struct MainView {
     ambiance_preview_playing: bool,
     cached_audio: Option<CachedAudio>,
     add_team_form: AddTeamForm,
+    edit_team_form: EditTeamForm,
     monitor_select: Entity<SelectState<Vec<MonitorOption>>>,
     monitor_options: Vec<MonitorOption>,
     region_selection: Option<RegionSelection>,
     region_canvas_bounds: Option<Bounds<Pixels>>,
     hotkey_config: HotkeyConfig,
     search_query: String,
+    pending_team_pack: Option<TeamPackPreview>,
+    /// `settings_generation` the controls were last synced with
+    seen_settings_generation: u64,
+    /// Detection metrics frame count last drawn
+    seen_metrics_frames: u64,
+    /// Decoded images of the OCR debug frame with this sequence number
+    ocr_debug_images: Option<(u64, Arc<GpuiImage>, Option<Arc<GpuiImage>>)>,
+    /// Auto-Find is scanning the screen on a background thread
+    finding_region: bool,
 }
 
 impl MainView {
@@ -319,19 +381,21 @@ impl MainView {
             ambiance_length_ms,
             ocr_threshold,
             debounce_ms,
+            team_match_threshold,
             selected_team,
             selected_monitor_index,
         ) = {
             let state = controller.state();
             let guard = state.lock();
             (
-                guard.music_volume,
-                guard.ambiance_volume,
+                guard.celebration().music_volume,
+                guard.celebration().ambiance_volume,
                 guard.music_length_ms,
                 guard.ambiance_length_ms,
                 guard.ocr_threshold,
                 guard.debounce_ms,
-                guard.selected_team.clone(),
+                guard.team_match_threshold,
+                guard.focused_team.clone(),
                 guard.selected_monitor_index,
             )
         };
@@ -378,6 +442,13 @@ impl MainView {
                 .step(100.)
                 .default_value(debounce_ms as f32)
         });
+        let team_match_slider = cx.new(|_| {
+            SliderState::new()
+                .min(50.)
+                .max(100.)
+                .step(1.)
+                .default_value((team_match_threshold * 100.0).round())
+        });
 
         // Language selector
         let languages = GuiController::get_available_languages();
@@ -393,6 +464,17 @@ impl MainView {
         // Custom phrase input
         let custom_phrase_input =
             cx.new(|cx| InputState::new(window, cx).placeholder("Add custom goal phrase"));
+        let competition_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("Competition as shown on screen, e.g. Champions League")
+                .clean_on_escape()
+        });
+
+        let profile_name_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("Profile name, e.g. Laptop")
+                .clean_on_escape()
+        });
 
         let active_league = selected_team.as_ref().map(|team| team.league.clone());
 
@@ -412,6 +494,19 @@ impl MainView {
                 .rows(4)
                 .placeholder("Variations (one per line)")
         });
+        let edit_team_name_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder("Team display name"));
+        let edit_variations_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .multi_line()
+                .rows(4)
+                .placeholder("Variations (one per line)")
+        });
+        let league_name_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("New league name")
+                .clean_on_escape()
+        });
 
         let monitor_options = {
             let summaries = controller.monitor_summaries();
@@ -453,8 +548,11 @@ impl MainView {
             ambiance_length_slider,
             ocr_slider,
             debounce_slider,
+            team_match_slider,
             language_select,
             custom_phrase_input,
+            competition_input,
+            profile_name_input,
             subscriptions: Vec::new(),
             music_preview: None,
             music_preview_playing: false,
@@ -472,23 +570,132 @@ impl MainView {
                 logo_path: None,
                 logo_preview: None,
             },
+            edit_team_form: EditTeamForm {
+                expanded: false,
+                editing: None,
+                error: None,
+                team_name_input: edit_team_name_input,
+                variations_input: edit_variations_input,
+                league_name_input,
+                match_counts: Vec::new(),
+                stale_variations: Vec::new(),
+            },
             monitor_select,
             monitor_options,
             region_selection: None,
             region_canvas_bounds: None,
             hotkey_config,
+            pending_team_pack: None,
+            seen_settings_generation: 0,
+            seen_metrics_frames: 0,
+            ocr_debug_images: None,
+            finding_region: false,
         };
 
         view.register_slider_subscriptions(cx);
         view.register_monitor_subscription(cx);
         view.register_language_subscription(cx);
+        view.watch_background_changes(cx);
         view
     }
 
+    /// Redraw when the controller changes state on its own, such as when a
+    /// settings file edited on disk is reloaded
+    fn watch_background_changes(&mut self, cx: &mut Context<Self>) {
+        cx.spawn(async move |this, cx| loop {
+            cx.background_executor()
+                .timer(Duration::from_millis(500))
+                .await;
+            let updated = this.update(cx, |view, cx| {
+                let (generation, metrics_frames) = {
+                    let state = view.controller.state();
+                    let guard = state.lock();
+                    (guard.settings_generation, guard.detection_metrics.frames)
+                };
+                let status = view.controller.status_message();
+                let metrics_changed = view.active_tab == AppTab::Detection
+                    && metrics_frames != view.seen_metrics_frames;
+                if generation != view.seen_settings_generation
+                    || status.as_str() != view.status_text.as_ref()
+                    || metrics_changed
+                {
+                    cx.notify();
+                }
+            });
+            if updated.is_err() {
+                break;
+            }
+        })
+        .detach();
+    }
+
     fn refresh_status(&mut self) {
         self.status_text = self.controller.status_message().into();
     }
 
+    /// Move sliders and pickers to the values in the state, after a profile
+    /// switch or an outside edit replaced them
+    fn sync_controls_with_state(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let state = self.controller.state();
+        let guard = state.lock();
+        let sliders = [
+            (
+                &self.music_volume_slider,
+                (guard.celebration().music_volume * 100.0).round(),
+            ),
+            (
+                &self.ambiance_volume_slider,
+                (guard.celebration().ambiance_volume * 100.0).round(),
+            ),
+            (
+                &self.music_length_slider,
+                (guard.music_length_ms as f32 / 1000.0).clamp(1.0, 60.0),
+            ),
+            (
+                &self.ambiance_length_slider,
+                (guard.ambiance_length_ms as f32 / 1000.0).clamp(1.0, 60.0),
+            ),
+            (&self.ocr_slider, guard.ocr_threshold as f32),
+            (&self.debounce_slider, guard.debounce_ms as f32),
+            (
+                &self.team_match_slider,
+                (guard.team_match_threshold * 100.0).round(),
+            ),
+        ];
+        let monitor_ix = self
+            .monitor_options
+            .iter()
+            .position(|option| option.value == guard.selected_monitor_index)
+            .map(|idx| IndexPath::default().row(idx));
+        self.active_league = guard.focused_team.as_ref().map(|team| team.league.clone());
+        drop(guard);
+
+        for (slider, value) in sliders {
+            slider.update(cx, |slider, cx| slider.set_value(value, window, cx));
+        }
+        self.monitor_select.update(cx, |select, cx| {
+            select.set_selected_index(monitor_ix, window, cx)
+        });
+    }
+
+    /// Show the outcome of a profile action; controls resync on the next render
+    fn finish_profile_action(
+        &mut self,
+        result: anyhow::Result<()>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        match result {
+            Ok(()) => {
+                self.profile_name_input
+                    .update(cx, |input, cx| input.set_value("", window, cx));
+                self.refresh_status();
+            }
+            Err(err) => self.status_text = format!("{err:#}").into(),
+        }
+        cx.notify();
+    }
+
     fn register_slider_subscriptions(&mut self, cx: &mut Context<Self>) {
         let subscribe_volume = cx.subscribe(
             &self.music_volume_slider,
@@ -596,6 +803,24 @@ impl MainView {
             },
         );
         self.subscriptions.push(subscribe_debounce);
+
+        let subscribe_team_match = cx.subscribe(
+            &self.team_match_slider,
+            |this, _, event: &SliderEvent, cx| match event {
+                SliderEvent::Change(value) => {
+                    if let Err(err) = this
+                        .controller
+                        .set_team_match_threshold(value.start().round() / 100.0)
+                    {
+                        this.status_text = format!("{err:#}").into();
+                    } else {
+                        this.refresh_status();
+                    }
+                    cx.notify();
+                }
+            },
+        );
+        self.subscriptions.push(subscribe_team_match);
     }
 
     fn register_monitor_subscription(&mut self, cx: &mut Context<Self>) {
@@ -793,7 +1018,7 @@ impl MainView {
 
     /// Increase music volume
     fn increase_volume(&mut self, _: &IncreaseVolume, window: &mut Window, cx: &mut Context<Self>) {
-        let current_volume = self.controller.state().lock().music_volume;
+        let current_volume = self.controller.state().lock().celebration().music_volume;
         let new_volume = (current_volume + 0.05).min(1.0);
         if let Err(err) = self.controller.set_music_volume(new_volume) {
             self.status_text = format!("Failed to adjust volume: {err:#}").into();
@@ -811,7 +1036,7 @@ impl MainView {
         _window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        let current_volume = self.controller.state().lock().music_volume;
+        let current_volume = self.controller.state().lock().celebration().music_volume;
         let new_volume = (current_volume - 0.05).max(0.0);
         if let Err(err) = self.controller.set_music_volume(new_volume) {
             self.status_text = format!("Failed to adjust volume: {err:#}").into();
@@ -829,7 +1054,7 @@ impl MainView {
         _window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        let current_volume = self.controller.state().lock().ambiance_volume;
+        let current_volume = self.controller.state().lock().celebration().ambiance_volume;
         let new_volume = (current_volume + 0.05).min(1.0);
         if let Err(err) = self.controller.set_ambiance_volume(new_volume) {
             self.status_text = format!("Failed to adjust ambiance volume: {err:#}").into();
@@ -847,7 +1072,7 @@ impl MainView {
         _window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        let current_volume = self.controller.state().lock().ambiance_volume;
+        let current_volume = self.controller.state().lock().celebration().ambiance_volume;
         let new_volume = (current_volume - 0.05).max(0.0);
         if let Err(err) = self.controller.set_ambiance_volume(new_volume) {
             self.status_text = format!("Failed to adjust ambiance volume: {err:#}").into();
@@ -882,18 +1107,196 @@ impl MainView {
         cx.quit();
     }
 
+    /// Save the detection flight recorder to disk
+    fn dump_flight_recorder(
+        &mut self,
+        _: &DumpFlightRecorder,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Err(err) = self.controller.dump_flight_recorder() {
+            self.status_text = format!("{err:#}").into();
+        } else {
+            self.refresh_status();
+        }
+        cx.notify();
+    }
+
+    /// Mark the last goal trigger as a false positive
+    fn mark_false_positive(
+        &mut self,
+        _: &MarkFalsePositive,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Err(err) = self.controller.mark_false_positive() {
+            self.status_text = format!("{err:#}").into();
+        } else {
+            self.refresh_status();
+        }
+        cx.notify();
+    }
+
+    /// Mark the last few seconds as containing a missed goal
+    fn mark_missed_goal(
+        &mut self,
+        _: &MarkMissedGoal,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Err(err) = self.controller.mark_missed_goal() {
+            self.status_text = format!("{err:#}").into();
+        } else {
+            self.refresh_status();
+        }
+        cx.notify();
+    }
+
+    /// Add the team text of the last unmatched goal banner as a variation
+    fn learn_variation(
+        &mut self,
+        _: &LearnVariation,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Err(err) = self.controller.learn_variation(None) {
+            self.status_text = format!("{err:#}").into();
+        } else {
+            self.refresh_status();
+        }
+        cx.notify();
+    }
+
     fn render_dashboard_tab(&mut self, cx: &mut Context<Self>) -> impl IntoElement {
         // Get core dashboard state
         let state = self.controller.state();
-        let (selected_team, music_list, playlist_indices) = {
+        let (
+            selected_team,
+            music_list,
+            playlist_indices,
+            last_goal_summary,
+            variation_suggestion,
+            current_match,
+            load_problems,
+        ) = {
             let guard = state.lock();
+            let last_goal_summary = guard.last_scoring_team.as_ref().map(|team| {
+                let goals = guard
+                    .goals_by_team
+                    .get(&team.display_name)
+                    .copied()
+                    .unwrap_or(0);
+                format!(
+                    "Last goal: {} ({}) — {} this session",
+                    team.display_name, team.league, goals
+                )
+            });
             (
-                guard.selected_team.clone(),
+                guard.focused_team.clone(),
                 guard.music_list.clone(),
                 guard.goal_playlist_indices(),
+                last_goal_summary,
+                guard.variation_suggestion.clone(),
+                guard.current_match.clone(),
+                guard.load_problems.clone(),
             )
         };
 
+        // Settings files that were unreadable or rejected after an outside edit
+        let load_notice = (!load_problems.is_empty()).then(|| {
+            div()
+                .flex()
+                .items_center()
+                .justify_between()
+                .gap_3()
+                .p_3()
+                .rounded_lg()
+                .border_1()
+                .border_color(cx.theme().danger)
+                .child(
+                    div().flex().flex_col().gap_1().text_sm().children(
+                        load_problems
+                            .into_iter()
+                            .map(|problem| div().child(problem)),
+                    ),
+                )
+                .child(
+                    Button::new("dismiss-load-problems")
+                        .ghost()
+                        .label("Dismiss")
+                        .on_click(cx.listener(|this, _event: &ClickEvent, _window, cx| {
+                            this.controller.dismiss_load_problems();
+                            cx.notify();
+                        })),
+                )
+        });
+
+        // Clubs of the match being played, detected at kickoff
+        let current_match_row = current_match.map(|(home, away)| {
+            div()
+                .flex()
+                .items_center()
+                .gap_3()
+                .child(self.render_team_logo(&home.team_key, &home.league, 28.0, cx))
+                .child(
+                    div()
+                        .text_lg()
+                        .font_semibold()
+                        .child(format!("{} vs {}", home.display_name, away.display_name)),
+                )
+                .child(self.render_team_logo(&away.team_key, &away.league, 28.0, cx))
+        });
+
+        // Offer a near-miss goal banner as a new variation of a monitored team
+        let variation_prompt = variation_suggestion.map(|(team, text)| {
+            div()
+                .flex()
+                .items_center()
+                .justify_between()
+                .gap_3()
+                .p_3()
+                .rounded_lg()
+                .border_1()
+                .border_color(cx.theme().accent)
+                .child(div().text_sm().child(format!(
+                    "Banner said '{}' — add it as a variation of {}?",
+                    text, team.display_name
+                )))
+                .child(
+                    div()
+                        .flex()
+                        .gap_2()
+                        .child(
+                            Button::new("learn-variation")
+                                .primary()
+                                .label("Add Variation")
+                                .on_click(cx.listener({
+                                    let suggestion = (team.clone(), text.clone());
+                                    move |this, _event: &ClickEvent, _window, cx| {
+                                        if let Err(err) = this
+                                            .controller
+                                            .learn_variation(Some(suggestion.clone()))
+                                        {
+                                            this.status_text = format!("{err:#}").into();
+                                        } else {
+                                            this.refresh_status();
+                                        }
+                                        cx.notify();
+                                    }
+                                })),
+                        )
+                        .child(
+                            Button::new("dismiss-variation")
+                                .ghost()
+                                .label("Dismiss")
+                                .on_click(cx.listener(|this, _event: &ClickEvent, _window, cx| {
+                                    this.controller.dismiss_variation_suggestion();
+                                    cx.notify();
+                                })),
+                        ),
+                )
+        });
+
         // Team callout tile
         let team_callout = div()
             .bg(cx.theme().group_box)
@@ -1007,7 +1410,18 @@ impl MainView {
                         )
                         .into_any_element()
                 },
-            );
+            )
+            .when_some(last_goal_summary, |this, summary| {
+                this.child(
+                    div()
+                        .text_sm()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(summary),
+                )
+            })
+            .when_some(load_notice, |this, notice| this.child(notice))
+            .when_some(current_match_row, |this, row| this.child(row))
+            .when_some(variation_prompt, |this, prompt| this.child(prompt));
 
         // Music cards with a hero area
         let goal_music = div()
@@ -1080,10 +1494,7 @@ impl MainView {
             );
 
         // Get ambiance data from state
-        let ambiance_path = {
-            let guard = state.lock();
-            guard.goal_ambiance_path.clone()
-        };
+        let ambiance_path = state.lock().celebration().ambiance_path;
 
         let other_music = div()
             .bg(cx.theme().group_box)
@@ -1260,9 +1671,8 @@ impl MainView {
         cx: &mut Context<Self>,
     ) -> AnyElement {
         // Try to load logo from user config directory
-        let logo_path = if let Some(base) = dirs::config_dir() {
-            base.join("FMGoalMusic")
-                .join("teams")
+        let logo_path = if let Ok(base) = app_paths::config_dir() {
+            base.join("teams")
                 .join(league)
                 .join(format!("{}.png", team_key))
         } else {
@@ -1337,22 +1747,35 @@ impl MainView {
         team_key: &str,
         team_name: &str,
         is_selected: bool,
+        is_monitored: bool,
         idx: usize,
         league: &str,
     ) -> impl IntoElement {
         let handle_click = cx.listener({
-            let key = team_key.to_string();
-            let league = league.to_string();
+            let team = SelectedTeam {
+                league: league.to_string(),
+                team_key: team_key.to_string(),
+                display_name: team_name.to_string(),
+            };
             move |this, event: &MouseDownEvent, _window, cx| {
-                // Toggle selection: if already selected, deselect; otherwise select
-                if is_selected {
-                    if let Err(err) = this.controller.clear_team_selection() {
-                        this.status_text = format!("{err:#}").into();
-                    }
+                // Clicking the edited team stops monitoring it, another monitored
+                // team switches to its profile, and shift-click monitors a new
+                // team alongside the others instead of replacing the current one;
+                // alt-click marks a club as the edited team's rival
+                let result = if event.modifiers.alt {
+                    this.controller.add_rival(team.clone())
+                } else if is_selected {
+                    this.controller.clear_team_selection()
+                } else if is_monitored {
+                    this.controller.focus_team_profile(&team)
+                } else if event.modifiers.shift {
+                    this.controller
+                        .add_team_profile(&team.league, &team.team_key)
                 } else {
-                    if let Err(err) = this.controller.select_team(&league, &key) {
-                        this.status_text = format!("{err:#}").into();
-                    }
+                    this.controller.select_team(&team.league, &team.team_key)
+                };
+                if let Err(err) = result {
+                    this.status_text = format!("{err:#}").into();
                 }
                 this.refresh_status();
                 cx.notify();
@@ -1363,6 +1786,8 @@ impl MainView {
             .border_1()
             .border_color(if is_selected {
                 cx.theme().accent
+            } else if is_monitored {
+                cx.theme().accent.opacity(0.4)
             } else {
                 cx.theme().border
             })
@@ -1586,7 +2011,12 @@ impl MainView {
         let state = self.controller.state();
         let guard = state.lock();
         let process_state = guard.process_state;
-        let selected_team = guard.selected_team.clone();
+        let active_profile = guard.active_profile.clone();
+        let monitored_names: Vec<String> = guard
+            .team_profiles
+            .iter()
+            .map(|profile| profile.team.display_name.clone())
+            .collect();
         drop(guard);
 
         // Get keyboard shortcut hint for toggle monitoring
@@ -1626,9 +2056,11 @@ impl MainView {
                 }))
         };
 
-        let team_summary = selected_team
-            .map(|team| format!("Watching {}", team.display_name))
-            .unwrap_or_else(|| "Watching any team".to_string());
+        let team_summary = if monitored_names.is_empty() {
+            "Watching any team".to_string()
+        } else {
+            format!("Watching {}", monitored_names.join(", "))
+        };
 
         div()
             .flex()
@@ -1672,6 +2104,12 @@ impl MainView {
                             .text_sm()
                             .text_color(cx.theme().muted_foreground)
                             .child("Monitoring: Monitor 1"),
+                    )
+                    .child(
+                        div()
+                            .text_sm()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(format!("Profile: {active_profile}")),
                     ),
             )
             .child(control_button)
@@ -1712,18 +2150,62 @@ impl MainView {
 
     fn render_library_tab(&mut self, cx: &mut Context<Self>) -> impl IntoElement {
         let state = self.controller.state();
-        let (music_list, selected_index, playlist_indices, ambiance_enabled, ambiance_path) = {
+        let (
+            music_list,
+            selected_index,
+            playlist_indices,
+            ambiance_enabled,
+            ambiance_path,
+            selected_team,
+            focused_profile,
+            opponent,
+        ) = {
             let guard = state.lock();
+            let celebration = guard.celebration();
+            let focused_profile = guard.focused_profile().cloned();
+            // The selected team's opponent in the match being played
+            let opponent = guard.current_match.as_ref().and_then(|(home, away)| {
+                let team = guard.focused_team.as_ref()?;
+                let is = |side: &SelectedTeam| {
+                    side.league == team.league && side.team_key == team.team_key
+                };
+                if is(home) {
+                    Some(away.clone())
+                } else if is(away) {
+                    Some(home.clone())
+                } else {
+                    None
+                }
+            });
             (
                 guard.music_list.clone(),
                 guard.selected_music_index,
-                guard.goal_music_indices.clone(),
+                celebration.goal_music_indices,
                 guard.ambiance_enabled,
-                guard.goal_ambiance_path.clone(),
+                celebration.ambiance_path,
+                guard.focused_team.clone(),
+                focused_profile,
+                opponent,
             )
         };
 
-        let header = div().text_xl().font_semibold().child("Library");
+        // Playlist and ambience belong to the team being edited, if any
+        let header = div()
+            .flex()
+            .flex_col()
+            .gap_1()
+            .child(div().text_xl().font_semibold().child("Library"))
+            .when_some(selected_team.as_ref(), |this, team| {
+                this.child(
+                    div()
+                        .text_sm()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(format!(
+                            "Goal playlist and ambience for {}",
+                            team.display_name
+                        )),
+                )
+            });
 
         div()
             .flex()
@@ -1737,42 +2219,433 @@ impl MainView {
                 &playlist_indices,
             ))
             .child(self.render_ambiance_panel(cx, ambiance_enabled, ambiance_path))
+            .when_some(focused_profile, |this, profile| {
+                this.child(self.render_conceded_panel(
+                    cx,
+                    &profile.team,
+                    profile.conceded_sound_path.clone(),
+                ))
+                .child(self.render_special_matches_panel(
+                    cx,
+                    &profile,
+                    &music_list,
+                    opponent,
+                ))
+            })
     }
 
-    fn render_music_collection_panel(
+    /// Rivals and the derby and competition celebrations of the edited team
+    fn render_special_matches_panel(
         &mut self,
         cx: &mut Context<Self>,
+        profile: &TeamProfile,
         music_list: &[MusicEntry],
-        selected_index: Option<usize>,
-        playlist_indices: &[usize],
+        opponent: Option<SelectedTeam>,
     ) -> impl IntoElement {
-        let header_row = div()
+        let rival_chips = div()
             .flex()
+            .flex_wrap()
             .items_center()
-            .justify_between()
-            .child(div().text_lg().font_semibold().child("Goal Music"))
-            .child(
-                Button::new("library-add-goal")
-                    .primary()
-                    .child(div().mr_1p5().child("+"))
-                    .label("Add Music")
-                    .on_click(cx.listener(|this, _event: &ClickEvent, _window, cx| {
-                        if let Some(path) = rfd::FileDialog::new()
-                            .add_filter("Audio Files", &["mp3", "wav", "ogg", "flac", "m4a"])
-                            .pick_file()
-                        {
-                            if let Err(err) = this.controller.add_music_file(path) {
+            .gap_2()
+            .child(div().text_sm().font_semibold().child("Rivals:"))
+            .when(profile.rivals.is_empty(), |this| {
+                this.child(
+                    div()
+                        .text_sm()
+                        .text_color(cx.theme().muted_foreground)
+                        .child("None yet — Alt-click a club in Team Selection to add one."),
+                )
+            })
+            .children(profile.rivals.iter().enumerate().map(|(idx, rival)| {
+                Button::new(("remove-rival", idx))
+                    .ghost()
+                    .label(format!("{} ✕", rival.display_name))
+                    .on_click(cx.listener({
+                        let rival = rival.clone();
+                        move |this, _event: &ClickEvent, _window, cx| {
+                            if let Err(err) = this.controller.remove_rival(&rival) {
                                 this.status_text = format!("{err:#}").into();
                             } else {
                                 this.refresh_status();
                             }
+                            cx.notify();
                         }
-                        cx.notify();
-                    })),
-            );
-
-        let playlist_summary = {
-            if playlist_indices.is_empty() {
+                    }))
+            }))
+            .when_some(
+                opponent.filter(|opponent| !profile.is_rival(opponent)),
+                |this, opponent| {
+                    this.child(
+                        Button::new("add-opponent-rival")
+                            .primary()
+                            .label(format!("Add {} as Rival", opponent.display_name))
+                            .on_click(cx.listener(
+                                move |this, _event: &ClickEvent, _window, cx| {
+                                    if let Err(err) = this.controller.add_rival(opponent.clone()) {
+                                        this.status_text = format!("{err:#}").into();
+                                    } else {
+                                        this.refresh_status();
+                                    }
+                                    cx.notify();
+                                },
+                            )),
+                    )
+                },
+            );
+
+        let derby = self.render_special_celebration(
+            cx,
+            0,
+            SpecialCelebration::Derby,
+            "Derby celebration".to_string(),
+            profile.derby_celebration.clone().unwrap_or_default(),
+            music_list,
+        );
+        let competitions: Vec<AnyElement> = profile
+            .competition_overrides
+            .iter()
+            .enumerate()
+            .map(|(idx, cup)| {
+                self.render_special_celebration(
+                    cx,
+                    idx + 1,
+                    SpecialCelebration::Competition(cup.competition.clone()),
+                    format!("In '{}'", cup.competition),
+                    cup.celebration.clone(),
+                    music_list,
+                )
+            })
+            .collect();
+
+        let add_competition_button = Button::new("add-competition-override")
+            .label("Add Competition")
+            .on_click(cx.listener(|this, _: &ClickEvent, window, cx| {
+                let name = this.competition_input.read(cx).value().to_string();
+                match this.controller.add_competition_override(name) {
+                    Ok(()) => {
+                        this.competition_input
+                            .update(cx, |input, cx| input.set_value("", window, cx));
+                        this.refresh_status();
+                    }
+                    Err(err) => this.status_text = format!("{err:#}").into(),
+                }
+                cx.notify();
+            }));
+
+        div()
+            .bg(cx.theme().group_box)
+            .border_1()
+            .border_color(cx.theme().border)
+            .rounded_lg()
+            .p_5()
+            .flex()
+            .flex_col()
+            .gap_3()
+            .child(
+                div()
+                    .text_lg()
+                    .font_semibold()
+                    .child("Derbies & Competitions"),
+            )
+            .child(
+                div()
+                    .text_sm()
+                    .text_color(cx.theme().muted_foreground)
+                    .child(
+                        "Goals against a rival or in a listed competition play these instead. \
+                         Empty playlists or ambience fall back to the regular ones; \
+                         competitions need the competition region (Detection tab).",
+                    ),
+            )
+            .child(rival_chips)
+            .child(derby)
+            .children(competitions)
+            .child(
+                div()
+                    .flex()
+                    .gap_2()
+                    .w_full()
+                    .child(Input::new(&self.competition_input).flex_1())
+                    .child(add_competition_button),
+            )
+    }
+
+    /// Playlist and ambience pickers for one special celebration
+    fn render_special_celebration(
+        &mut self,
+        cx: &mut Context<Self>,
+        block: usize,
+        special: SpecialCelebration,
+        title: String,
+        celebration: CelebrationOverride,
+        music_list: &[MusicEntry],
+    ) -> AnyElement {
+        let ambiance = celebration
+            .ambiance_path
+            .as_ref()
+            .map(|path| {
+                std::path::Path::new(path)
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_else(|| path.clone())
+            })
+            .unwrap_or_else(|| "regular ambience".to_string());
+        let id_base = block * 10_000;
+
+        let track_chips: Vec<_> = music_list
+            .iter()
+            .enumerate()
+            .map(|(idx, entry)| {
+                let in_playlist = celebration.goal_music_indices.contains(&idx);
+                Button::new(("special-track", id_base + idx))
+                    .ghost()
+                    .selected(in_playlist)
+                    .label(entry.name.clone())
+                    .on_click(cx.listener({
+                        let special = special.clone();
+                        move |this, _event: &ClickEvent, _window, cx| {
+                            if let Err(err) = this.controller.set_special_playlist_membership(
+                                &special,
+                                idx,
+                                !in_playlist,
+                            ) {
+                                this.status_text = format!("{err:#}").into();
+                            } else {
+                                this.refresh_status();
+                            }
+                            cx.notify();
+                        }
+                    }))
+            })
+            .collect();
+
+        let header = div()
+            .flex()
+            .items_center()
+            .justify_between()
+            .child(div().text_sm().font_semibold().child(title))
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(ambiance),
+                    )
+                    .child(
+                        Button::new(("special-ambiance", id_base))
+                            .ghost()
+                            .label("Ambience…")
+                            .on_click(cx.listener({
+                                let special = special.clone();
+                                move |this, _event: &ClickEvent, _window, cx| {
+                                    if let Some(path) = rfd::FileDialog::new()
+                                        .add_filter("Audio", &["wav"])
+                                        .pick_file()
+                                    {
+                                        if let Err(err) = this
+                                            .controller
+                                            .set_special_ambiance_path(&special, Some(path))
+                                        {
+                                            this.status_text = format!("{err:#}").into();
+                                        } else {
+                                            this.refresh_status();
+                                        }
+                                    }
+                                    cx.notify();
+                                }
+                            })),
+                    )
+                    .when(celebration.ambiance_path.is_some(), |this| {
+                        this.child(
+                            Button::new(("special-ambiance-clear", id_base))
+                                .ghost()
+                                .label("Regular Ambience")
+                                .on_click(cx.listener({
+                                    let special = special.clone();
+                                    move |this, _event: &ClickEvent, _window, cx| {
+                                        if let Err(err) = this
+                                            .controller
+                                            .set_special_ambiance_path(&special, None)
+                                        {
+                                            this.status_text = format!("{err:#}").into();
+                                        } else {
+                                            this.refresh_status();
+                                        }
+                                        cx.notify();
+                                    }
+                                })),
+                        )
+                    })
+                    .when_some(
+                        match &special {
+                            SpecialCelebration::Competition(name) => Some(name.clone()),
+                            SpecialCelebration::Derby => None,
+                        },
+                        |this, name| {
+                            this.child(
+                                Button::new(("special-remove", id_base))
+                                    .ghost()
+                                    .label("Remove")
+                                    .on_click(cx.listener(
+                                        move |this, _event: &ClickEvent, _window, cx| {
+                                            if let Err(err) =
+                                                this.controller.remove_competition_override(&name)
+                                            {
+                                                this.status_text = format!("{err:#}").into();
+                                            } else {
+                                                this.refresh_status();
+                                            }
+                                            cx.notify();
+                                        },
+                                    )),
+                            )
+                        },
+                    ),
+            );
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .p_3()
+            .rounded_lg()
+            .bg(cx.theme().tab_active)
+            .child(header)
+            .child(div().flex().flex_wrap().gap_2().children(track_chips))
+            .into_any_element()
+    }
+
+    /// Sound played when the team being edited concedes in a detected match
+    fn render_conceded_panel(
+        &mut self,
+        cx: &mut Context<Self>,
+        team: &SelectedTeam,
+        conceded_sound_path: Option<String>,
+    ) -> impl IntoElement {
+        let description = match &conceded_sound_path {
+            Some(path) => std::path::Path::new(path)
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| path.clone()),
+            None => format!(
+                "Nothing plays when {} concedes. The opponent is known once the current match is detected.",
+                team.display_name
+            ),
+        };
+
+        div()
+            .bg(cx.theme().group_box)
+            .border_1()
+            .border_color(cx.theme().border)
+            .rounded_lg()
+            .p_5()
+            .flex()
+            .flex_col()
+            .gap_3()
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .child(
+                        div()
+                            .text_lg()
+                            .font_semibold()
+                            .child(format!("When {} Concedes", team.display_name)),
+                    )
+                    .child(
+                        div()
+                            .flex()
+                            .gap_2()
+                            .child(
+                                Button::new("conceded-choose")
+                                    .primary()
+                                    .label("Choose Sound")
+                                    .on_click(cx.listener(
+                                        |this, _event: &ClickEvent, _window, cx| {
+                                            if let Some(path) = rfd::FileDialog::new()
+                                                .add_filter("Audio", &["wav", "mp3", "ogg", "flac"])
+                                                .pick_file()
+                                            {
+                                                if let Err(err) = this
+                                                    .controller
+                                                    .set_conceded_sound_path(Some(path))
+                                                {
+                                                    this.status_text = format!("{err:#}").into();
+                                                } else {
+                                                    this.refresh_status();
+                                                }
+                                            }
+                                            cx.notify();
+                                        },
+                                    )),
+                            )
+                            .child(
+                                Button::new("conceded-remove")
+                                    .ghost()
+                                    .label("Remove")
+                                    .disabled(conceded_sound_path.is_none())
+                                    .on_click(cx.listener(
+                                        |this, _event: &ClickEvent, _window, cx| {
+                                            if let Err(err) =
+                                                this.controller.set_conceded_sound_path(None)
+                                            {
+                                                this.status_text = format!("{err:#}").into();
+                                            } else {
+                                                this.refresh_status();
+                                            }
+                                            cx.notify();
+                                        },
+                                    )),
+                            ),
+                    ),
+            )
+            .child(
+                div()
+                    .text_sm()
+                    .text_color(cx.theme().muted_foreground)
+                    .child(description),
+            )
+    }
+
+    fn render_music_collection_panel(
+        &mut self,
+        cx: &mut Context<Self>,
+        music_list: &[MusicEntry],
+        selected_index: Option<usize>,
+        playlist_indices: &[usize],
+    ) -> impl IntoElement {
+        let header_row = div()
+            .flex()
+            .items_center()
+            .justify_between()
+            .child(div().text_lg().font_semibold().child("Goal Music"))
+            .child(
+                Button::new("library-add-goal")
+                    .primary()
+                    .child(div().mr_1p5().child("+"))
+                    .label("Add Music")
+                    .on_click(cx.listener(|this, _event: &ClickEvent, _window, cx| {
+                        if let Some(path) = rfd::FileDialog::new()
+                            .add_filter("Audio Files", &["mp3", "wav", "ogg", "flac", "m4a"])
+                            .pick_file()
+                        {
+                            if let Err(err) = this.controller.add_music_file(path) {
+                                this.status_text = format!("{err:#}").into();
+                            } else {
+                                this.refresh_status();
+                            }
+                        }
+                        cx.notify();
+                    })),
+            );
+
+        let playlist_summary = {
+            if playlist_indices.is_empty() {
                 "No tracks in the goal playlist yet.".to_string()
             } else if playlist_indices.len() == 1 {
                 let idx = playlist_indices[0];
@@ -2185,10 +3058,18 @@ impl MainView {
                 );
         };
 
-        let (selected_team, leagues) = {
+        let (selected_team, monitored_teams, leagues) = {
             let state = self.controller.state();
             let guard = state.lock();
-            (guard.selected_team.clone(), database.get_leagues())
+            (
+                guard.focused_team.clone(),
+                guard
+                    .team_profiles
+                    .iter()
+                    .map(|profile| profile.team.clone())
+                    .collect::<Vec<_>>(),
+                database.get_leagues(),
+            )
         };
 
         if self.active_league.is_none() {
@@ -2271,11 +3152,15 @@ impl MainView {
                                     .as_ref()
                                     .map(|st| st.league == *league && st.team_key == key)
                                     .unwrap_or(false);
+                                let is_monitored = monitored_teams
+                                    .iter()
+                                    .any(|mt| mt.league == *league && mt.team_key == key);
                                 self.render_team_card(
                                     cx,
                                     &key,
                                     &team.display_name,
                                     is_selected,
+                                    is_monitored,
                                     idx,
                                     league,
                                 )
@@ -2302,9 +3187,46 @@ impl MainView {
                 "No team selected — celebrations trigger for all goals.".to_string()
             });
 
+        // Every monitored team, each with its own playlist and ambience
+        let monitored_row = (monitored_teams.len() > 1).then(|| {
+            div()
+                .flex()
+                .flex_wrap()
+                .items_center()
+                .gap_2()
+                .child(
+                    div()
+                        .text_sm()
+                        .text_color(cx.theme().muted_foreground)
+                        .child("Monitored teams:"),
+                )
+                .children(monitored_teams.iter().enumerate().map(|(idx, team)| {
+                    let focused = selected_team
+                        .as_ref()
+                        .map(|st| st.league == team.league && st.team_key == team.team_key)
+                        .unwrap_or(false);
+                    Button::new(("monitored-team", idx))
+                        .ghost()
+                        .selected(focused)
+                        .label(team.display_name.clone())
+                        .on_click(cx.listener({
+                            let team = team.clone();
+                            move |this, _event: &ClickEvent, _window, cx| {
+                                if let Err(err) = this.controller.focus_team_profile(&team) {
+                                    this.status_text = format!("{err:#}").into();
+                                } else {
+                                    this.refresh_status();
+                                }
+                                this.active_league = Some(team.league.clone());
+                                cx.notify();
+                            }
+                        }))
+                }))
+        });
+
         let clear_button = Button::new("clear-team")
             .ghost()
-            .label("Clear Selection")
+            .label("Stop Monitoring Team")
             .disabled(selected_team.is_none())
             .on_click(cx.listener(|this, _event: &ClickEvent, _window, cx| {
                 if let Err(err) = this.controller.clear_team_selection() {
@@ -2358,9 +3280,21 @@ impl MainView {
                                     .child(clear_button),
                             ),
                     )
-                    .child(team_grid),
+                    .when_some(monitored_row, |this, row| this.child(row))
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(
+                                "Shift-click a team to monitor it alongside the selected one, \
+                                 Alt-click to mark it as a rival.",
+                            ),
+                    )
+                    .child(team_grid),
             )
             .child(self.render_add_team_section(cx, &leagues))
+            .child(self.render_edit_team_section(cx, &leagues, selected_team.as_ref()))
+            .child(self.render_team_pack_section(cx, &database, selected_team.as_ref()))
     }
 
     fn submit_team_form(
@@ -2741,73 +3675,1170 @@ impl MainView {
         container
     }
 
-    fn render_detection_tab(&mut self, cx: &mut Context<Self>) -> impl IntoElement {
-        let (region, monitor_index, preview_path, preview_generation) = {
-            let state = self.controller.state();
-            let guard = state.lock();
-            (
-                guard.capture_region,
-                guard.selected_monitor_index,
-                guard.preview_image_path.clone(),
-                guard.preview_generation,
+    /// Load a team from the database into the edit form
+    fn begin_team_edit(&mut self, team: SelectedTeam, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(entry) = self
+            .controller
+            .team_database()
+            .and_then(|db| db.find_team(&team.league, &team.team_key))
+        else {
+            self.edit_team_form.error = Some("Team not found in database".to_string());
+            return;
+        };
+
+        self.edit_team_form.team_name_input.update(cx, |state, cx| {
+            state.set_value(entry.display_name.clone(), window, cx)
+        });
+        self.edit_team_form
+            .variations_input
+            .update(cx, |state, cx| {
+                state.set_value(entry.variations.join("\n"), window, cx)
+            });
+        self.edit_team_form.match_counts = self
+            .controller
+            .variation_match_counts(&team.league, &team.team_key);
+        self.edit_team_form.stale_variations = self
+            .controller
+            .stale_variations(&team.league, &team.team_key);
+        self.edit_team_form.editing = Some(team);
+        self.edit_team_form.error = None;
+    }
+
+    fn end_team_edit(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.edit_team_form.editing = None;
+        self.edit_team_form.error = None;
+        self.edit_team_form.match_counts.clear();
+        self.edit_team_form.stale_variations.clear();
+        self.edit_team_form
+            .team_name_input
+            .update(cx, |state, cx| state.set_value("", window, cx));
+        self.edit_team_form
+            .variations_input
+            .update(cx, |state, cx| state.set_value("", window, cx));
+    }
+
+    fn submit_team_edit(&mut self, cx: &mut Context<Self>) -> Result<(), String> {
+        let team = self
+            .edit_team_form
+            .editing
+            .clone()
+            .ok_or_else(|| "Pick a team to edit".to_string())?;
+
+        let team_name = self
+            .edit_team_form
+            .team_name_input
+            .read(cx)
+            .value()
+            .trim()
+            .to_string();
+        let variations_text = self.edit_team_form.variations_input.read(cx).value();
+        let mut variations: Vec<String> = variations_text
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+            .map(|line| line.to_string())
+            .collect();
+        if !team_name.is_empty()
+            && !variations
+                .iter()
+                .any(|variation| variation.eq_ignore_ascii_case(&team_name))
+        {
+            variations.insert(0, team_name.clone());
+        }
+
+        let updated = self
+            .controller
+            .update_team(&team.league, &team.team_key, &team_name, variations)
+            .map_err(|err| format!("{err:#}"))?;
+        self.edit_team_form.editing = Some(updated);
+        self.refresh_status();
+        Ok(())
+    }
+
+    fn render_edit_team_section(
+        &mut self,
+        cx: &mut Context<Self>,
+        leagues: &[String],
+        selected_team: Option<&SelectedTeam>,
+    ) -> impl IntoElement {
+        let toggle_button = Button::new("toggle-edit-team")
+            .ghost()
+            .label(if self.edit_team_form.expanded {
+                "Hide editor"
+            } else {
+                "Show editor"
+            })
+            .on_click(cx.listener(|this, _event: &ClickEvent, _window, cx| {
+                this.edit_team_form.expanded = !this.edit_team_form.expanded;
+                cx.notify();
+            }));
+
+        let mut container = div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .border_1()
+            .border_color(cx.theme().border)
+            .rounded_md()
+            .p_3()
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .child(div().text_lg().font_semibold().child("✏️ Edit Teams"))
+                    .child(toggle_button),
+            );
+
+        if !self.edit_team_form.expanded {
+            return container;
+        }
+
+        let team_editor = match self.edit_team_form.editing.clone() {
+            None => {
+                let edit_selected = Button::new("edit-selected-team")
+                    .primary()
+                    .label("Edit Selected Team")
+                    .disabled(selected_team.is_none())
+                    .on_click(cx.listener({
+                        let selected_team = selected_team.cloned();
+                        move |this, _event: &ClickEvent, window, cx| {
+                            if let Some(team) = selected_team.clone() {
+                                this.begin_team_edit(team, window, cx);
+                            }
+                            cx.notify();
+                        }
+                    }));
+
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(edit_selected)
+                    .child(
+                        div()
+                            .text_sm()
+                            .text_color(cx.theme().muted_foreground)
+                            .child("Select a team above to rename it, edit its variations, move or remove it."),
+                    )
+                    .into_any_element()
+            }
+            Some(team) => {
+                let save_button = Button::new("save-team-edit")
+                    .primary()
+                    .label("Save Changes")
+                    .on_click(cx.listener(|this, _event: &ClickEvent, _window, cx| {
+                        match this.submit_team_edit(cx) {
+                            Ok(()) => this.edit_team_form.error = None,
+                            Err(err) => this.edit_team_form.error = Some(err),
+                        }
+                        cx.notify();
+                    }));
+
+                let cancel_button = Button::new("cancel-team-edit")
+                    .ghost()
+                    .label("Done")
+                    .on_click(cx.listener(|this, _event: &ClickEvent, window, cx| {
+                        this.end_team_edit(window, cx);
+                        cx.notify();
+                    }));
+
+                let remove_button = Button::new("remove-team")
+                    .danger()
+                    .label("Remove Team")
+                    .on_click(cx.listener({
+                        let team = team.clone();
+                        move |this, _event: &ClickEvent, window, cx| {
+                            match this.controller.remove_team(&team.league, &team.team_key) {
+                                Ok(()) => {
+                                    this.end_team_edit(window, cx);
+                                    this.refresh_status();
+                                }
+                                Err(err) => this.edit_team_form.error = Some(format!("{err:#}")),
+                            }
+                            cx.notify();
+                        }
+                    }));
+
+                let move_buttons = leagues
+                    .iter()
+                    .filter(|league| **league != team.league)
+                    .enumerate()
+                    .map(|(idx, league)| {
+                        Button::new(("move-team-league", idx))
+                            .ghost()
+                            .label(league.clone())
+                            .on_click(cx.listener({
+                                let team = team.clone();
+                                let league = league.clone();
+                                move |this, _event: &ClickEvent, _window, cx| {
+                                    match this.controller.move_team(
+                                        &team.league,
+                                        &team.team_key,
+                                        &league,
+                                    ) {
+                                        Ok(()) => {
+                                            if let Some(editing) =
+                                                this.edit_team_form.editing.as_mut()
+                                            {
+                                                editing.league = league.clone();
+                                            }
+                                            this.active_league = Some(league.clone());
+                                            this.edit_team_form.error = None;
+                                            this.refresh_status();
+                                        }
+                                        Err(err) => {
+                                            this.edit_team_form.error = Some(format!("{err:#}"))
+                                        }
+                                    }
+                                    cx.notify();
+                                }
+                            }))
+                    })
+                    .collect::<Vec<_>>();
+
+                let match_summary = self
+                    .edit_team_form
+                    .match_counts
+                    .iter()
+                    .filter(|(_, count)| *count > 0)
+                    .map(|(variation, count)| format!("{variation} ×{count}"))
+                    .collect::<Vec<_>>();
+                let stale_count = self.edit_team_form.stale_variations.len();
+                let prune_button = Button::new("prune-stale-variations")
+                    .ghost()
+                    .label(format!(
+                        "Prune {} Unused Variation{}",
+                        stale_count,
+                        if stale_count == 1 { "" } else { "s" }
+                    ))
+                    .on_click(cx.listener({
+                        let team = team.clone();
+                        move |this, _event: &ClickEvent, window, cx| {
+                            match this
+                                .controller
+                                .prune_stale_variations(&team.league, &team.team_key)
+                            {
+                                Ok(_) => this.begin_team_edit(team.clone(), window, cx),
+                                Err(err) => this.edit_team_form.error = Some(format!("{err:#}")),
+                            }
+                            cx.notify();
+                        }
+                    }));
+
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap_2()
+                    .child(
+                        div()
+                            .text_sm()
+                            .font_semibold()
+                            .child(format!("Editing {} ({})", team.display_name, team.league)),
+                    )
+                    .child(
+                        div()
+                            .flex()
+                            .flex_col()
+                            .gap_1()
+                            .child(
+                                div()
+                                    .text_sm()
+                                    .text_color(cx.theme().muted_foreground)
+                                    .child("Team display name"),
+                            )
+                            .child(Input::new(&self.edit_team_form.team_name_input)),
+                    )
+                    .child(
+                        div()
+                            .flex()
+                            .flex_col()
+                            .gap_1()
+                            .child(
+                                div()
+                                    .text_sm()
+                                    .text_color(cx.theme().muted_foreground)
+                                    .child("Name variations (one per line)"),
+                            )
+                            .child(Input::new(&self.edit_team_form.variations_input).h(px(140.0))),
+                    )
+                    .child(
+                        div()
+                            .text_sm()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(if match_summary.is_empty() {
+                                "No goal banners matched this team yet".to_string()
+                            } else {
+                                format!("Matches: {}", match_summary.join(", "))
+                            }),
+                    )
+                    .when(stale_count > 0, |this| {
+                        this.child(
+                            div()
+                                .flex()
+                                .items_center()
+                                .gap_2()
+                                .child(prune_button)
+                                .child(
+                                    div()
+                                        .text_sm()
+                                        .text_color(cx.theme().muted_foreground)
+                                        .child(self.edit_team_form.stale_variations.join(", ")),
+                                ),
+                        )
+                    })
+                    .child(
+                        div()
+                            .flex()
+                            .gap_2()
+                            .child(save_button)
+                            .child(cancel_button)
+                            .child(remove_button),
+                    )
+                    .when(!move_buttons.is_empty(), |this| {
+                        this.child(
+                            div()
+                                .text_sm()
+                                .text_color(cx.theme().muted_foreground)
+                                .child("Move to league"),
+                        )
+                        .child(div().flex().flex_wrap().gap_1().children(move_buttons))
+                    })
+                    .into_any_element()
+            }
+        };
+
+        container = container.child(team_editor);
+
+        if let Some(league) = self.active_league.clone() {
+            let rename_button = Button::new("rename-league")
+                .ghost()
+                .label("Rename League")
+                .on_click(cx.listener({
+                    let league = league.clone();
+                    move |this, _event: &ClickEvent, window, cx| {
+                        let new_name = this
+                            .edit_team_form
+                            .league_name_input
+                            .read(cx)
+                            .value()
+                            .trim()
+                            .to_string();
+                        match this.controller.rename_league(&league, &new_name) {
+                            Ok(()) => {
+                                if let Some(editing) = this
+                                    .edit_team_form
+                                    .editing
+                                    .as_mut()
+                                    .filter(|team| team.league == league)
+                                {
+                                    editing.league = new_name.clone();
+                                }
+                                this.active_league = Some(new_name);
+                                this.add_team_form.selected_league = None;
+                                this.edit_team_form.error = None;
+                                this.edit_team_form
+                                    .league_name_input
+                                    .update(cx, |state, cx| state.set_value("", window, cx));
+                                this.refresh_status();
+                            }
+                            Err(err) => this.edit_team_form.error = Some(format!("{err:#}")),
+                        }
+                        cx.notify();
+                    }
+                }));
+
+            let remove_league_button = Button::new("remove-league")
+                .danger()
+                .label("Remove League")
+                .on_click(cx.listener({
+                    let league = league.clone();
+                    move |this, _event: &ClickEvent, window, cx| {
+                        match this.controller.remove_league(&league) {
+                            Ok(()) => {
+                                let editing_removed = this
+                                    .edit_team_form
+                                    .editing
+                                    .as_ref()
+                                    .map(|team| team.league == league)
+                                    .unwrap_or(false);
+                                if editing_removed {
+                                    this.end_team_edit(window, cx);
+                                }
+                                this.active_league = None;
+                                this.add_team_form.selected_league = None;
+                                this.edit_team_form.error = None;
+                                this.refresh_status();
+                            }
+                            Err(err) => this.edit_team_form.error = Some(format!("{err:#}")),
+                        }
+                        cx.notify();
+                    }
+                }));
+
+            container = container
+                .child(div().h(px(1.0)).w_full().bg(cx.theme().border))
+                .child(
+                    div()
+                        .text_sm()
+                        .font_semibold()
+                        .child(format!("League: {league}")),
+                )
+                .child(
+                    div()
+                        .flex()
+                        .items_center()
+                        .gap_2()
+                        .child(div().flex_grow().child(
+                            Input::new(&self.edit_team_form.league_name_input).cleanable(true),
+                        ))
+                        .child(rename_button)
+                        .child(remove_league_button),
+                )
+                .child(
+                    div()
+                        .text_xs()
+                        .text_color(cx.theme().muted_foreground)
+                        .child("Removing a league also removes all of its teams and their logos."),
+                );
+        }
+
+        if let Some(error) = &self.edit_team_form.error {
+            container = container.child(
+                div()
+                    .text_sm()
+                    .text_color(cx.theme().danger)
+                    .child(error.clone()),
+            );
+        }
+
+        container
+    }
+
+    /// Ask where to save a team pack and export the given teams to it
+    fn export_team_pack_dialog(&mut self, name: &str, teams: Vec<(String, String)>) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("Team Packs", &[PACK_EXTENSION])
+            .set_file_name(format!("{}.{}", slugify(name), PACK_EXTENSION))
+            .save_file()
+        else {
+            return;
+        };
+        if let Err(err) = self.controller.export_team_pack(name, &teams, &path) {
+            self.status_text = format!("Failed to export team pack: {err:#}").into();
+        } else {
+            self.refresh_status();
+        }
+    }
+
+    fn import_pending_team_pack(&mut self, resolution: ConflictResolution) {
+        let Some(preview) = self.pending_team_pack.take() else {
+            return;
+        };
+        if let Err(err) = self.controller.import_team_pack(&preview.path, resolution) {
+            self.status_text = format!("Failed to import team pack: {err:#}").into();
+        } else {
+            self.add_team_form.selected_league = None;
+            self.refresh_status();
+        }
+    }
+
+    fn render_team_pack_section(
+        &mut self,
+        cx: &mut Context<Self>,
+        database: &TeamDatabase,
+        selected_team: Option<&SelectedTeam>,
+    ) -> impl IntoElement {
+        let import_button = Button::new("import-team-pack")
+            .ghost()
+            .label("Import Pack…")
+            .on_click(cx.listener(|this, _event: &ClickEvent, _window, cx| {
+                if let Some(path) = rfd::FileDialog::new()
+                    .add_filter("Team Packs", &[PACK_EXTENSION])
+                    .pick_file()
+                {
+                    match this.controller.preview_team_pack(&path) {
+                        Ok(preview) => this.pending_team_pack = Some(preview),
+                        Err(err) => {
+                            this.status_text = format!("Failed to read team pack: {err:#}").into();
+                        }
+                    }
+                }
+                cx.notify();
+            }));
+
+        let fm_import_button = Button::new("import-fm-export")
+            .ghost()
+            .label("Import FM Export…")
+            .on_click(cx.listener(|this, _event: &ClickEvent, _window, cx| {
+                if let Some(path) = rfd::FileDialog::new()
+                    .add_filter("FM Exports", &["txt", "html", "htm"])
+                    .pick_file()
+                {
+                    if let Err(err) = this.controller.import_fm_export(&path) {
+                        this.status_text = format!("Failed to import FM export: {err:#}").into();
+                    } else {
+                        this.refresh_status();
+                    }
+                }
+                cx.notify();
+            }));
+
+        let league_teams: Vec<(String, String)> = self
+            .active_league
+            .as_ref()
+            .and_then(|league| {
+                database.get_teams(league).map(|teams| {
+                    teams
+                        .into_iter()
+                        .map(|(key, _)| (league.clone(), key))
+                        .collect()
+                })
+            })
+            .unwrap_or_default();
+        let export_league_button = Button::new("export-league-pack")
+            .ghost()
+            .label("Export League…")
+            .disabled(league_teams.is_empty())
+            .on_click(cx.listener({
+                let league = self.active_league.clone().unwrap_or_default();
+                move |this, _event: &ClickEvent, _window, cx| {
+                    this.export_team_pack_dialog(&league, league_teams.clone());
+                    cx.notify();
+                }
+            }));
+
+        let export_team_button = Button::new("export-team-pack")
+            .ghost()
+            .label("Export Selected Team…")
+            .disabled(selected_team.is_none())
+            .on_click(cx.listener({
+                let selected_team = selected_team.cloned();
+                move |this, _event: &ClickEvent, _window, cx| {
+                    if let Some(team) = &selected_team {
+                        this.export_team_pack_dialog(
+                            &team.display_name,
+                            vec![(team.league.clone(), team.team_key.clone())],
+                        );
+                    }
+                    cx.notify();
+                }
+            }));
+
+        let all_teams: Vec<(String, String)> = database
+            .iter_teams()
+            .map(|(league, key, _)| (league.to_string(), key.to_string()))
+            .collect();
+        let export_all_button = Button::new("export-all-pack")
+            .ghost()
+            .label("Export All…")
+            .disabled(all_teams.is_empty())
+            .on_click(cx.listener(move |this, _event: &ClickEvent, _window, cx| {
+                this.export_team_pack_dialog("FM Goal Musics teams", all_teams.clone());
+                cx.notify();
+            }));
+
+        let mut container = div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .border_1()
+            .border_color(cx.theme().border)
+            .rounded_md()
+            .p_3()
+            .child(div().text_lg().font_semibold().child("📦 Team Packs"))
+            .child(
+                div()
+                    .text_sm()
+                    .text_color(cx.theme().muted_foreground)
+                    .child(format!(
+                        "Share leagues and teams, including logos, as .{PACK_EXTENSION} files, or import clubs from an FM \"Print Screen\" text/web page export."
+                    )),
+            )
+            .child(
+                div()
+                    .flex()
+                    .flex_wrap()
+                    .gap_2()
+                    .child(import_button)
+                    .child(fm_import_button)
+                    .child(export_league_button)
+                    .child(export_team_button)
+                    .child(export_all_button),
+            );
+
+        if let Some(preview) = &self.pending_team_pack {
+            let summary = if preview.conflicts == 0 {
+                format!("'{}': {} teams, all new.", preview.name, preview.team_count)
+            } else {
+                format!(
+                    "'{}': {} teams, {} already in your database.",
+                    preview.name, preview.team_count, preview.conflicts
+                )
+            };
+
+            let mut actions = div().flex().flex_wrap().gap_2();
+            if preview.conflicts == 0 {
+                actions = actions.child(
+                    Button::new("import-pack-confirm")
+                        .primary()
+                        .label("Import")
+                        .on_click(cx.listener(|this, _event: &ClickEvent, _window, cx| {
+                            this.import_pending_team_pack(ConflictResolution::Skip);
+                            cx.notify();
+                        })),
+                );
+            } else {
+                actions = actions
+                    .child(
+                        Button::new("import-pack-skip")
+                            .primary()
+                            .label("Keep Existing")
+                            .on_click(cx.listener(|this, _event: &ClickEvent, _window, cx| {
+                                this.import_pending_team_pack(ConflictResolution::Skip);
+                                cx.notify();
+                            })),
+                    )
+                    .child(
+                        Button::new("import-pack-merge")
+                            .ghost()
+                            .label("Merge Variations")
+                            .on_click(cx.listener(|this, _event: &ClickEvent, _window, cx| {
+                                this.import_pending_team_pack(ConflictResolution::MergeVariations);
+                                cx.notify();
+                            })),
+                    )
+                    .child(
+                        Button::new("import-pack-replace")
+                            .ghost()
+                            .label("Replace Existing")
+                            .on_click(cx.listener(|this, _event: &ClickEvent, _window, cx| {
+                                this.import_pending_team_pack(ConflictResolution::Replace);
+                                cx.notify();
+                            })),
+                    );
+            }
+            actions = actions.child(
+                Button::new("import-pack-cancel")
+                    .ghost()
+                    .label("Cancel")
+                    .on_click(cx.listener(|this, _event: &ClickEvent, _window, cx| {
+                        this.pending_team_pack = None;
+                        cx.notify();
+                    })),
+            );
+
+            container = container
+                .child(div().h(px(1.0)).w_full().bg(cx.theme().border))
+                .child(div().text_sm().font_semibold().child(summary))
+                .child(actions);
+        }
+
+        container
+    }
+
+    fn render_detection_tab(&mut self, cx: &mut Context<Self>) -> impl IntoElement {
+        let (
+            region,
+            monitor_index,
+            preview_path,
+            preview_generation,
+            competition_region,
+            competition,
+            metrics,
+            running,
+            ocr_debug_enabled,
+            ocr_debug_frame,
+        ) = {
+            let state = self.controller.state();
+            let guard = state.lock();
+            (
+                guard.capture_region,
+                guard.selected_monitor_index,
+                guard.preview_image_path.clone(),
+                guard.preview_generation,
+                guard.competition_region,
+                guard.current_competition.clone(),
+                guard.detection_metrics.clone(),
+                guard.process_state.is_running(),
+                guard.ocr_debug_enabled,
+                guard.ocr_debug_frame.clone(),
+            )
+        };
+        self.seen_metrics_frames = metrics.frames;
+
+        let mut content = div()
+            .flex()
+            .flex_col()
+            .gap_4()
+            .child(self.render_capture_region_card(
+                region,
+                monitor_index,
+                preview_path.clone(),
+                preview_generation,
+                cx,
+            ))
+            .child(self.render_detection_metrics_card(&metrics, running, cx))
+            .child(self.render_ocr_debug_card(ocr_debug_enabled, ocr_debug_frame, running, cx))
+            .child(self.render_competition_region_card(competition_region, competition, cx))
+            .child(self.render_detection_settings_card(cx));
+
+        if let Some(selector) = self.render_region_modal(cx) {
+            content = content.child(selector);
+        }
+
+        content
+    }
+
+    fn render_settings_tab(&mut self, cx: &mut Context<Self>) -> impl IntoElement {
+        let auto_updates = {
+            let state = self.controller.state();
+            let guard = state.lock();
+            guard.auto_check_updates
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_4()
+            .child(self.render_profiles_section(cx))
+            .child(self.render_audio_section(cx))
+            .child(self.render_detection_sensitivity_section(cx))
+            .child(self.render_language_section(cx))
+            .child(self.render_custom_phrases_section(cx))
+            .child(
+                div()
+                    .flex()
+                    .flex_wrap()
+                    .gap_4()
+                    .child(
+                        div()
+                            .flex_grow()
+                            .min_w(px(320.0))
+                            .child(self.render_update_section(auto_updates, cx)),
+                    )
+                    .child(
+                        div()
+                            .flex_grow()
+                            .min_w(px(320.0))
+                            .child(self.render_diagnostics_section(cx)),
+                    ),
+            )
+    }
+
+    /// Optional region with the competition name, for competition celebrations
+    fn render_competition_region_card(
+        &mut self,
+        region: Option<[u32; 4]>,
+        competition: Option<String>,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let summary = match region {
+            Some(region) => format!(
+                "Reading [{}, {}, {}, {}]{}",
+                region[0],
+                region[1],
+                region[2],
+                region[3],
+                competition
+                    .map(|name| format!(" — last read: {name}"))
+                    .unwrap_or_default()
+            ),
+            None => "Not set. Select where the competition name is shown to use \
+                     competition celebrations (Library tab)."
+                .to_string(),
+        };
+
+        div()
+            .bg(cx.theme().group_box)
+            .border_1()
+            .border_color(cx.theme().border)
+            .rounded_lg()
+            .p_5()
+            .flex()
+            .flex_col()
+            .gap_3()
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .child(div().text_lg().font_semibold().child("Competition Region"))
+                    .child(
+                        div()
+                            .flex()
+                            .gap_2()
+                            .child(
+                                Button::new("select-competition-region")
+                                    .ghost()
+                                    .label("Select Region")
+                                    .on_click(cx.listener(
+                                        |this, _event: &ClickEvent, _window, context| {
+                                            match this.controller.capture_fullscreen_for_selection()
+                                            {
+                                                Ok(capture) => {
+                                                    let mut selection =
+                                                        RegionSelection::from_capture(capture);
+                                                    selection.target = RegionTarget::Competition;
+                                                    this.region_selection = Some(selection);
+                                                    this.status_text = "Drag around the competition name on the screenshot.".into();
+                                                }
+                                                Err(err) => {
+                                                    this.status_text = format!("{err:#}").into();
+                                                }
+                                            }
+                                            context.notify();
+                                        },
+                                    )),
+                            )
+                            .child(
+                                Button::new("clear-competition-region")
+                                    .ghost()
+                                    .label("Clear")
+                                    .disabled(region.is_none())
+                                    .on_click(cx.listener(
+                                        |this, _event: &ClickEvent, _window, context| {
+                                            if let Err(err) =
+                                                this.controller.set_competition_region(None)
+                                            {
+                                                this.status_text = format!("{err:#}").into();
+                                            } else {
+                                                this.refresh_status();
+                                            }
+                                            context.notify();
+                                        },
+                                    )),
+                            ),
+                    ),
+            )
+            .child(
+                div()
+                    .text_sm()
+                    .text_color(cx.theme().muted_foreground)
+                    .child(summary),
+            )
+    }
+
+    /// Images, text and outcome of the frame the detection loop last published
+    fn render_ocr_debug_card(
+        &mut self,
+        enabled: bool,
+        frame: Option<OcrDebugFrame>,
+        running: bool,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let toggle = Button::new("toggle-ocr-debug")
+            .ghost()
+            .label(if enabled {
+                "Hide Live OCR"
+            } else {
+                "Show Live OCR"
+            })
+            .on_click(
+                cx.listener(move |this, _event: &ClickEvent, _window, context| {
+                    this.controller.set_ocr_debug(!enabled);
+                    if enabled {
+                        this.ocr_debug_images = None;
+                    }
+                    context.notify();
+                }),
+            );
+
+        let header = div()
+            .flex()
+            .items_center()
+            .justify_between()
+            .child(div().text_lg().font_semibold().child("Live OCR"))
+            .child(toggle);
+
+        let card = div()
+            .bg(cx.theme().group_box)
+            .border_1()
+            .border_color(cx.theme().border)
+            .rounded_lg()
+            .p_5()
+            .flex()
+            .flex_col()
+            .gap_3()
+            .child(header);
+
+        let hint = |text: &'static str| {
+            div()
+                .text_sm()
+                .text_color(cx.theme().muted_foreground)
+                .child(text)
+        };
+        if !enabled {
+            return card.child(hint(
+                "Shows the captured region, the binarised image OCR reads and what the \
+                 detector decided, about twice a second while monitoring.",
+            ));
+        }
+        let Some(frame) = frame else {
+            return card.child(hint(if running {
+                "Waiting for the next frame…"
+            } else {
+                "Start monitoring to see what OCR reads."
+            }));
+        };
+
+        let cached =
+            matches!(&self.ocr_debug_images, Some((sequence, _, _)) if *sequence == frame.sequence);
+        if !cached {
+            self.ocr_debug_images = Some((
+                frame.sequence,
+                Arc::new(GpuiImage::from_bytes(
+                    ImageFormat::Png,
+                    frame.capture_png.clone(),
+                )),
+                frame
+                    .binary_png
+                    .clone()
+                    .map(|png| Arc::new(GpuiImage::from_bytes(ImageFormat::Png, png))),
+            ));
+        }
+        let Some((_, capture, binary)) = self.ocr_debug_images.clone() else {
+            return card;
+        };
+
+        let pane = |title: &'static str, content: AnyElement| {
+            div()
+                .flex()
+                .flex_col()
+                .gap_2()
+                .flex_1()
+                .child(
+                    div()
+                        .text_sm()
+                        .font_medium()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(title),
+                )
+                .child(
+                    div()
+                        .h(px(120.0))
+                        .rounded_lg()
+                        .border_1()
+                        .border_color(cx.theme().border)
+                        .bg(cx.theme().background)
+                        .overflow_hidden()
+                        .child(content),
+                )
+        };
+        let image_element = |image: Arc<GpuiImage>| {
+            img(image)
+                .object_fit(ObjectFit::Contain)
+                .w_full()
+                .h_full()
+                .into_any_element()
+        };
+        let binary_element = match binary {
+            Some(binary) => image_element(binary),
+            None => div()
+                .p_3()
+                .text_sm()
+                .text_color(cx.theme().danger)
+                .child("OCR failed on this frame")
+                .into_any_element(),
+        };
+
+        let (decision, decision_color) = match frame.decision {
+            FrameDecision::Triggered => ("Goal music played", cx.theme().success),
+            FrameDecision::Debounced => ("Goal suppressed (debounce)", cx.theme().primary),
+            FrameDecision::TeamMismatch => {
+                ("Goal suppressed (no monitored team)", cx.theme().primary)
+            }
+            FrameDecision::NoGoal => ("No goal phrase", cx.theme().muted_foreground),
+            FrameDecision::NoText => ("No text", cx.theme().muted_foreground),
+        };
+        let row = |label: &'static str, value: String| {
+            div()
+                .flex()
+                .gap_3()
+                .text_sm()
+                .child(
+                    div()
+                        .w(px(90.0))
+                        .text_color(cx.theme().muted_foreground)
+                        .child(label),
+                )
+                .child(div().flex_1().child(value))
+        };
+        let text = if frame.text.is_empty() {
+            "(nothing read)".to_string()
+        } else {
+            frame.text.clone()
+        };
+
+        card.child(
+            div()
+                .flex()
+                .gap_3()
+                .child(pane("Captured region", image_element(capture)))
+                .child(pane("Binarised for OCR", binary_element)),
+        )
+        .child(row("Text", text))
+        .child(row(
+            "Goal rule",
+            frame.rule.clone().unwrap_or_else(|| "none".to_string()),
+        ))
+        .child(row(
+            "Team",
+            frame.team.clone().unwrap_or_else(|| "none".to_string()),
+        ))
+        .child(
+            div()
+                .flex()
+                .gap_3()
+                .text_sm()
+                .child(
+                    div()
+                        .w(px(90.0))
+                        .text_color(cx.theme().muted_foreground)
+                        .child("Decision"),
+                )
+                .child(
+                    div()
+                        .font_semibold()
+                        .text_color(decision_color)
+                        .child(decision),
+                ),
+        )
+    }
+
+    /// Live timings and outcomes of the detection loop
+    fn render_detection_metrics_card(
+        &self,
+        metrics: &DetectionMetrics,
+        running: bool,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let summary = if metrics.frames == 0 {
+            "Start monitoring to see how long each frame takes and what the detector decided."
+                .to_string()
+        } else {
+            format!(
+                "{} frames{} · {:.1} fps · {:.0}% empty reads · {} OCR failures",
+                metrics.frames,
+                if running { "" } else { " (last session)" },
+                metrics.fps(),
+                metrics.empty_rate() * 100.0,
+                metrics.skipped_frames
             )
         };
 
-        let mut content = div()
-            .flex()
-            .flex_col()
-            .gap_4()
-            .child(self.render_capture_region_card(
-                region,
-                monitor_index,
-                preview_path.clone(),
-                preview_generation,
-                cx,
-            ))
-            .child(self.render_detection_settings_card(cx));
-
-        if let Some(selector) = self.render_region_modal(cx) {
-            content = content.child(selector);
-        }
-
-        content
-    }
+        let stage_rows = Stage::ALL.into_iter().map(|stage| {
+            div()
+                .flex()
+                .items_center()
+                .gap_3()
+                .child(
+                    div()
+                        .w(px(90.0))
+                        .text_sm()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(stage.label()),
+                )
+                .child(
+                    div()
+                        .w(px(80.0))
+                        .text_sm()
+                        .font_medium()
+                        .child(format!("{:.1} ms", metrics.average_ms(stage))),
+                )
+                .child(Self::render_sparkline(&metrics.series(stage), cx))
+        });
 
-    fn render_settings_tab(&mut self, cx: &mut Context<Self>) -> impl IntoElement {
-        let auto_updates = {
-            let state = self.controller.state();
-            let guard = state.lock();
-            guard.auto_check_updates
+        let outcome = |label: &str, count: u64, color: Hsla| {
+            div()
+                .flex()
+                .flex_col()
+                .gap_1()
+                .flex_1()
+                .px(px(12.0))
+                .py(px(8.0))
+                .rounded_lg()
+                .border_1()
+                .border_color(cx.theme().border)
+                .bg(cx.theme().background)
+                .child(
+                    div()
+                        .text_xs()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(label.to_string()),
+                )
+                .child(
+                    div()
+                        .text_xl()
+                        .font_semibold()
+                        .text_color(color)
+                        .child(count.to_string()),
+                )
         };
 
         div()
+            .bg(cx.theme().group_box)
+            .border_1()
+            .border_color(cx.theme().border)
+            .rounded_lg()
+            .p_5()
             .flex()
             .flex_col()
-            .gap_4()
-            .child(self.render_audio_section(cx))
-            .child(self.render_detection_sensitivity_section(cx))
-            .child(self.render_language_section(cx))
-            .child(self.render_custom_phrases_section(cx))
+            .gap_3()
+            .child(div().text_lg().font_semibold().child("Detection Metrics"))
+            .child(
+                div()
+                    .text_sm()
+                    .text_color(cx.theme().muted_foreground)
+                    .child(summary),
+            )
+            .children(stage_rows)
             .child(
                 div()
                     .flex()
-                    .flex_wrap()
-                    .gap_4()
-                    .child(
-                        div()
-                            .flex_grow()
-                            .min_w(px(320.0))
-                            .child(self.render_update_section(auto_updates, cx)),
-                    )
-                    .child(
-                        div()
-                            .flex_grow()
-                            .min_w(px(320.0))
-                            .child(self.render_diagnostics_section(cx)),
-                    ),
+                    .gap_3()
+                    .child(outcome(
+                        "Goals played",
+                        metrics.triggered,
+                        cx.theme().success,
+                    ))
+                    .child(outcome(
+                        "Suppressed (debounce)",
+                        metrics.debounced,
+                        cx.theme().foreground,
+                    ))
+                    .child(outcome(
+                        "Suppressed (other team)",
+                        metrics.team_mismatches,
+                        cx.theme().foreground,
+                    )),
             )
     }
 
+    /// Bars for recent values, scaled to the largest one
+    fn render_sparkline(values: &[f64], cx: &Context<Self>) -> impl IntoElement {
+        const HEIGHT: f32 = 24.0;
+        let max = values.iter().copied().fold(0.0, f64::max);
+        let bars = values.iter().map(|value| {
+            let height = if max > 0.0 {
+                (value / max) as f32 * HEIGHT
+            } else {
+                0.0
+            };
+            div()
+                .w(px(2.0))
+                .h(px(height.max(1.0)))
+                .bg(cx.theme().primary)
+        });
+
+        div()
+            .flex()
+            .items_end()
+            .gap(px(1.0))
+            .h(px(HEIGHT))
+            .children(bars)
+    }
+
     fn render_capture_region_card(
         &mut self,
         region: [u32; 4],
@@ -2909,6 +4940,48 @@ impl MainView {
                 context.notify();
             }));
 
+        let auto_find_button = Button::new("auto-find-region")
+            .ghost()
+            .label("Auto-Find")
+            .flex_1()
+            .disabled(self.finding_region)
+            .on_click(cx.listener(|this, _event: &ClickEvent, _window, context| {
+                if this.finding_region {
+                    return;
+                }
+                // The screenshot and OCR passes take seconds; keep the window responsive
+                this.finding_region = true;
+                this.status_text = "Looking for a goal banner on screen...".into();
+                let controller = this.controller.clone();
+                context
+                    .spawn(async move |this, cx| {
+                        let result = cx
+                            .background_executor()
+                            .spawn(async move { controller.auto_find_capture_region() })
+                            .await;
+                        let _ = this.update(cx, |view, cx| {
+                            view.finding_region = false;
+                            match result {
+                                Ok(proposal) => {
+                                    view.status_text = format!(
+                                        "Found '{}'. Adjust the box if needed, then apply.",
+                                        proposal.matched_text
+                                    )
+                                    .into();
+                                    view.region_selection =
+                                        Some(RegionSelection::from_proposal(proposal));
+                                }
+                                Err(err) => {
+                                    view.status_text = format!("{err:#}").into();
+                                }
+                            }
+                            cx.notify();
+                        });
+                    })
+                    .detach();
+                context.notify();
+            }));
+
         let capture_preview_button = Button::new("capture-preview")
             .ghost()
             .label("Capture Preview")
@@ -2979,6 +5052,7 @@ impl MainView {
                     .flex()
                     .gap_3()
                     .child(select_region_button)
+                    .child(auto_find_button)
                     .child(capture_preview_button),
             )
             // Active Monitor section
@@ -3147,7 +5221,13 @@ impl MainView {
                 let mut applied = false;
                 if let Some(selection) = this.region_selection.as_ref() {
                     if let Some(region) = selection.logical_rect() {
-                        match this.controller.update_capture_region(region) {
+                        let result = match selection.target {
+                            RegionTarget::Goal => this.controller.update_capture_region(region),
+                            RegionTarget::Competition => {
+                                this.controller.set_competition_region(Some(region))
+                            }
+                        };
+                        match result {
                             Ok(()) => {
                                 this.refresh_status();
                                 applied = true;
@@ -3320,6 +5400,7 @@ impl MainView {
     fn render_detection_sensitivity_section(&mut self, cx: &mut Context<Self>) -> impl IntoElement {
         let ocr_value = self.ocr_threshold_value(&self.ocr_slider, cx);
         let debounce_value = self.debounce_value(&self.debounce_slider, cx);
+        let team_match_value = self.team_match_value(&self.team_match_slider, cx);
 
         let ocr_label = if ocr_value <= 0.5 {
             "Auto (Otsu)".to_string()
@@ -3386,6 +5467,17 @@ impl MainView {
                 debounce_label,
                 Slider::new(&self.debounce_slider),
             ))
+            .child(slider_row(
+                "Team Name Match",
+                format!("{:.0}%", team_match_value),
+                Slider::new(&self.team_match_slider),
+            ))
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(cx.theme().muted_foreground)
+                    .child("(Lower to tolerate OCR typos in team names)"),
+            )
     }
 
     fn render_language_section(&mut self, cx: &mut Context<Self>) -> impl IntoElement {
@@ -3418,6 +5510,125 @@ impl MainView {
             .child(Select::new(&self.language_select))
     }
 
+    /// Named configuration profiles: switcher and management
+    fn render_profiles_section(&mut self, cx: &mut Context<Self>) -> impl IntoElement {
+        let active_profile = self.controller.state().lock().active_profile.clone();
+        let profile_names = self.controller.profile_names();
+
+        let profile_rows: Vec<AnyElement> = profile_names
+            .into_iter()
+            .enumerate()
+            .map(|(idx, name)| {
+                let is_active = name == active_profile;
+                let is_default = name == profiles::DEFAULT_PROFILE;
+                let switch_name = name.clone();
+                let delete_name = name.clone();
+                div()
+                    .flex()
+                    .justify_between()
+                    .items_center()
+                    .px(px(8.0))
+                    .py(px(6.0))
+                    .rounded_md()
+                    .when(is_active, |row| row.bg(cx.theme().tab_active))
+                    .child(div().text_sm().child(if is_active {
+                        format!("{name} (in use)")
+                    } else {
+                        name.clone()
+                    }))
+                    .child(
+                        div()
+                            .flex()
+                            .gap_2()
+                            .child(
+                                Button::new(("switch-profile", idx))
+                                    .ghost()
+                                    .label("Switch")
+                                    .disabled(is_active)
+                                    .on_click(cx.listener(
+                                        move |this, _: &ClickEvent, window, cx| {
+                                            let result =
+                                                this.controller.switch_profile(&switch_name);
+                                            this.finish_profile_action(result, window, cx);
+                                        },
+                                    )),
+                            )
+                            .child(
+                                Button::new(("delete-profile", idx))
+                                    .ghost()
+                                    .label("Delete")
+                                    .disabled(is_default)
+                                    .on_click(cx.listener(
+                                        move |this, _: &ClickEvent, window, cx| {
+                                            let result =
+                                                this.controller.delete_profile(&delete_name);
+                                            this.finish_profile_action(result, window, cx);
+                                        },
+                                    )),
+                            ),
+                    )
+                    .into_any_element()
+            })
+            .collect();
+
+        let create_button = Button::new("create-profile")
+            .label("New")
+            .on_click(cx.listener(|this, _: &ClickEvent, window, cx| {
+                let name = this.profile_name_input.read(cx).value().to_string();
+                let result = this.controller.create_profile(&name);
+                this.finish_profile_action(result, window, cx);
+            }));
+        let duplicate_button = Button::new("duplicate-profile")
+            .label("Duplicate Current")
+            .on_click(cx.listener(|this, _: &ClickEvent, window, cx| {
+                let name = this.profile_name_input.read(cx).value().to_string();
+                let result = this.controller.duplicate_profile(&name);
+                this.finish_profile_action(result, window, cx);
+            }));
+        let rename_button = Button::new("rename-profile")
+            .ghost()
+            .label("Rename Current")
+            .disabled(active_profile == profiles::DEFAULT_PROFILE)
+            .on_click(cx.listener(|this, _: &ClickEvent, window, cx| {
+                let name = this.profile_name_input.read(cx).value().to_string();
+                let result = this.controller.rename_active_profile(&name);
+                this.finish_profile_action(result, window, cx);
+            }));
+
+        div()
+            .border_1()
+            .border_color(cx.theme().border)
+            .rounded_lg()
+            .p_4()
+            .flex()
+            .flex_col()
+            .gap_3()
+            .child(
+                div()
+                    .flex()
+                    .justify_between()
+                    .items_center()
+                    .child(div().text_lg().font_semibold().child("🗂️ Profiles"))
+                    .child(
+                        div()
+                            .text_sm()
+                            .text_color(cx.theme().muted_foreground)
+                            .child("A full set of settings per machine, monitor or skin."),
+                    ),
+            )
+            .child(div().flex().flex_col().gap_1().children(profile_rows))
+            .child(
+                div()
+                    .flex()
+                    .gap_2()
+                    .w_full()
+                    .child(Input::new(&self.profile_name_input).flex_1())
+                    .child(create_button)
+                    .child(duplicate_button)
+                    .child(rename_button),
+            )
+    }
+
     fn render_custom_phrases_section(&mut self, cx: &mut Context<Self>) -> impl IntoElement {
         let custom_phrases = self.controller.get_custom_goal_phrases();
 
@@ -3567,6 +5778,23 @@ impl MainView {
     fn render_diagnostics_section(&mut self, cx: &mut Context<Self>) -> impl IntoElement {
         let config_path = self.controller.config_file_path();
         let logs_path = self.controller.logs_directory();
+        let recorder_path = self.controller.flight_recorder_directory();
+        let samples_path = self.controller.sample_library_directory();
+        let mark_false_positive_label = self
+            .hotkey_config
+            .get(ActionId::MarkFalsePositive)
+            .map(|kb| kb.format())
+            .unwrap_or_else(|| "the false positive shortcut".to_string());
+        let mark_missed_goal_label = self
+            .hotkey_config
+            .get(ActionId::MarkMissedGoal)
+            .map(|kb| kb.format())
+            .unwrap_or_else(|| "the missed goal shortcut".to_string());
+        let dump_on_trigger = {
+            let state = self.controller.state();
+            let guard = state.lock();
+            guard.dump_recorder_on_trigger
+        };
 
         let config_display = config_path
             .as_ref()
@@ -3624,6 +5852,59 @@ impl MainView {
                 cx.notify();
             }));
 
+        let dump_recorder_button = Button::new("dump-flight-recorder")
+            .ghost()
+            .label("Save Flight Recorder")
+            .on_click(cx.listener(|this, _event: &ClickEvent, _window, cx| {
+                if let Err(err) = this.controller.dump_flight_recorder() {
+                    this.status_text = format!("{err:#}").into();
+                } else {
+                    this.refresh_status();
+                }
+                cx.notify();
+            }));
+
+        let open_recordings_button = Button::new("open-flight-recorder-folder")
+            .ghost()
+            .label("Open Recordings Folder")
+            .on_click(cx.listener(move |this, _event: &ClickEvent, _window, cx| {
+                if let Some(path) = recorder_path.clone() {
+                    let _ = fs::create_dir_all(&path);
+                    if let Err(err) = open::that(&path) {
+                        this.status_text = format!("Failed to open recordings: {err:#}").into();
+                    }
+                } else {
+                    this.status_text = "Recordings folder unavailable.".into();
+                }
+                cx.notify();
+            }));
+
+        let open_samples_button = Button::new("open-sample-library-folder")
+            .ghost()
+            .label("Open Samples Folder")
+            .on_click(cx.listener(move |this, _event: &ClickEvent, _window, cx| {
+                if let Some(path) = samples_path.clone() {
+                    let _ = fs::create_dir_all(&path);
+                    if let Err(err) = open::that(&path) {
+                        this.status_text = format!("Failed to open samples: {err:#}").into();
+                    }
+                } else {
+                    this.status_text = "Samples folder unavailable.".into();
+                }
+                cx.notify();
+            }));
+
+        let dump_on_trigger_switch = Switch::new("dump-recorder-on-trigger")
+            .label("Save flight recorder on every goal trigger")
+            .checked(dump_on_trigger)
+            .on_click(cx.listener(|this, checked: &bool, _event, _cx| {
+                if let Err(err) = this.controller.set_dump_recorder_on_trigger(*checked) {
+                    this.status_text = format!("{err:#}").into();
+                } else {
+                    this.refresh_status();
+                }
+            }));
+
         div()
             .border_1()
             .border_color(cx.theme().border)
@@ -3689,6 +5970,35 @@ impl MainView {
                     .child(reveal_config_button)
                     .child(open_logs_button),
             )
+            .child(
+                div()
+                    .text_sm()
+                    .text_color(cx.theme().muted_foreground)
+                    .child(
+                        "The flight recorder keeps the last seconds of captured frames, \
+                         OCR text and decisions while monitoring.",
+                    ),
+            )
+            .child(dump_on_trigger_switch)
+            .child(
+                div()
+                    .flex()
+                    .flex_wrap()
+                    .gap_2()
+                    .child(dump_recorder_button)
+                    .child(open_recordings_button),
+            )
+            .child(
+                div()
+                    .text_sm()
+                    .text_color(cx.theme().muted_foreground)
+                    .child(format!(
+                        "Press {} after a wrong goal or {} after a missed one to save \
+                         a labelled sample for calibration.",
+                        mark_false_positive_label, mark_missed_goal_label
+                    )),
+            )
+            .child(div().flex().child(open_samples_button))
     }
 
     fn slider_value(&self, slider: &Entity<SliderState>, cx: &mut Context<Self>) -> f32 {
@@ -3722,12 +6032,23 @@ impl MainView {
         }
     }
 
+    fn team_match_value(&self, slider: &Entity<SliderState>, cx: &mut Context<Self>) -> f32 {
+        let value = slider.read(cx).value().start();
+        if value.is_nan() {
+            let state = self.controller.state();
+            let guard = state.lock();
+            guard.team_match_threshold * 100.0
+        } else {
+            value
+        }
+    }
+
     fn music_volume_value(&self, slider: &Entity<SliderState>, cx: &mut Context<Self>) -> f32 {
         let value = slider.read(cx).value().start();
         if value.is_nan() {
             let state = self.controller.state();
             let guard = state.lock();
-            guard.music_volume * 100.0
+            guard.celebration().music_volume * 100.0
         } else {
             value
         }
@@ -3738,7 +6059,7 @@ impl MainView {
         if value.is_nan() {
             let state = self.controller.state();
             let guard = state.lock();
-            guard.ambiance_volume * 100.0
+            guard.celebration().ambiance_volume * 100.0
         } else {
             value
         }
@@ -3807,9 +6128,9 @@ impl MainView {
             let state = self.controller.state();
             let guard = state.lock();
             guard
-                .goal_ambiance_path
-                .as_ref()
-                .map(|p| PathBuf::from(p))
+                .celebration()
+                .ambiance_path
+                .map(PathBuf::from)
                 .ok_or_else(|| "Add a goal cheer sound first to preview.".to_string())?
         };
 
@@ -4421,8 +6742,13 @@ impl MainView {
 }
 
 impl Render for MainView {
-    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         self.status_text = self.controller.status_message().into();
+        let settings_generation = self.controller.state().lock().settings_generation;
+        if settings_generation != self.seen_settings_generation {
+            self.seen_settings_generation = settings_generation;
+            self.sync_controls_with_state(window, cx);
+        }
 
         let content = match self.active_tab {
             AppTab::Dashboard => self.render_dashboard_tab(cx).into_any_element(),
@@ -4460,6 +6786,11 @@ impl Render for MainView {
             .on_action(cx.listener(Self::open_settings))
             .on_action(cx.listener(Self::check_for_updates))
             .on_action(cx.listener(Self::quit_app))
+            // Diagnostics shortcuts
+            .on_action(cx.listener(Self::dump_flight_recorder))
+            .on_action(cx.listener(Self::mark_false_positive))
+            .on_action(cx.listener(Self::mark_missed_goal))
+            .on_action(cx.listener(Self::learn_variation))
             .flex()
             .size_full()
             .bg(cx.theme().background)
@@ -7,36 +7,58 @@ use sysinfo::System;
 use tracing_appender::rolling;
 use tracing_subscriber::{fmt, prelude::*, EnvFilter};
 
+use fm_goal_musics::{
+    app_paths, audio_converter, capture, config, config_migrations, detection, ocr, persistence,
+    profiles, slug, team_matcher, teams, utils,
+};
+
 mod audio;
-mod audio_converter;
 mod audio_system;
-mod capture;
-mod config;
-mod detection;
 mod error;
+mod file_watch;
+mod flight_recorder;
+mod fm_import;
 mod gui;
 mod messaging;
-mod ocr;
-mod slug;
+mod sample_library;
+mod settings_writer;
 mod state;
-mod team_matcher;
-mod teams;
+mod team_pack;
 mod update_checker;
-mod utils;
+mod variation_stats;
 mod wizard;
 
 const LOG_TARGET_STARTUP: &str = "fm_goal_musics::startup";
 
 fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    // Must come first: every file path depends on it
+    if args.iter().any(|arg| arg == "--portable") {
+        app_paths::enable_portable_mode();
+    }
+
     initialize_tracing();
     log_runtime_environment();
-    gui::run()
+    gui::run(profile_argument(args.into_iter()))
+}
+
+/// Value of `--profile <name>` or `--profile=<name>` on the command line
+fn profile_argument(mut args: impl Iterator<Item = String>) -> Option<String> {
+    while let Some(arg) = args.next() {
+        if arg == "--profile" {
+            return args.next();
+        }
+        if let Some(name) = arg.strip_prefix("--profile=") {
+            return Some(name.to_string());
+        }
+    }
+    None
 }
 
 fn initialize_tracing() {
-    let log_dir = dirs::config_dir()
-        .map(|dir| dir.join("FMGoalMusic").join("logs"))
-        .unwrap_or_else(|| std::path::PathBuf::from("logs"));
+    let log_dir = app_paths::config_dir()
+        .map(|dir| dir.join("logs"))
+        .unwrap_or_else(|_| std::path::PathBuf::from("logs"));
 
     if let Err(e) = std::fs::create_dir_all(&log_dir) {
         eprintln!("Warning: Failed to create log directory: {}", e);
@@ -77,6 +99,9 @@ fn initialize_tracing() {
             .init();
     }
 
+    if app_paths::is_portable() {
+        tracing::info!("Portable mode: keeping all data beside the executable");
+    }
     tracing::info!("Log directory: {}", log_dir.display());
 }
 
@@ -0,0 +1,19 @@
+// Modules shared by the GUI app and the command-line diagnostics
+//
+// Both binaries link this library instead of compiling the modules into
+// each of them, so neither has to silence the dead code warnings for the
+// parts only the other one uses.
+
+pub mod app_paths;
+pub mod audio_converter;
+pub mod capture;
+pub mod config;
+pub mod config_migrations;
+pub mod detection;
+pub mod ocr;
+pub mod persistence;
+pub mod profiles;
+pub mod slug;
+pub mod team_matcher;
+pub mod teams;
+pub mod utils;
@@ -93,7 +93,7 @@ impl Clone for EventBus {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::time::Instant;
+    use std::time::SystemTime;
 
     #[test]
     fn test_event_bus_subscribe() {
@@ -119,7 +119,8 @@ mod tests {
 
         let event = Event::GoalDetected {
             team: None,
-            timestamp: Instant::now(),
+            match_score: None,
+            timestamp: SystemTime::now(),
         };
 
         bus.publish(event.clone());
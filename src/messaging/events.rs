@@ -1,28 +1,52 @@
 /// Event types for the application
 ///
 /// Events represent things that have happened (past tense).
-/// They are broadcast to all subscribers.
+/// They are broadcast to all subscribers, and serialize to JSON for
+/// consumers outside the app (see `EventMessage`).
 use std::path::PathBuf;
-use std::time::Instant;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Serialize, Serializer};
 
 use crate::config::SelectedTeam;
 use crate::state::ProcessState;
 
+/// Version of the JSON event messages; bump it when a field is renamed or
+/// removed, not when one is added
+pub const EVENT_SCHEMA_VERSION: u32 = 1;
+
 /// Application events
-#[derive(Debug, Clone)]
+///
+/// Serialized with a snake_case `type` tag, for example
+/// `{"type": "goal_detected", "team": null, ...}`. Timestamps are
+/// milliseconds since the Unix epoch.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
 pub enum Event {
     /// A goal was detected
+    ///
+    /// `team` is the selected team, or the club identified from the banner
+    /// text when no team is selected. `match_score` is the identification
+    /// score (1.0 for the selected team).
     GoalDetected {
         team: Option<SelectedTeam>,
-        timestamp: Instant,
+        match_score: Option<f32>,
+        #[serde(serialize_with = "unix_millis")]
+        timestamp: SystemTime,
     },
 
-    /// Match has started (kickoff detected) - for v0.2
-    MatchStarted { timestamp: Instant },
+    /// A new match was recognised at kickoff
+    MatchStarted {
+        home: Option<SelectedTeam>,
+        away: Option<SelectedTeam>,
+        #[serde(serialize_with = "unix_millis")]
+        timestamp: SystemTime,
+    },
 
-    /// Match has ended - for v0.3
+    /// The full-time screen was read
     MatchEnded {
-        timestamp: Instant,
+        #[serde(serialize_with = "unix_millis")]
+        timestamp: SystemTime,
         home_score: u32,
         away_score: u32,
     },
@@ -45,8 +69,11 @@ pub enum Event {
     /// Capture region was changed
     RegionChanged { region: [u32; 4] },
 
-    /// Audio playback started
-    AudioPlaybackStarted { source: AudioSource },
+    /// Audio playback started; `name` is the track, when known
+    AudioPlaybackStarted {
+        source: AudioSource,
+        name: Option<String>,
+    },
 
     /// Audio playback finished
     AudioPlaybackFinished { source: AudioSource },
@@ -59,7 +86,8 @@ pub enum Event {
 }
 
 /// Configuration field that changed
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ConfigField {
     MusicVolume,
     AmbianceVolume,
@@ -74,7 +102,8 @@ pub enum ConfigField {
 }
 
 /// Audio source types
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum AudioSource {
     GoalMusic,
     Ambiance,
@@ -82,6 +111,36 @@ pub enum AudioSource {
     Commentator, // Future
 }
 
+/// An event as sent to outside consumers, with the schema version
+///
+/// `{"version": 1, "event": {"type": "match_started", ...}}`
+#[derive(Debug, Serialize)]
+pub struct EventMessage<'a> {
+    pub version: u32,
+    pub event: &'a Event,
+}
+
+impl<'a> EventMessage<'a> {
+    pub fn new(event: &'a Event) -> Self {
+        Self {
+            version: EVENT_SCHEMA_VERSION,
+            event,
+        }
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(self)
+    }
+}
+
+fn unix_millis<S: Serializer>(time: &SystemTime, serializer: S) -> Result<S::Ok, S::Error> {
+    let millis = time
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or(0);
+    serializer.serialize_u64(millis)
+}
+
 impl Event {
     /// Get a human-readable description of the event
     pub fn description(&self) -> String {
@@ -116,7 +175,7 @@ impl Event {
             Event::RegionChanged { region } => {
                 format!("Region changed: {:?}", region)
             }
-            Event::AudioPlaybackStarted { source } => {
+            Event::AudioPlaybackStarted { source, .. } => {
                 format!("Audio started: {:?}", source)
             }
             Event::AudioPlaybackFinished { source } => {
@@ -133,18 +192,71 @@ impl Event {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::time::Duration;
 
     #[test]
     fn test_event_description() {
         let event = Event::GoalDetected {
             team: None,
-            timestamp: Instant::now(),
+            match_score: None,
+            timestamp: SystemTime::now(),
         };
         assert_eq!(event.description(), "Goal detected");
 
+        let event = Event::GoalDetected {
+            team: Some(SelectedTeam {
+                league: "Premier League".to_string(),
+                team_key: "arsenal".to_string(),
+                display_name: "Arsenal".to_string(),
+            }),
+            match_score: Some(0.9),
+            timestamp: SystemTime::now(),
+        };
+        assert_eq!(event.description(), "Goal detected for Arsenal");
+
         let event = Event::MatchStarted {
-            timestamp: Instant::now(),
+            home: None,
+            away: None,
+            timestamp: SystemTime::now(),
         };
         assert_eq!(event.description(), "Match started");
     }
+
+    #[test]
+    fn test_event_message_json() {
+        let event = Event::GoalDetected {
+            team: Some(SelectedTeam {
+                league: "Premier League".to_string(),
+                team_key: "arsenal".to_string(),
+                display_name: "Arsenal".to_string(),
+            }),
+            match_score: Some(1.0),
+            timestamp: UNIX_EPOCH + Duration::from_millis(1_700_000_000_123),
+        };
+        let json: serde_json::Value =
+            serde_json::from_str(&EventMessage::new(&event).to_json().unwrap()).unwrap();
+
+        assert_eq!(json["version"], EVENT_SCHEMA_VERSION);
+        assert_eq!(json["event"]["type"], "goal_detected");
+        assert_eq!(json["event"]["team"]["display_name"], "Arsenal");
+        assert_eq!(json["event"]["timestamp"], 1_700_000_000_123u64);
+
+        let event = Event::ProcessStateChanged {
+            old_state: ProcessState::Stopped,
+            new_state: ProcessState::Running {
+                since: std::time::Instant::now(),
+            },
+        };
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "process_state_changed");
+        assert_eq!(json["new_state"], "running");
+
+        let event = Event::AudioPlaybackFinished {
+            source: AudioSource::GoalMusic,
+        };
+        assert_eq!(
+            serde_json::to_value(&event).unwrap()["source"],
+            "goal_music"
+        );
+    }
 }
@@ -53,5 +53,5 @@ pub mod executor;
 // Re-export commonly used types
 pub use bus::{EventBus, SubscriberId};
 pub use commands::{AudioSourceType, Command, CommandResult, ConfigUpdate, CrowdCheerVariant};
-pub use events::{AudioSource, ConfigField, Event};
+pub use events::{AudioSource, ConfigField, Event, EventMessage, EVENT_SCHEMA_VERSION};
 pub use executor::CommandExecutor;
@@ -6,6 +6,8 @@ use image::GrayImage;
 use leptess::{LepTess, Variable};
 use std::path::PathBuf;
 
+use super::layout::{self, OcrWord};
+
 /// Tesseract OCR detector
 ///
 /// Manages Tesseract instance and performs OCR on binary (preprocessed) images
@@ -176,6 +178,27 @@ impl TesseractDetector {
         Ok(String::new())
     }
 
+    /// Perform OCR on a binary image and return every word with its bounding box
+    ///
+    /// Coordinates are in pixels of `binary_image`.
+    pub fn detect_words(
+        &mut self,
+        binary_image: &GrayImage,
+    ) -> Result<Vec<OcrWord>, Box<dyn std::error::Error>> {
+        let temp_path = self.get_temp_path("ocr_layout.png");
+        binary_image.save(&temp_path)?;
+
+        self.tess.set_image(&temp_path)?;
+        let tsv = self.tess.get_tsv_text(0)?;
+        let words = layout::parse_tsv(&tsv);
+
+        tracing::debug!("[fm-goal-musics][ocr-layout] {} words", words.len());
+
+        let _ = std::fs::remove_file(&temp_path);
+
+        Ok(words)
+    }
+
     /// Get temporary file path
     fn get_temp_path(&self, filename: &str) -> PathBuf {
         std::env::temp_dir().join(filename)
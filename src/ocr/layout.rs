@@ -0,0 +1,256 @@
+//! Word layout analysis for locating text on screen
+//!
+//! This module parses Tesseract's TSV output into words with bounding boxes
+//! and finds where known phrases (e.g. goal phrases) appear in an image.
+//! It is used to propose a capture region automatically from a full-screen
+//! screenshot.
+
+/// Tesseract TSV level for a single word
+const TSV_WORD_LEVEL: u32 = 5;
+
+/// A single recognised word with its bounding box in image pixels
+#[derive(Debug, Clone, PartialEq)]
+pub struct OcrWord {
+    pub text: String,
+    pub left: u32,
+    pub top: u32,
+    pub width: u32,
+    pub height: u32,
+    pub confidence: f32,
+    /// (block, paragraph, line) identifying the text line this word belongs to
+    pub line: (u32, u32, u32),
+}
+
+/// A phrase located in the image
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhraseMatch {
+    /// The phrase that matched
+    pub phrase: String,
+    /// Full text of the line containing the phrase
+    pub line_text: String,
+    /// Bounding box of the whole line [x, y, width, height]
+    pub bounds: [u32; 4],
+}
+
+/// Parse Tesseract TSV output into word boxes
+///
+/// Non-word rows, empty words and malformed lines are skipped.
+///
+/// # Arguments
+/// * `tsv` - Output of `TessBaseAPIGetTsvText`
+///
+/// # Returns
+/// Words in reading order
+pub fn parse_tsv(tsv: &str) -> Vec<OcrWord> {
+    tsv.lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split('\t').collect();
+            if fields.len() < 12 {
+                return None;
+            }
+
+            let level: u32 = fields[0].parse().ok()?;
+            if level != TSV_WORD_LEVEL {
+                return None;
+            }
+
+            let text = fields[11..].join("\t").trim().to_string();
+            if text.is_empty() {
+                return None;
+            }
+
+            Some(OcrWord {
+                text,
+                left: fields[6].parse().ok()?,
+                top: fields[7].parse().ok()?,
+                width: fields[8].parse().ok()?,
+                height: fields[9].parse().ok()?,
+                confidence: fields[10].parse().unwrap_or(-1.0),
+                line: (
+                    fields[2].parse().ok()?,
+                    fields[3].parse().ok()?,
+                    fields[4].parse().ok()?,
+                ),
+            })
+        })
+        .collect()
+}
+
+/// Find the text line containing one of the given phrases
+///
+/// Phrases are compared word by word, ignoring case and punctuation, so
+/// "GOAL!" matches the word "Goal" but not "GOALKEEPER". When several lines
+/// match, the one matching the longest phrase wins, then the widest line.
+///
+/// # Arguments
+/// * `words` - Recognised words (see `parse_tsv`)
+/// * `phrases` - Phrases to look for
+///
+/// # Returns
+/// `Some(PhraseMatch)` for the best matching line, `None` if no phrase was found
+pub fn find_phrase_line(words: &[OcrWord], phrases: &[String]) -> Option<PhraseMatch> {
+    let mut lines: Vec<Vec<&OcrWord>> = Vec::new();
+    for word in words {
+        match lines.last_mut() {
+            Some(line) if line[0].line == word.line => line.push(word),
+            _ => lines.push(vec![word]),
+        }
+    }
+
+    let phrase_tokens: Vec<(&String, Vec<String>)> = phrases
+        .iter()
+        .map(|phrase| (phrase, tokenize(phrase)))
+        .filter(|(_, tokens)| !tokens.is_empty())
+        .collect();
+
+    let mut best: Option<(usize, PhraseMatch)> = None;
+
+    for line in &lines {
+        let line_tokens: Vec<String> = line.iter().map(|w| normalize_token(&w.text)).collect();
+
+        for (phrase, tokens) in &phrase_tokens {
+            let found = line_tokens
+                .windows(tokens.len())
+                .any(|window| window == tokens.as_slice());
+            if !found {
+                continue;
+            }
+
+            let candidate = PhraseMatch {
+                phrase: (*phrase).clone(),
+                line_text: line
+                    .iter()
+                    .map(|w| w.text.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                bounds: union_bounds(line),
+            };
+
+            let better = match &best {
+                None => true,
+                Some((best_len, best_match)) => {
+                    tokens.len() > *best_len
+                        || (tokens.len() == *best_len && candidate.bounds[2] > best_match.bounds[2])
+                }
+            };
+            if better {
+                best = Some((tokens.len(), candidate));
+            }
+        }
+    }
+
+    best.map(|(_, m)| m)
+}
+
+/// Grow a rectangle by the given padding and clamp it to the image size
+///
+/// # Arguments
+/// * `rect` - Rectangle [x, y, width, height]
+/// * `pad_x` - Padding added on the left and right
+/// * `pad_y` - Padding added above and below
+/// * `max_width` / `max_height` - Image dimensions
+pub fn pad_rect(
+    rect: [u32; 4],
+    pad_x: u32,
+    pad_y: u32,
+    max_width: u32,
+    max_height: u32,
+) -> [u32; 4] {
+    let [x, y, w, h] = rect;
+    let left = x.saturating_sub(pad_x);
+    let top = y.saturating_sub(pad_y);
+    let right = x.saturating_add(w).saturating_add(pad_x).min(max_width);
+    let bottom = y.saturating_add(h).saturating_add(pad_y).min(max_height);
+
+    [
+        left,
+        top,
+        right.saturating_sub(left).max(1),
+        bottom.saturating_sub(top).max(1),
+    ]
+}
+
+fn tokenize(phrase: &str) -> Vec<String> {
+    phrase
+        .split_whitespace()
+        .map(normalize_token)
+        .filter(|t| !t.is_empty())
+        .collect()
+}
+
+fn normalize_token(word: &str) -> String {
+    word.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(|c| c.to_uppercase())
+        .collect()
+}
+
+fn union_bounds(words: &[&OcrWord]) -> [u32; 4] {
+    let left = words.iter().map(|w| w.left).min().unwrap_or(0);
+    let top = words.iter().map(|w| w.top).min().unwrap_or(0);
+    let right = words.iter().map(|w| w.left + w.width).max().unwrap_or(left);
+    let bottom = words.iter().map(|w| w.top + w.height).max().unwrap_or(top);
+    [left, top, right - left, bottom - top]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_TSV: &str = "\
+1\t1\t0\t0\t0\t0\t0\t0\t1920\t1080\t-1\t
+4\t1\t1\t1\t1\t0\t800\t900\t320\t40\t-1\t
+5\t1\t1\t1\t1\t1\t800\t900\t110\t40\t95.1\tGOAL!
+5\t1\t1\t1\t1\t2\t920\t900\t200\t40\t91.3\tArsenal
+5\t1\t2\t1\t1\t1\t40\t20\t90\t18\t88.0\tGoalkeeper
+5\t1\t2\t1\t1\t2\t140\t20\t60\t18\t80.0\tstats";
+
+    #[test]
+    fn test_parse_tsv_words_only() {
+        let words = parse_tsv(SAMPLE_TSV);
+        assert_eq!(words.len(), 4);
+        assert_eq!(words[0].text, "GOAL!");
+        assert_eq!(words[0].left, 800);
+        assert_eq!(words[0].line, (1, 1, 1));
+        assert_eq!(words[2].line, (2, 1, 1));
+    }
+
+    #[test]
+    fn test_parse_tsv_malformed() {
+        assert!(parse_tsv("").is_empty());
+        assert!(parse_tsv("5\t1\t1").is_empty());
+        assert!(parse_tsv("5\t1\t1\t1\t1\t1\tx\t0\t1\t1\t90\tGOAL").is_empty());
+    }
+
+    #[test]
+    fn test_find_phrase_line() {
+        let words = parse_tsv(SAMPLE_TSV);
+        let found = find_phrase_line(&words, &["GOAL".to_string()]).unwrap();
+        assert_eq!(found.line_text, "GOAL! Arsenal");
+        assert_eq!(found.bounds, [800, 900, 320, 40]);
+    }
+
+    #[test]
+    fn test_find_phrase_line_whole_words() {
+        let words = parse_tsv(SAMPLE_TSV);
+        assert!(find_phrase_line(&words, &["KEEPER".to_string()]).is_none());
+        assert!(find_phrase_line(&words, &["TOR".to_string()]).is_none());
+    }
+
+    #[test]
+    fn test_find_phrase_line_prefers_longer_phrase() {
+        let words = parse_tsv(SAMPLE_TSV);
+        let phrases = vec!["stats".to_string(), "Goal! Arsenal".to_string()];
+        let found = find_phrase_line(&words, &phrases).unwrap();
+        assert_eq!(found.phrase, "Goal! Arsenal");
+    }
+
+    #[test]
+    fn test_pad_rect_clamps() {
+        assert_eq!(
+            pad_rect([100, 100, 50, 20], 10, 5, 1920, 1080),
+            [90, 95, 70, 30]
+        );
+        assert_eq!(pad_rect([5, 5, 50, 20], 10, 10, 60, 30), [0, 0, 60, 30]);
+    }
+}
@@ -11,6 +11,7 @@ mod detection;
 /// - `preprocessing`: Image transformations and thresholding
 /// - `detection`: Tesseract OCR integration
 /// - `text_extraction`: Parsing team names from OCR results
+/// - `layout`: Word bounding boxes and phrase location
 ///
 /// # Public API
 ///
@@ -18,12 +19,25 @@ mod detection;
 /// - `new_with_options()`: Initialize with custom settings
 /// - `detect_goal()`: Simple goal detection
 /// - `detect_goal_with_team()`: Goal detection with team name extraction
+/// - `locate_phrase()`: Find where a phrase appears on a full screenshot
+pub mod layout;
 mod preprocessing;
 pub mod text_extraction;
 
 use detection::TesseractDetector;
-use image::{ImageBuffer, Rgba};
+use image::{GrayImage, ImageBuffer, Rgba};
+use layout::PhraseMatch;
 use preprocessing::ImagePreprocessor;
+use std::time::Instant;
+
+/// Preprocessed images read by `OcrManager::get_text_per_variant()`, in order
+pub const TEXT_VARIANTS: [&str; 5] = [
+    "standard",
+    "red channel",
+    "green channel",
+    "blue channel",
+    "edges",
+];
 
 /// OCR manager for goal detection
 ///
@@ -36,7 +50,7 @@ use preprocessing::ImagePreprocessor;
 /// use image::RgbaImage;
 ///
 /// let mut ocr = OcrManager::new_with_options(0, false)?;
-/// let image: RgbaImage = /* captured screen */;
+/// let image = RgbaImage::new(1920, 1080); // A captured screen
 ///
 /// // Simple detection
 /// if ocr.detect_goal(&image)? {
@@ -200,26 +214,121 @@ impl OcrManager {
         ))
     }
 
+    /// Locate the text line containing one of the given phrases
+    ///
+    /// Runs OCR with word bounding boxes over the whole image, trying the
+    /// standard preprocessing first and the alternative methods afterwards.
+    ///
+    /// # Arguments
+    /// * `image` - RGBA screen capture (typically a full screenshot)
+    /// * `phrases` - Phrases to look for
+    ///
+    /// # Returns
+    /// `Ok(Some(PhraseMatch))` with the line bounds in image pixels,
+    /// `Ok(None)` if no phrase was found, or error on OCR failure
+    pub fn locate_phrase(
+        &mut self,
+        image: &ImageBuffer<Rgba<u8>, Vec<u8>>,
+        phrases: &[String],
+    ) -> Result<Option<PhraseMatch>, Box<dyn std::error::Error>> {
+        let binary = self.preprocessor.preprocess(image);
+        let words = self.detector.detect_words(&binary)?;
+        if let Some(found) = layout::find_phrase_line(&words, phrases) {
+            return Ok(Some(found));
+        }
+
+        for alt in self.preprocessor.try_alternative_methods(image) {
+            let words = self.detector.detect_words(&alt)?;
+            if let Some(found) = layout::find_phrase_line(&words, phrases) {
+                return Ok(Some(found));
+            }
+        }
+
+        Ok(None)
+    }
+
     /// Get detected text (for debugging)
     ///
     /// Returns the raw OCR text without any filtering.
-    #[allow(dead_code)]
     pub fn get_text(
         &mut self,
         image: &ImageBuffer<Rgba<u8>, Vec<u8>>,
     ) -> Result<String, Box<dyn std::error::Error>> {
+        self.get_text_traced(image).map(|trace| trace.text)
+    }
+
+    /// Get detected text together with the binarised image and stage timings
+    ///
+    /// Same recognition as `get_text()`, but keeps the intermediate results
+    /// so the detection loop can record them for diagnostics.
+    pub fn get_text_traced(
+        &mut self,
+        image: &ImageBuffer<Rgba<u8>, Vec<u8>>,
+    ) -> Result<OcrTrace, Box<dyn std::error::Error>> {
+        let preprocess_start = Instant::now();
         let binary = self.preprocessor.preprocess(image);
+        let mut preprocess_us = preprocess_start.elapsed().as_secs_f64() * 1_000_000.0;
+
+        let ocr_start = Instant::now();
         let text = self.detector.detect_text(&binary)?;
+        let mut ocr_us = ocr_start.elapsed().as_secs_f64() * 1_000_000.0;
 
         if !text.is_empty() {
-            return Ok(text);
+            return Ok(OcrTrace {
+                text,
+                binary,
+                preprocess_us,
+                ocr_us,
+            });
         }
 
+        let alt_start = Instant::now();
         let alt_images = self.preprocessor.try_alternative_methods(image);
+        preprocess_us += alt_start.elapsed().as_secs_f64() * 1_000_000.0;
+
+        let alt_ocr_start = Instant::now();
         let alt_text = self.detector.detect_text_multi(alt_images)?;
+        ocr_us += alt_ocr_start.elapsed().as_secs_f64() * 1_000_000.0;
 
-        Ok(alt_text)
+        Ok(OcrTrace {
+            text: alt_text,
+            binary,
+            preprocess_us,
+            ocr_us,
+        })
     }
+
+    /// Read every preprocessing variant separately (for diagnostics)
+    ///
+    /// Detection stops at the first variant that yields text; this shows
+    /// what each of them would have read.
+    ///
+    /// # Returns
+    /// One `(variant, text)` pair per entry of `TEXT_VARIANTS`
+    pub fn get_text_per_variant(
+        &mut self,
+        image: &ImageBuffer<Rgba<u8>, Vec<u8>>,
+    ) -> Result<Vec<(&'static str, String)>, Box<dyn std::error::Error>> {
+        let binaries = std::iter::once(self.preprocessor.preprocess(image))
+            .chain(self.preprocessor.try_alternative_methods(image));
+        let mut texts = Vec::new();
+        for (variant, binary) in TEXT_VARIANTS.into_iter().zip(binaries) {
+            texts.push((variant, self.detector.detect_text(&binary)?));
+        }
+        Ok(texts)
+    }
+}
+
+/// Result of `OcrManager::get_text_traced()`
+pub struct OcrTrace {
+    /// Recognised text (uppercase, trimmed)
+    pub text: String,
+    /// Primary binarised image fed to Tesseract
+    pub binary: GrayImage,
+    /// Time spent preprocessing, in microseconds
+    pub preprocess_us: f64,
+    /// Time spent in Tesseract, in microseconds
+    pub ocr_us: f64,
 }
 
 #[cfg(test)]
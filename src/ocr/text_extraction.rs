@@ -2,6 +2,16 @@
 ///
 /// This module handles parsing OCR results to extract goal-related information,
 /// specifically team names from "GOAL FOR {team}" or "GOL {team}" patterns.
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+/// Score between the sides of a fixture, e.g. " 1-0 " or " 2 : 1 "
+static SCORE_SEPARATOR: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\s\d{1,2}\s?[-:–]\s?\d{1,2}\s").unwrap());
+/// "vs", "v" or a dash between the sides of a fixture
+static VERSUS_SEPARATOR: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)\s(?:vs?\.?|[-–])\s").unwrap());
 
 /// Check if text contains goal-related keywords
 ///
@@ -86,6 +96,28 @@ pub fn extract_team_name(text: &str) -> Option<String> {
     None
 }
 
+/// Split a kickoff or scoreboard line into its home and away sides
+///
+/// Recognises a score ("Galatasaray 1-0 Fenerbahçe", "GAL 2:1 FEN") or a
+/// versus marker ("Galatasaray vs Fenerbahçe", "Arsenal v Chelsea",
+/// "Arsenal - Chelsea"). A score wins over a plain dash so "1 - 0" is not
+/// split in the middle.
+///
+/// # Returns
+/// `Some((home, away))` with both sides non-empty, `None` otherwise
+pub fn split_fixture(text: &str) -> Option<(String, String)> {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let separator = SCORE_SEPARATOR
+        .find(&text)
+        .or_else(|| VERSUS_SEPARATOR.find(&text))?;
+    let home = text[..separator.start()].trim();
+    let away = text[separator.end()..].trim();
+    if home.is_empty() || away.is_empty() {
+        return None;
+    }
+    Some((home.to_string(), away.to_string()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -150,4 +182,26 @@ mod tests {
             Some("ARSENAL MORE TEXT".to_string())
         );
     }
+
+    #[test]
+    fn test_split_fixture() {
+        assert_eq!(
+            split_fixture("Galatasaray vs Fenerbahçe"),
+            Some(("Galatasaray".to_string(), "Fenerbahçe".to_string()))
+        );
+        assert_eq!(
+            split_fixture("Arsenal  1 - 0  Chelsea"),
+            Some(("Arsenal".to_string(), "Chelsea".to_string()))
+        );
+        assert_eq!(
+            split_fixture("Man Utd v Liverpool"),
+            Some(("Man Utd".to_string(), "Liverpool".to_string()))
+        );
+        assert_eq!(
+            split_fixture("GAL 2:1 FEN"),
+            Some(("GAL".to_string(), "FEN".to_string()))
+        );
+        assert_eq!(split_fixture("GOAL FOR Arsenal"), None);
+        assert_eq!(split_fixture("vs Chelsea"), None);
+    }
 }
@@ -0,0 +1,317 @@
+/// Crash-safe reading and writing of the JSON files in the config directory
+///
+/// Files are written to a temporary sibling, flushed, then renamed over the
+/// original, so a crash mid-write leaves the previous file intact. Before a
+/// file is replaced its current contents are kept as `<name>.bak1`, shifting
+/// older copies up to `<name>.bak<BACKUP_COUNT>`. When a file no longer
+/// parses, loading falls back to the newest backup that does and keeps the
+/// broken file as `<name>.corrupt` so hand edits are not lost.
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::{self, File};
+use std::hash::{Hash, Hasher};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use parking_lot::Mutex;
+
+/// Number of previous versions kept beside each file
+pub const BACKUP_COUNT: usize = 3;
+
+/// Hash of the contents last written to each file by the app itself
+static OWN_WRITES: Mutex<BTreeMap<PathBuf, u64>> = Mutex::new(BTreeMap::new());
+
+fn content_hash(contents: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    contents.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Whether `contents` is exactly what the app last wrote to `path`, so a
+/// change seen on disk is the app's own save rather than a user edit
+pub fn is_own_write(path: &Path, contents: &[u8]) -> bool {
+    OWN_WRITES.lock().get(path) == Some(&content_hash(contents))
+}
+
+/// Sibling of `path` with `suffix` appended to the file name
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path
+        .file_name()
+        .map(|n| n.to_os_string())
+        .unwrap_or_default();
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+/// Backups of `path`, newest first
+pub fn backup_paths(path: &Path) -> Vec<PathBuf> {
+    (1..=BACKUP_COUNT)
+        .map(|n| sibling(path, &format!(".bak{n}")))
+        .collect()
+}
+
+/// Where a file that failed to parse is kept
+pub fn corrupt_path(path: &Path) -> PathBuf {
+    sibling(path, ".corrupt")
+}
+
+/// Replace `path` with `contents` without ever leaving a half-written file
+pub fn write_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let temp = sibling(path, ".tmp");
+    {
+        let mut file = File::create(&temp)?;
+        file.write_all(contents)?;
+        file.sync_all()?;
+    }
+    // Recorded before the rename so a watcher never sees the new file
+    // without knowing it is ours
+    let previous = OWN_WRITES
+        .lock()
+        .insert(path.to_path_buf(), content_hash(contents));
+    if let Err(err) = fs::rename(&temp, path) {
+        let _ = fs::remove_file(&temp);
+        let mut own_writes = OWN_WRITES.lock();
+        match previous {
+            Some(hash) => own_writes.insert(path.to_path_buf(), hash),
+            None => own_writes.remove(path),
+        };
+        return Err(err);
+    }
+    Ok(())
+}
+
+/// Like `write_atomic`, first rotating the current file into the backups
+pub fn write_with_backups(path: &Path, contents: &[u8]) -> io::Result<()> {
+    if path.exists() {
+        let backups = backup_paths(path);
+        for pair in backups.windows(2).rev() {
+            if pair[0].exists() {
+                fs::rename(&pair[0], &pair[1])?;
+            }
+        }
+        fs::copy(path, &backups[0])?;
+    }
+    write_atomic(path, contents)
+}
+
+/// A file that could not be parsed when it was loaded
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoadProblem {
+    pub path: PathBuf,
+    /// Parser message, including the line and column for JSON errors
+    pub error: String,
+    /// Backup the settings were restored from, if any parsed
+    pub recovered_from: Option<PathBuf>,
+    /// Copy of the unreadable file
+    pub preserved_at: Option<PathBuf>,
+}
+
+impl fmt::Display for LoadProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = |path: &Path| {
+            path.file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_else(|| path.display().to_string())
+        };
+        write!(f, "{} could not be read ({})", name(&self.path), self.error)?;
+        match &self.recovered_from {
+            Some(backup) => write!(f, "; restored from {}", name(backup))?,
+            None => write!(f, "; using defaults")?,
+        }
+        if let Some(copy) = &self.preserved_at {
+            write!(f, ". Your file was kept as {}", name(copy))?;
+        }
+        Ok(())
+    }
+}
+
+/// Outcome of `load_with_recovery`
+pub struct Recovered<T> {
+    /// Parsed contents, or `None` if neither the file nor a backup parsed
+    pub value: Option<T>,
+    pub problem: Option<LoadProblem>,
+}
+
+/// Read and parse `path`, falling back to its backups if it does not parse
+///
+/// # Returns
+/// `Ok(None)` when `path` doesn't exist; I/O errors reading `path` itself
+/// are returned as is
+pub fn load_with_recovery<T, E: fmt::Display>(
+    path: &Path,
+    parse: impl Fn(&str) -> Result<T, E>,
+) -> io::Result<Option<Recovered<T>>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(path)?;
+    let error = match parse(&content) {
+        Ok(value) => {
+            return Ok(Some(Recovered {
+                value: Some(value),
+                problem: None,
+            }))
+        }
+        Err(err) => err.to_string(),
+    };
+    tracing::warn!("Failed to parse {}: {}", path.display(), error);
+
+    let preserved_at = corrupt_path(path);
+    let preserved_at = match fs::write(&preserved_at, &content) {
+        Ok(()) => Some(preserved_at),
+        Err(err) => {
+            tracing::warn!("Could not keep a copy of {}: {}", path.display(), err);
+            None
+        }
+    };
+
+    for backup in backup_paths(path) {
+        let Ok(backup_content) = fs::read_to_string(&backup) else {
+            continue;
+        };
+        match parse(&backup_content) {
+            Ok(value) => {
+                tracing::info!("✓ Restored {} from {}", path.display(), backup.display());
+                return Ok(Some(Recovered {
+                    value: Some(value),
+                    problem: Some(LoadProblem {
+                        path: path.to_path_buf(),
+                        error,
+                        recovered_from: Some(backup),
+                        preserved_at,
+                    }),
+                }));
+            }
+            Err(err) => tracing::warn!("Skipping backup {}: {}", backup.display(), err),
+        }
+    }
+
+    Ok(Some(Recovered {
+        value: None,
+        problem: Some(LoadProblem {
+            path: path.to_path_buf(),
+            error,
+            recovered_from: None,
+            preserved_at,
+        }),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_file(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "fm_goal_musics_persistence_{}_{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir.join("settings.json")
+    }
+
+    fn parse_number(content: &str) -> Result<u32, serde_json::Error> {
+        serde_json::from_str(content)
+    }
+
+    #[test]
+    fn test_write_atomic_replaces_without_leftovers() {
+        let path = temp_file("atomic");
+        write_atomic(&path, b"1").unwrap();
+        write_atomic(&path, b"2").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "2");
+        assert!(!sibling(&path, ".tmp").exists());
+        assert!(!backup_paths(&path)[0].exists());
+    }
+
+    #[test]
+    fn test_own_writes_are_recognised() {
+        let path = temp_file("own");
+        write_atomic(&path, b"{\"a\": 1}").unwrap();
+
+        assert!(is_own_write(&path, b"{\"a\": 1}"));
+        assert!(!is_own_write(&path, b"{\"a\": 2}"));
+        assert!(!is_own_write(
+            &path.with_file_name("other.json"),
+            b"{\"a\": 1}"
+        ));
+    }
+
+    #[test]
+    fn test_failed_write_is_not_remembered_as_own() {
+        let path = temp_file("failed");
+        write_atomic(&path, b"1").unwrap();
+        // A non-empty directory can't be renamed over
+        let blocked = path.with_file_name("blocked.json");
+        fs::create_dir_all(blocked.join("inner")).unwrap();
+
+        assert!(write_atomic(&blocked, b"2").is_err());
+        assert!(!is_own_write(&blocked, b"2"));
+        assert!(is_own_write(&path, b"1"));
+    }
+
+    #[test]
+    fn test_backups_rotate_and_are_capped() {
+        let path = temp_file("rotate");
+        for n in 1..=BACKUP_COUNT + 2 {
+            write_with_backups(&path, n.to_string().as_bytes()).unwrap();
+        }
+
+        let current = BACKUP_COUNT + 2;
+        assert_eq!(fs::read_to_string(&path).unwrap(), current.to_string());
+        for (age, backup) in backup_paths(&path).iter().enumerate() {
+            let expected = current - age - 1;
+            assert_eq!(fs::read_to_string(backup).unwrap(), expected.to_string());
+        }
+        assert!(!sibling(&path, &format!(".bak{}", BACKUP_COUNT + 1)).exists());
+    }
+
+    #[test]
+    fn test_missing_file_loads_nothing() {
+        let path = temp_file("missing");
+        assert!(load_with_recovery(&path, parse_number).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_corrupt_file_recovers_from_newest_good_backup() {
+        let path = temp_file("recover");
+        write_with_backups(&path, b"7").unwrap();
+        write_with_backups(&path, b"8").unwrap();
+        write_with_backups(&path, b"oops").unwrap();
+        // A crash-truncated file on top of a broken backup
+        write_with_backups(&path, b"{\"truncat").unwrap();
+
+        let recovered = load_with_recovery(&path, parse_number).unwrap().unwrap();
+        assert_eq!(recovered.value, Some(8));
+
+        let problem = recovered.problem.unwrap();
+        assert_eq!(problem.recovered_from, Some(backup_paths(&path)[1].clone()));
+        assert!(problem.error.contains("line 1"));
+        assert_eq!(
+            fs::read_to_string(corrupt_path(&path)).unwrap(),
+            "{\"truncat"
+        );
+        assert!(problem.to_string().contains("settings.json.bak2"));
+    }
+
+    #[test]
+    fn test_corrupt_file_without_backups_reports_defaults() {
+        let path = temp_file("unrecoverable");
+        write_atomic(&path, b"not json").unwrap();
+
+        let recovered = load_with_recovery(&path, parse_number).unwrap().unwrap();
+        assert!(recovered.value.is_none());
+        let problem = recovered.problem.unwrap();
+        assert!(problem.recovered_from.is_none());
+        assert!(problem.to_string().contains("using defaults"));
+    }
+}
@@ -0,0 +1,290 @@
+/// Named configuration profiles
+///
+/// A profile is a complete config for one setup, for example a streaming PC
+/// with a 1440p monitor and a laptop running a different skin. The default
+/// profile is config.json itself; every other profile is stored as
+/// `profiles/<name>.json` beside it. The profile in use is remembered in
+/// `profiles/active` and opened again on the next start.
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, Result};
+
+use crate::config::Config;
+use crate::persistence;
+
+/// Name of the profile stored in config.json
+pub const DEFAULT_PROFILE: &str = "Default";
+
+const PROFILES_DIR: &str = "profiles";
+const ACTIVE_FILE: &str = "active";
+const MAX_NAME_LEN: usize = 64;
+
+/// Folder holding config.json
+fn config_root() -> Result<PathBuf> {
+    let config_path = Config::config_path().map_err(|err| anyhow!("{err}"))?;
+    config_path
+        .parent()
+        .map(Path::to_path_buf)
+        .ok_or_else(|| anyhow!("Could not determine config directory"))
+}
+
+fn is_default(name: &str) -> bool {
+    name.eq_ignore_ascii_case(DEFAULT_PROFILE)
+}
+
+/// Config file of the profile `name`
+pub fn profile_path(name: &str) -> Result<PathBuf> {
+    Ok(profile_path_in(&config_root()?, name))
+}
+
+fn profile_path_in(root: &Path, name: &str) -> PathBuf {
+    if is_default(name) {
+        root.join("config.json")
+    } else {
+        root.join(PROFILES_DIR).join(format!("{name}.json"))
+    }
+}
+
+/// Every profile name, the default profile first
+pub fn list() -> Result<Vec<String>> {
+    Ok(list_in(&config_root()?))
+}
+
+fn list_in(root: &Path) -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(root.join(PROFILES_DIR))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("json"))
+        .filter_map(|path| path.file_stem()?.to_str().map(str::to_string))
+        .filter(|name| !is_default(name))
+        .collect();
+    names.sort_by_key(|name| name.to_lowercase());
+    names.insert(0, DEFAULT_PROFILE.to_string());
+    names
+}
+
+/// Check a new profile name against the rules and the existing profiles
+///
+/// # Returns
+/// The trimmed name
+pub fn validate_name(name: &str, existing: &[String]) -> Result<String> {
+    let name = name.trim();
+    if name.is_empty() {
+        bail!("Enter a profile name");
+    }
+    if name.chars().count() > MAX_NAME_LEN {
+        bail!("Profile names can be at most {MAX_NAME_LEN} characters");
+    }
+    if name.starts_with('.')
+        || name
+            .chars()
+            .any(|c| c.is_control() || r#"/\:*?"<>|"#.contains(c))
+    {
+        bail!("Profile names can't start with '.' or contain / \\ : * ? \" < > |");
+    }
+    if existing
+        .iter()
+        .any(|other| other.eq_ignore_ascii_case(name))
+    {
+        bail!("A profile named '{name}' already exists");
+    }
+    Ok(name.to_string())
+}
+
+/// Save `config` as a new profile
+///
+/// # Returns
+/// The profile's name, trimmed
+pub fn create(name: &str, config: &Config) -> Result<String> {
+    let name = validate_name(name, &list()?)?;
+    config
+        .save_to(&profile_path(&name)?)
+        .map_err(|err| anyhow!("Failed to save profile '{name}': {err}"))?;
+    Ok(name)
+}
+
+/// Rename a profile, backups included
+///
+/// # Returns
+/// The new name, trimmed
+pub fn rename(from: &str, to: &str) -> Result<String> {
+    let root = config_root()?;
+    let existing: Vec<String> = list_in(&root)
+        .into_iter()
+        .filter(|name| name != from)
+        .collect();
+    let to = validate_name(to, &existing)?;
+    let was_active = active_in(&root) == from;
+    rename_in(&root, from, &to)?;
+    if was_active {
+        set_active_in(&root, &to)?;
+    }
+    Ok(to)
+}
+
+fn rename_in(root: &Path, from: &str, to: &str) -> Result<()> {
+    if is_default(from) || is_default(to) {
+        bail!("The {DEFAULT_PROFILE} profile can't be renamed");
+    }
+    let from_path = profile_path_in(root, from);
+    let to_path = profile_path_in(root, to);
+    if !from_path.exists() {
+        bail!("Profile '{from}' doesn't exist");
+    }
+    fs::rename(&from_path, &to_path)?;
+    for (old, new) in persistence::backup_paths(&from_path)
+        .into_iter()
+        .zip(persistence::backup_paths(&to_path))
+    {
+        if old.exists() {
+            fs::rename(old, new)?;
+        }
+    }
+    Ok(())
+}
+
+/// Delete a profile and its backups
+pub fn delete(name: &str) -> Result<()> {
+    let root = config_root()?;
+    let was_active = active_in(&root) == name;
+    delete_in(&root, name)?;
+    if was_active {
+        set_active_in(&root, DEFAULT_PROFILE)?;
+    }
+    Ok(())
+}
+
+fn delete_in(root: &Path, name: &str) -> Result<()> {
+    if is_default(name) {
+        bail!("The {DEFAULT_PROFILE} profile can't be deleted");
+    }
+    let path = profile_path_in(root, name);
+    if !path.exists() {
+        bail!("Profile '{name}' doesn't exist");
+    }
+    fs::remove_file(&path)?;
+    for leftover in persistence::backup_paths(&path)
+        .into_iter()
+        .chain([persistence::corrupt_path(&path)])
+    {
+        let _ = fs::remove_file(leftover);
+    }
+    Ok(())
+}
+
+/// Profile used last, or the default one if it no longer exists
+pub fn active() -> String {
+    config_root()
+        .map(|root| active_in(&root))
+        .unwrap_or_else(|_| DEFAULT_PROFILE.to_string())
+}
+
+fn active_in(root: &Path) -> String {
+    fs::read_to_string(root.join(PROFILES_DIR).join(ACTIVE_FILE))
+        .ok()
+        .map(|name| name.trim().to_string())
+        .filter(|name| profile_path_in(root, name).exists())
+        .unwrap_or_else(|| DEFAULT_PROFILE.to_string())
+}
+
+/// Remember `name` as the profile to open on the next start
+pub fn set_active(name: &str) -> Result<()> {
+    set_active_in(&config_root()?, name)
+}
+
+fn set_active_in(root: &Path, name: &str) -> Result<()> {
+    persistence::write_atomic(&root.join(PROFILES_DIR).join(ACTIVE_FILE), name.as_bytes())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_root(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!(
+            "fm_goal_musics_profiles_{}_{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        root
+    }
+
+    fn touch(root: &Path, name: &str) {
+        persistence::write_atomic(&profile_path_in(root, name), b"{}").unwrap();
+    }
+
+    #[test]
+    fn test_default_profile_is_config_json() {
+        let root = Path::new("/cfg");
+        assert_eq!(profile_path_in(root, "Default"), root.join("config.json"));
+        assert_eq!(profile_path_in(root, "default"), root.join("config.json"));
+        assert_eq!(
+            profile_path_in(root, "Laptop"),
+            root.join("profiles").join("Laptop.json")
+        );
+    }
+
+    #[test]
+    fn test_list_puts_default_first() {
+        let root = temp_root("list");
+        touch(&root, "streaming PC");
+        touch(&root, "Laptop");
+        fs::write(root.join(PROFILES_DIR).join("notes.txt"), "x").unwrap();
+
+        assert_eq!(list_in(&root), vec!["Default", "Laptop", "streaming PC"]);
+    }
+
+    #[test]
+    fn test_validate_name() {
+        let existing = vec!["Default".to_string(), "Laptop".to_string()];
+        assert_eq!(
+            validate_name("  FM26 skin ", &existing).unwrap(),
+            "FM26 skin"
+        );
+        assert!(validate_name("", &existing).is_err());
+        assert!(validate_name("laptop", &existing).is_err());
+        assert!(validate_name("DEFAULT", &existing).is_err());
+        assert!(validate_name("../evil", &existing).is_err());
+        assert!(validate_name(".hidden", &existing).is_err());
+        assert!(validate_name(&"x".repeat(MAX_NAME_LEN + 1), &existing).is_err());
+    }
+
+    #[test]
+    fn test_rename_moves_backups() {
+        let root = temp_root("rename");
+        let path = profile_path_in(&root, "Laptop");
+        persistence::write_with_backups(&path, b"1").unwrap();
+        persistence::write_with_backups(&path, b"2").unwrap();
+
+        rename_in(&root, "Laptop", "Travel").unwrap();
+
+        let renamed = profile_path_in(&root, "Travel");
+        assert!(!path.exists());
+        assert_eq!(fs::read_to_string(&renamed).unwrap(), "2");
+        assert_eq!(
+            fs::read_to_string(&persistence::backup_paths(&renamed)[0]).unwrap(),
+            "1"
+        );
+        assert!(rename_in(&root, DEFAULT_PROFILE, "Other").is_err());
+    }
+
+    #[test]
+    fn test_delete_and_active_fallback() {
+        let root = temp_root("delete");
+        touch(&root, "Laptop");
+        set_active_in(&root, "Laptop").unwrap();
+        assert_eq!(active_in(&root), "Laptop");
+
+        delete_in(&root, "Laptop").unwrap();
+        assert!(!profile_path_in(&root, "Laptop").exists());
+        // The remembered profile is gone, so the default one opens
+        assert_eq!(active_in(&root), DEFAULT_PROFILE);
+        assert!(delete_in(&root, DEFAULT_PROFILE).is_err());
+    }
+}
@@ -0,0 +1,199 @@
+/// Labelled detection samples collected from user feedback
+///
+/// When the user flags a false positive or a missed goal, the relevant
+/// flight recorder frames are saved under `samples/<label>/<timestamp>/`
+/// together with a `sample.json` describing them, ready to be collected for
+/// calibration or regression tests.
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::app_paths;
+use crate::flight_recorder::{self, RecordedFrame};
+
+const SAMPLE_FILE: &str = "sample.json";
+
+/// What the user said about a detection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SampleLabel {
+    /// Goal music played but there was no goal for the selected team
+    FalsePositive,
+    /// A goal happened but no music played
+    MissedGoal,
+}
+
+impl SampleLabel {
+    /// Folder name used for this label inside the library
+    pub fn dir_name(&self) -> &'static str {
+        match self {
+            SampleLabel::FalsePositive => "false_positive",
+            SampleLabel::MissedGoal => "missed_goal",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            SampleLabel::FalsePositive => "false positive",
+            SampleLabel::MissedGoal => "missed goal",
+        }
+    }
+}
+
+/// Detection settings in effect when a sample was taken
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SampleContext {
+    pub language: String,
+    pub team: Option<String>,
+    pub custom_goal_phrases: Vec<String>,
+}
+
+/// Metadata stored next to the frames of a sample
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SampleInfo {
+    pub label: SampleLabel,
+    pub created_at_ms: u128,
+    pub frame_count: usize,
+    /// Distinct non-empty OCR texts, in the order they were read
+    pub texts: Vec<String>,
+    #[serde(flatten)]
+    pub context: SampleContext,
+}
+
+/// Root directory of the sample library
+pub fn samples_dir() -> Result<PathBuf> {
+    Ok(app_paths::config_dir()?.join("samples"))
+}
+
+/// Save frames as a new labelled sample in the library
+///
+/// # Returns
+/// The folder the sample was written to
+pub fn save_sample(
+    label: SampleLabel,
+    frames: &[RecordedFrame],
+    context: SampleContext,
+) -> Result<PathBuf> {
+    save_sample_in(&samples_dir()?, label, frames, context)
+}
+
+fn save_sample_in(
+    root: &Path,
+    label: SampleLabel,
+    frames: &[RecordedFrame],
+    context: SampleContext,
+) -> Result<PathBuf> {
+    if frames.is_empty() {
+        return Err(anyhow!("No recorded frames to save"));
+    }
+
+    let dir = flight_recorder::unique_timestamped_dir(&root.join(label.dir_name()));
+    flight_recorder::write_frames(&dir, frames, label.description())?;
+
+    let info = SampleInfo {
+        label,
+        created_at_ms: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis())
+            .unwrap_or(0),
+        frame_count: frames.len(),
+        texts: distinct_texts(frames.iter().map(|f| f.text.as_str())),
+        context,
+    };
+    let json = serde_json::to_string_pretty(&info)?;
+    fs::write(dir.join(SAMPLE_FILE), json)
+        .with_context(|| format!("Failed to write {SAMPLE_FILE}"))?;
+
+    tracing::info!(
+        "[samples] Saved {} sample with {} frames to {}",
+        label.description(),
+        frames.len(),
+        dir.display()
+    );
+
+    Ok(dir)
+}
+
+fn distinct_texts<'a>(texts: impl Iterator<Item = &'a str>) -> Vec<String> {
+    let mut result: Vec<String> = Vec::new();
+    for text in texts.map(str::trim).filter(|t| !t.is_empty()) {
+        if !result.iter().any(|t| t == text) {
+            result.push(text.to_string());
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::flight_recorder::FrameDecision;
+    use crate::utils::IterationTiming;
+    use image::RgbaImage;
+
+    fn frame(text: &str) -> RecordedFrame {
+        RecordedFrame::new(
+            RgbaImage::new(4, 4),
+            None,
+            text.to_string(),
+            IterationTiming::new(),
+            FrameDecision::NoGoal,
+        )
+    }
+
+    fn temp_root(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!(
+            "fm_goal_musics_samples_{}_{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&root);
+        root
+    }
+
+    #[test]
+    fn test_distinct_texts() {
+        let texts = distinct_texts(["", "GOAL Arsenal", "GOAL Arsenal ", "Corner"].into_iter());
+        assert_eq!(texts, vec!["GOAL Arsenal", "Corner"]);
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let root = temp_root("roundtrip");
+        let frames = vec![frame("GOAL Arsenal"), frame("GOAL Arsenal")];
+        let context = SampleContext {
+            language: "English".to_string(),
+            team: Some("Arsenal".to_string()),
+            custom_goal_phrases: Vec::new(),
+        };
+
+        let dir = save_sample_in(&root, SampleLabel::FalsePositive, &frames, context).unwrap();
+        assert!(dir.join("000_capture.png").exists());
+        assert!(dir.join("frames.json").exists());
+
+        assert!(dir.starts_with(root.join("false_positive")));
+        let json = fs::read_to_string(dir.join(SAMPLE_FILE)).unwrap();
+        let info: SampleInfo = serde_json::from_str(&json).unwrap();
+        assert_eq!(info.label, SampleLabel::FalsePositive);
+        assert_eq!(info.frame_count, 2);
+        assert_eq!(info.texts, vec!["GOAL Arsenal"]);
+        assert_eq!(info.context.team.as_deref(), Some("Arsenal"));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_save_empty_sample_fails() {
+        let root = temp_root("empty");
+        assert!(save_sample_in(
+            &root,
+            SampleLabel::MissedGoal,
+            &[],
+            SampleContext::default()
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,222 @@
+/// Background writer that batches settings saves
+///
+/// Dragging a slider calls the controller setters many times a second, and
+/// each call used to rewrite the config file on the UI thread. The latest
+/// settings are now handed to a worker thread instead, which writes them once
+/// no change has arrived for `SAVE_DELAY`, and at least every
+/// `MAX_SAVE_DELAY` while changes keep coming. `flush` writes anything
+/// pending right away, before the app quits or a profile file is moved.
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crossbeam_channel::{bounded, unbounded, Receiver, RecvTimeoutError, SendError, Sender};
+
+use crate::config::Config;
+
+/// Quiet time after the last change before settings are written
+const SAVE_DELAY: Duration = Duration::from_millis(500);
+/// Longest a change waits while changes keep arriving
+const MAX_SAVE_DELAY: Duration = Duration::from_secs(2);
+
+enum Message {
+    Save(PathBuf, Box<Config>),
+    /// Write pending settings now, then signal the sender
+    Flush(Sender<()>),
+}
+
+/// Latest unsaved value and when it has to be written
+struct Batch<T> {
+    delay: Duration,
+    max_delay: Duration,
+    pending: Option<(PathBuf, T)>,
+    first_change: Instant,
+    last_change: Instant,
+}
+
+impl<T> Batch<T> {
+    fn new(delay: Duration, max_delay: Duration) -> Self {
+        let now = Instant::now();
+        Self {
+            delay,
+            max_delay,
+            pending: None,
+            first_change: now,
+            last_change: now,
+        }
+    }
+
+    /// Replace the pending value
+    ///
+    /// # Returns
+    /// The value pending for another file, which has to be written now
+    fn push(&mut self, path: PathBuf, value: T, now: Instant) -> Option<(PathBuf, T)> {
+        let (same_file, previous) = match self.pending.take() {
+            Some((pending_path, _)) if pending_path == path => (true, None),
+            other => (false, other),
+        };
+        if !same_file {
+            self.first_change = now;
+        }
+        self.last_change = now;
+        self.pending = Some((path, value));
+        previous
+    }
+
+    fn deadline(&self) -> Option<Instant> {
+        self.pending
+            .as_ref()
+            .map(|_| (self.last_change + self.delay).min(self.first_change + self.max_delay))
+    }
+
+    /// Pending value if it is due at `now`
+    fn take_due(&mut self, now: Instant) -> Option<(PathBuf, T)> {
+        match self.deadline() {
+            Some(deadline) if deadline <= now => self.pending.take(),
+            _ => None,
+        }
+    }
+
+    fn take(&mut self) -> Option<(PathBuf, T)> {
+        self.pending.take()
+    }
+}
+
+/// Handle to the worker thread; dropping every handle writes what is pending
+/// and stops the thread
+pub struct SettingsWriter {
+    tx: Sender<Message>,
+}
+
+impl SettingsWriter {
+    /// Start the worker; `on_error` receives a message for every failed write
+    pub fn spawn(on_error: impl Fn(String) + Send + 'static) -> Self {
+        let (tx, rx) = unbounded();
+        thread::spawn(move || run(rx, on_error));
+        Self { tx }
+    }
+
+    /// Queue `config` to be written to `path`
+    pub fn save(&self, path: PathBuf, config: Config) {
+        if let Err(SendError(Message::Save(path, config))) =
+            self.tx.send(Message::Save(path, Box::new(config)))
+        {
+            // The worker is gone, so write directly rather than lose settings
+            if let Err(err) = config.save_to(&path) {
+                tracing::error!("Failed to save {}: {}", path.display(), err);
+            }
+        }
+    }
+
+    /// Write pending settings and wait until they are on disk
+    pub fn flush(&self) {
+        let (done_tx, done_rx) = bounded(1);
+        if self.tx.send(Message::Flush(done_tx)).is_ok() {
+            let _ = done_rx.recv();
+        }
+    }
+}
+
+fn run(rx: Receiver<Message>, on_error: impl Fn(String)) {
+    let mut batch = Batch::new(SAVE_DELAY, MAX_SAVE_DELAY);
+    let write = |(path, config): (PathBuf, Box<Config>)| {
+        if let Err(err) = config.save_to(&path) {
+            tracing::error!("Failed to save {}: {}", path.display(), err);
+            on_error(format!(
+                "Settings could not be saved to {}: {err}",
+                path.display()
+            ));
+        }
+    };
+
+    loop {
+        let received = match batch.deadline() {
+            Some(deadline) => rx.recv_timeout(deadline.saturating_duration_since(Instant::now())),
+            None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+        match received {
+            Ok(Message::Save(path, config)) => {
+                if let Some(previous) = batch.push(path, config, Instant::now()) {
+                    write(previous);
+                }
+            }
+            Ok(Message::Flush(done)) => {
+                if let Some(pending) = batch.take() {
+                    write(pending);
+                }
+                let _ = done.send(());
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                if let Some(due) = batch.take_due(Instant::now()) {
+                    write(due);
+                }
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                if let Some(pending) = batch.take() {
+                    write(pending);
+                }
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DELAY: Duration = Duration::from_millis(500);
+    const MAX_DELAY: Duration = Duration::from_secs(2);
+
+    fn ms(start: Instant, millis: u64) -> Instant {
+        start + Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn test_changes_are_coalesced_until_quiet() {
+        let mut batch = Batch::new(DELAY, MAX_DELAY);
+        let start = Instant::now();
+        let path = PathBuf::from("config.json");
+
+        assert!(batch.push(path.clone(), 1, start).is_none());
+        assert!(batch.push(path.clone(), 2, ms(start, 100)).is_none());
+        assert_eq!(batch.deadline(), Some(ms(start, 600)));
+        assert!(batch.take_due(ms(start, 599)).is_none());
+
+        assert_eq!(batch.take_due(ms(start, 600)), Some((path, 2)));
+        assert_eq!(batch.deadline(), None);
+    }
+
+    #[test]
+    fn test_steady_changes_are_written_by_max_delay() {
+        let mut batch = Batch::new(DELAY, MAX_DELAY);
+        let start = Instant::now();
+        let path = PathBuf::from("config.json");
+
+        for step in 0..=30 {
+            batch.push(path.clone(), step, ms(start, step * 100));
+        }
+        assert_eq!(batch.deadline(), Some(ms(start, 2000)));
+        assert_eq!(batch.take_due(ms(start, 3000)), Some((path.clone(), 30)));
+
+        // The next change starts a new batch
+        batch.push(path, 31, ms(start, 3100));
+        assert_eq!(batch.deadline(), Some(ms(start, 3600)));
+    }
+
+    #[test]
+    fn test_switching_files_returns_the_previous_value() {
+        let mut batch = Batch::new(DELAY, MAX_DELAY);
+        let start = Instant::now();
+        let default = PathBuf::from("config.json");
+        let laptop = PathBuf::from("profiles/Laptop.json");
+
+        batch.push(default.clone(), 1, start);
+        assert_eq!(
+            batch.push(laptop.clone(), 2, ms(start, 100)),
+            Some((default, 1))
+        );
+        assert_eq!(batch.deadline(), Some(ms(start, 600)));
+        assert_eq!(batch.take(), Some((laptop, 2)));
+    }
+}
@@ -1,16 +1,22 @@
 /// Application state with validation
 ///
 /// Contains all runtime state for the application with validation methods.
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
+use std::time::SystemTime;
 
+use super::detection_metrics::DetectionMetrics;
+use super::ocr_debug::OcrDebugFrame;
 use super::process_state::ProcessState;
 
-// Use SelectedTeam from config module
-pub use crate::config::SelectedTeam;
+// Use SelectedTeam and TeamProfile from config module
+pub use crate::config::{SelectedTeam, TeamProfile};
 
 // Import Language for i18n support
 pub use crate::detection::i18n::Language;
 
+use crate::profiles::DEFAULT_PROFILE;
+
 /// Music entry with file path and optional keyboard shortcut
 #[derive(Clone, Debug)]
 pub struct MusicEntry {
@@ -25,6 +31,7 @@ pub struct AppState {
     // Music library
     pub music_list: Vec<MusicEntry>,
     pub selected_music_index: Option<usize>,
+    /// Default goal playlist, used when no team is monitored
     pub goal_music_indices: Vec<usize>,
     pub last_played_music_index: Option<usize>,
 
@@ -32,9 +39,43 @@ pub struct AppState {
     pub process_state: ProcessState,
     pub detection_count: usize,
     pub status_message: String,
+    /// Configuration profile whose file the settings are saved to
+    pub active_profile: String,
+    /// Problems reading settings files (at startup or when edited on disk),
+    /// shown until dismissed
+    pub load_problems: Vec<String>,
+    /// Bumped whenever settings are replaced wholesale, so the view resyncs
+    /// its controls
+    pub settings_generation: u64,
+    /// Club named in the most recent goal banner (selected or identified)
+    pub last_scoring_team: Option<SelectedTeam>,
+    /// Goals this session keyed by club display name
+    pub goals_by_team: HashMap<String, usize>,
+    /// Team text of the last goal banner that matched no monitored team
+    pub last_unmatched_team_text: Option<String>,
+    /// Near-miss team text offered as a new variation of a monitored team
+    pub variation_suggestion: Option<(SelectedTeam, String)>,
+    /// Suggestions the user dismissed this session (normalized to lowercase)
+    pub dismissed_variation_suggestions: HashSet<String>,
+    /// Home and away clubs of the match being played, read at kickoff
+    pub current_match: Option<(SelectedTeam, SelectedTeam)>,
+    /// Competition name last read from the competition region
+    pub current_competition: Option<String>,
+    /// When the last goal of this monitoring session was celebrated
+    pub last_goal_at: Option<SystemTime>,
+    /// Name of the goal music playing right now
+    pub current_track: Option<String>,
+    /// Timings and decisions of the current (or last) monitoring session
+    pub detection_metrics: DetectionMetrics,
+    /// Whether the detection loop publishes frames for the OCR debug panel
+    pub ocr_debug_enabled: bool,
+    /// Frame last published for the OCR debug panel
+    pub ocr_debug_frame: Option<OcrDebugFrame>,
 
     // OCR settings
     pub capture_region: [u32; 4],
+    /// Region showing the competition name, if configured
+    pub competition_region: Option<[u32; 4]>,
     pub ocr_threshold: u8,
     pub enable_morph_open: bool,
 
@@ -42,9 +83,13 @@ pub struct AppState {
     pub debounce_ms: u64,
 
     // Team selection
-    pub selected_team: Option<SelectedTeam>,
+    /// Monitored team whose profile the UI shows and edits; not saved
+    pub focused_team: Option<SelectedTeam>,
+    /// Monitored teams, each with its own celebration
+    pub team_profiles: Vec<TeamProfile>,
+    pub team_match_threshold: f32,
 
-    // Audio settings
+    // Audio settings (the default celebration's, when no team is monitored)
     pub music_volume: f32,
     pub ambiance_volume: f32,
     pub goal_ambiance_path: Option<String>,
@@ -64,6 +109,12 @@ pub struct AppState {
     // Internationalization
     pub selected_language: Language,
     pub custom_goal_phrases: Vec<String>,
+
+    // Diagnostics
+    pub flight_recorder_seconds: u64,
+    pub dump_recorder_on_trigger: bool,
+    /// Default iterations of the CLI `bench` command; only kept for saving
+    pub bench_frames: usize,
 }
 
 impl Default for AppState {
@@ -76,11 +127,29 @@ impl Default for AppState {
             process_state: ProcessState::Stopped,
             detection_count: 0,
             status_message: "Ready".to_string(),
+            active_profile: DEFAULT_PROFILE.to_string(),
+            load_problems: Vec::new(),
+            settings_generation: 0,
+            last_scoring_team: None,
+            goals_by_team: HashMap::new(),
+            last_unmatched_team_text: None,
+            variation_suggestion: None,
+            dismissed_variation_suggestions: HashSet::new(),
+            current_match: None,
+            current_competition: None,
+            last_goal_at: None,
+            current_track: None,
+            detection_metrics: DetectionMetrics::default(),
+            ocr_debug_enabled: false,
+            ocr_debug_frame: None,
             capture_region: [0, 0, 200, 100],
+            competition_region: None,
             ocr_threshold: 0, // 0 = Otsu auto-thresholding
             enable_morph_open: false,
             debounce_ms: 8000, // 8 seconds
-            selected_team: None,
+            focused_team: None,
+            team_profiles: Vec::new(),
+            team_match_threshold: crate::team_matcher::DEFAULT_MATCH_THRESHOLD,
             music_volume: 1.0,
             ambiance_volume: 0.6,
             goal_ambiance_path: None,
@@ -94,10 +163,30 @@ impl Default for AppState {
             preview_generation: 0,
             selected_language: Language::English,
             custom_goal_phrases: Vec::new(),
+            flight_recorder_seconds: 10,
+            dump_recorder_on_trigger: false,
+            bench_frames: 500,
         }
     }
 }
 
+/// Regular playlist, ambiance and volumes of the celebration shown in the UI
+#[derive(Clone, Debug, PartialEq)]
+pub struct CelebrationSettings {
+    pub goal_music_indices: Vec<usize>,
+    pub ambiance_path: Option<String>,
+    pub music_volume: f32,
+    pub ambiance_volume: f32,
+}
+
+/// Mutable view of the celebration shown in the UI, wherever it is stored
+pub struct CelebrationSettingsMut<'a> {
+    pub goal_music_indices: &'a mut Vec<usize>,
+    pub ambiance_path: &'a mut Option<String>,
+    pub music_volume: &'a mut f32,
+    pub ambiance_volume: &'a mut f32,
+}
+
 /// Validation errors
 #[derive(Debug, Clone)]
 pub enum ValidationError {
@@ -261,13 +350,22 @@ impl AppState {
 
     /// Effective goal playlist indices, with fallback to legacy single selection.
     ///
-    /// - If `goal_music_indices` is non-empty, it is used as the playlist.
+    /// - The playlist of the celebration shown in the UI is used if non-empty.
     /// - Otherwise, if `selected_music_index` is set, it is treated as a
     ///   single-track playlist.
     /// - Indices that are out of bounds are filtered out.
     pub fn goal_playlist_indices(&self) -> Vec<usize> {
-        let base_indices: Vec<usize> = if !self.goal_music_indices.is_empty() {
-            self.goal_music_indices.clone()
+        match self.focused_profile() {
+            Some(profile) => self.playlist_indices(&profile.goal_music_indices),
+            None => self.playlist_indices(&self.goal_music_indices),
+        }
+    }
+
+    /// Valid, deduplicated playlist for a set of goal music indices, falling
+    /// back to the selected music like `goal_playlist_indices`
+    pub fn playlist_indices(&self, goal_music_indices: &[usize]) -> Vec<usize> {
+        let base_indices: Vec<usize> = if !goal_music_indices.is_empty() {
+            goal_music_indices.to_vec()
         } else if let Some(idx) = self.selected_music_index {
             vec![idx]
         } else {
@@ -283,6 +381,147 @@ impl AppState {
         deduped
     }
 
+    /// Profile of the team shown in the UI
+    pub fn focused_profile(&self) -> Option<&TeamProfile> {
+        let team = self.focused_team.as_ref()?;
+        self.team_profiles
+            .iter()
+            .find(|profile| profile.is_for(team))
+    }
+
+    pub fn focused_profile_mut(&mut self) -> Option<&mut TeamProfile> {
+        let team = self.focused_team.as_ref()?;
+        self.team_profiles
+            .iter_mut()
+            .find(|profile| profile.is_for(team))
+    }
+
+    /// Celebration shown in the UI: the focused team's, or the default one
+    /// when no team is monitored
+    pub fn celebration(&self) -> CelebrationSettings {
+        match self.focused_profile() {
+            Some(profile) => CelebrationSettings {
+                goal_music_indices: profile.goal_music_indices.clone(),
+                ambiance_path: profile.ambiance_path.clone(),
+                music_volume: profile.music_volume,
+                ambiance_volume: profile.ambiance_volume,
+            },
+            None => CelebrationSettings {
+                goal_music_indices: self.goal_music_indices.clone(),
+                ambiance_path: self.goal_ambiance_path.clone(),
+                music_volume: self.music_volume,
+                ambiance_volume: self.ambiance_volume,
+            },
+        }
+    }
+
+    /// Edit the celebration shown in the UI in place
+    pub fn celebration_mut(&mut self) -> CelebrationSettingsMut<'_> {
+        let index = self.focused_team.as_ref().and_then(|team| {
+            self.team_profiles
+                .iter()
+                .position(|profile| profile.is_for(team))
+        });
+        match index {
+            Some(index) => {
+                let profile = &mut self.team_profiles[index];
+                CelebrationSettingsMut {
+                    goal_music_indices: &mut profile.goal_music_indices,
+                    ambiance_path: &mut profile.ambiance_path,
+                    music_volume: &mut profile.music_volume,
+                    ambiance_volume: &mut profile.ambiance_volume,
+                }
+            }
+            None => CelebrationSettingsMut {
+                goal_music_indices: &mut self.goal_music_indices,
+                ambiance_path: &mut self.goal_ambiance_path,
+                music_volume: &mut self.music_volume,
+                ambiance_volume: &mut self.ambiance_volume,
+            },
+        }
+    }
+
+    /// Show a team's profile, creating it from the shown celebration if needed
+    pub fn focus_team(&mut self, team: SelectedTeam) {
+        if !self
+            .team_profiles
+            .iter()
+            .any(|profile| profile.is_for(&team))
+        {
+            let celebration = self.celebration();
+            self.team_profiles.push(TeamProfile::new(
+                team.clone(),
+                celebration.goal_music_indices,
+                celebration.ambiance_path,
+                celebration.music_volume,
+                celebration.ambiance_volume,
+            ));
+        }
+        self.focused_team = Some(team);
+    }
+
+    /// Point the focused profile at another team, keeping its celebration
+    ///
+    /// Focuses the team's own profile instead if it already has one.
+    pub fn switch_focused_team(&mut self, team: SelectedTeam) {
+        let has_profile = self
+            .team_profiles
+            .iter()
+            .any(|profile| profile.is_for(&team));
+        if !has_profile {
+            if let Some(profile) = self.focused_profile_mut() {
+                profile.team = team.clone();
+            }
+        }
+        self.focus_team(team);
+    }
+
+    /// Stop monitoring the focused team and show the next remaining profile
+    ///
+    /// With no profiles left, the default celebration is shown again.
+    pub fn remove_focused_profile(&mut self) -> Option<TeamProfile> {
+        let team = self.focused_team.take()?;
+        let index = self
+            .team_profiles
+            .iter()
+            .position(|profile| profile.is_for(&team))?;
+        let removed = self.team_profiles.remove(index);
+        self.focused_team = self
+            .team_profiles
+            .first()
+            .map(|profile| profile.team.clone());
+        Some(removed)
+    }
+
+    /// Keep goal playlists pointing at the right tracks after one is removed
+    pub fn forget_music_index(&mut self, index: usize) {
+        let lists = std::iter::once(&mut self.goal_music_indices).chain(
+            self.team_profiles.iter_mut().flat_map(|profile| {
+                std::iter::once(&mut profile.goal_music_indices)
+                    .chain(
+                        profile
+                            .derby_celebration
+                            .iter_mut()
+                            .map(|derby| &mut derby.goal_music_indices),
+                    )
+                    .chain(
+                        profile
+                            .competition_overrides
+                            .iter_mut()
+                            .map(|cup| &mut cup.celebration.goal_music_indices),
+                    )
+            }),
+        );
+        for indices in lists {
+            indices.retain(|&i| i != index);
+            for idx_ref in indices.iter_mut() {
+                if *idx_ref > index {
+                    *idx_ref -= 1;
+                }
+            }
+        }
+    }
+
     /// Check if detection can be started
     pub fn can_start_detection(&self) -> Result<(), ValidationError> {
         // Must have music selected
@@ -342,4 +581,87 @@ mod tests {
         // No music selected
         assert!(state.validate_music_selection().is_err());
     }
+
+    fn team(key: &str) -> SelectedTeam {
+        SelectedTeam {
+            league: "Süper Lig".to_string(),
+            team_key: key.to_string(),
+            display_name: key.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_team_profiles_keep_their_own_playlist() {
+        let mut state = AppState {
+            goal_music_indices: vec![0],
+            ..Default::default()
+        };
+        state.focus_team(team("galatasaray"));
+
+        state.focus_team(team("fenerbahce"));
+        // A new profile starts from the shown celebration
+        assert_eq!(state.celebration().goal_music_indices, vec![0]);
+        *state.celebration_mut().goal_music_indices = vec![1, 2];
+        *state.celebration_mut().music_volume = 0.5;
+
+        state.focus_team(team("galatasaray"));
+        assert_eq!(state.celebration().goal_music_indices, vec![0]);
+        assert_eq!(state.celebration().music_volume, 1.0);
+
+        // Edits go straight to the profiles; the defaults are untouched
+        assert_eq!(state.team_profiles.len(), 2);
+        assert_eq!(state.team_profiles[1].goal_music_indices, vec![1, 2]);
+        assert_eq!(state.team_profiles[1].music_volume, 0.5);
+        assert_eq!(state.goal_music_indices, vec![0]);
+        assert_eq!(state.music_volume, 1.0);
+    }
+
+    #[test]
+    fn test_switch_and_remove_focused_team() {
+        let mut state = AppState::default();
+        state.focus_team(team("galatasaray"));
+        *state.celebration_mut().goal_music_indices = vec![3];
+
+        state.switch_focused_team(team("besiktas"));
+        assert_eq!(state.team_profiles.len(), 1);
+        assert_eq!(state.team_profiles[0].team.team_key, "besiktas");
+        assert_eq!(state.team_profiles[0].goal_music_indices, vec![3]);
+
+        state.focus_team(team("fenerbahce"));
+        let removed = state.remove_focused_profile().unwrap();
+        assert_eq!(removed.team.team_key, "fenerbahce");
+        assert_eq!(
+            state.focused_team.as_ref().map(|t| t.team_key.as_str()),
+            Some("besiktas")
+        );
+
+        state.remove_focused_profile();
+        assert!(state.focused_team.is_none());
+        assert!(state.team_profiles.is_empty());
+        assert!(state.celebration().goal_music_indices.is_empty());
+    }
+
+    #[test]
+    fn test_forget_music_index_updates_profiles() {
+        let mut state = AppState {
+            goal_music_indices: vec![1, 2],
+            ..Default::default()
+        };
+        state.focus_team(team("galatasaray"));
+        *state.celebration_mut().goal_music_indices = vec![0, 2];
+        state.focus_team(team("fenerbahce"));
+        *state.celebration_mut().goal_music_indices = vec![1, 2];
+
+        state.focused_profile_mut().unwrap().derby_celebration =
+            Some(crate::config::CelebrationOverride {
+                goal_music_indices: vec![1, 3],
+                ambiance_path: None,
+            });
+
+        state.forget_music_index(1);
+        assert_eq!(state.goal_music_indices, vec![1]);
+        assert_eq!(state.team_profiles[0].goal_music_indices, vec![0, 1]);
+        let derby = state.team_profiles[1].derby_celebration.as_ref().unwrap();
+        assert_eq!(derby.goal_music_indices, vec![2]);
+    }
 }
@@ -0,0 +1,196 @@
+/// Rolling statistics published by the detection loop
+///
+/// The loop records every frame it processes. The Detection tab shows
+/// averages and sparklines over the last `METRICS_WINDOW` frames, plus
+/// counts since monitoring started, so users can see during a match whether
+/// their region and threshold are working.
+use std::collections::VecDeque;
+use std::time::Instant;
+
+use crate::flight_recorder::FrameDecision;
+use crate::utils::IterationTiming;
+
+/// Frames kept for averages and sparklines
+pub const METRICS_WINDOW: usize = 120;
+
+/// Pipeline stage shown in the metrics
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    Capture,
+    Preprocess,
+    Ocr,
+    Total,
+}
+
+impl Stage {
+    pub const ALL: [Stage; 4] = [Stage::Capture, Stage::Preprocess, Stage::Ocr, Stage::Total];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Stage::Capture => "Capture",
+            Stage::Preprocess => "Preprocess",
+            Stage::Ocr => "OCR",
+            Stage::Total => "Total",
+        }
+    }
+
+    fn micros(&self, timing: &IterationTiming) -> f64 {
+        match self {
+            Stage::Capture => timing.capture_us,
+            Stage::Preprocess => timing.preprocess_us,
+            Stage::Ocr => timing.ocr_us,
+            Stage::Total => timing.total_us,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Sample {
+    at: Instant,
+    timing: IterationTiming,
+    decision: FrameDecision,
+    ocr_failed: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DetectionMetrics {
+    samples: VecDeque<Sample>,
+    /// Frames processed since monitoring started
+    pub frames: u64,
+    /// Frames that could not be judged because OCR failed
+    pub skipped_frames: u64,
+    pub triggered: u64,
+    /// Goals suppressed because one played within the debounce time
+    pub debounced: u64,
+    /// Goals suppressed because the banner named no monitored team
+    pub team_mismatches: u64,
+}
+
+impl DetectionMetrics {
+    /// Add one processed frame
+    pub fn record(
+        &mut self,
+        at: Instant,
+        timing: IterationTiming,
+        decision: FrameDecision,
+        ocr_failed: bool,
+    ) {
+        self.frames += 1;
+        if ocr_failed {
+            self.skipped_frames += 1;
+        }
+        match decision {
+            FrameDecision::Triggered => self.triggered += 1,
+            FrameDecision::Debounced => self.debounced += 1,
+            FrameDecision::TeamMismatch => self.team_mismatches += 1,
+            FrameDecision::NoText | FrameDecision::NoGoal => {}
+        }
+
+        if self.samples.len() == METRICS_WINDOW {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(Sample {
+            at,
+            timing,
+            decision,
+            ocr_failed,
+        });
+    }
+
+    /// Time spent in `stage` for each recent frame, oldest first, in ms
+    pub fn series(&self, stage: Stage) -> Vec<f64> {
+        self.samples
+            .iter()
+            .map(|sample| stage.micros(&sample.timing) / 1000.0)
+            .collect()
+    }
+
+    /// Mean time spent in `stage` over recent frames, in ms
+    pub fn average_ms(&self, stage: Stage) -> f64 {
+        if self.samples.is_empty() {
+            return 0.0;
+        }
+        self.series(stage).iter().sum::<f64>() / self.samples.len() as f64
+    }
+
+    /// Frames processed per second over recent frames
+    pub fn fps(&self) -> f64 {
+        let (Some(first), Some(last)) = (self.samples.front(), self.samples.back()) else {
+            return 0.0;
+        };
+        let span = last.at.duration_since(first.at).as_secs_f64();
+        if span <= 0.0 {
+            return 0.0;
+        }
+        (self.samples.len() - 1) as f64 / span
+    }
+
+    /// Share of recent frames where OCR read no text at all
+    pub fn empty_rate(&self) -> f64 {
+        let read: Vec<&Sample> = self.samples.iter().filter(|s| !s.ocr_failed).collect();
+        if read.is_empty() {
+            return 0.0;
+        }
+        let empty = read
+            .iter()
+            .filter(|sample| sample.decision == FrameDecision::NoText)
+            .count();
+        empty as f64 / read.len() as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn timing(ocr_ms: f64) -> IterationTiming {
+        IterationTiming {
+            ocr_us: ocr_ms * 1000.0,
+            total_us: ocr_ms * 1000.0 + 5000.0,
+            ..IterationTiming::new()
+        }
+    }
+
+    #[test]
+    fn test_counts_and_rates() {
+        let start = Instant::now();
+        let mut metrics = DetectionMetrics::default();
+        let decisions = [
+            (FrameDecision::NoText, false),
+            (FrameDecision::NoText, true),
+            (FrameDecision::NoGoal, false),
+            (FrameDecision::Triggered, false),
+            (FrameDecision::Debounced, false),
+        ];
+        for (index, (decision, failed)) in decisions.into_iter().enumerate() {
+            let at = start + Duration::from_millis(100 * index as u64);
+            metrics.record(at, timing(20.0), decision, failed);
+        }
+
+        assert_eq!(metrics.frames, 5);
+        assert_eq!(metrics.skipped_frames, 1);
+        assert_eq!((metrics.triggered, metrics.debounced), (1, 1));
+        // Four frames over 0.4 seconds
+        assert!((metrics.fps() - 10.0).abs() < 1e-9);
+        // The failed frame doesn't count as empty
+        assert_eq!(metrics.empty_rate(), 0.25);
+        assert_eq!(metrics.average_ms(Stage::Ocr), 20.0);
+        assert_eq!(metrics.average_ms(Stage::Total), 25.0);
+    }
+
+    #[test]
+    fn test_window_keeps_recent_frames() {
+        let start = Instant::now();
+        let mut metrics = DetectionMetrics::default();
+        for index in 0..METRICS_WINDOW + 10 {
+            let at = start + Duration::from_millis(index as u64);
+            metrics.record(at, timing(index as f64), FrameDecision::NoGoal, false);
+        }
+
+        let series = metrics.series(Stage::Ocr);
+        assert_eq!(series.len(), METRICS_WINDOW);
+        assert_eq!(series[0], 10.0);
+        assert_eq!(metrics.frames, (METRICS_WINDOW + 10) as u64);
+    }
+}
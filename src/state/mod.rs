@@ -2,8 +2,12 @@
 ///
 /// Centralized state management for the application with validation and persistence.
 pub mod app_state;
+pub mod detection_metrics;
+pub mod ocr_debug;
 pub mod process_state;
 
 // Re-export commonly used types
 pub use app_state::{AppState, MusicEntry, SelectedTeam, ValidationError};
+pub use detection_metrics::{DetectionMetrics, Stage};
+pub use ocr_debug::{OcrDebugFrame, OCR_DEBUG_INTERVAL};
 pub use process_state::{ProcessState, ProcessStateMachine, TransitionError};
@@ -0,0 +1,112 @@
+/// Latest frame shown in the live OCR debug panel
+///
+/// While the panel is open the detection loop publishes what it saw and
+/// decided at most every `OCR_DEBUG_INTERVAL`, with both images encoded as
+/// PNG so the view can draw them without touching the disk.
+use std::fmt;
+use std::io::Cursor;
+use std::time::Duration;
+
+use image::{GrayImage, ImageFormat, ImageResult, RgbaImage};
+
+use crate::flight_recorder::FrameDecision;
+
+/// Shortest time between two published debug frames
+pub const OCR_DEBUG_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Clone)]
+pub struct OcrDebugFrame {
+    /// Increases with every published frame
+    pub sequence: u64,
+    /// Captured region, as PNG
+    pub capture_png: Vec<u8>,
+    /// Binarised image fed to Tesseract, as PNG; `None` when OCR failed
+    pub binary_png: Option<Vec<u8>>,
+    pub text: String,
+    /// Goal rule that matched the text
+    pub rule: Option<String>,
+    /// Monitored team the banner was attributed to
+    pub team: Option<String>,
+    pub decision: FrameDecision,
+}
+
+impl OcrDebugFrame {
+    pub fn new(
+        sequence: u64,
+        capture: &RgbaImage,
+        binary: Option<&GrayImage>,
+        text: String,
+        rule: Option<String>,
+        team: Option<String>,
+        decision: FrameDecision,
+    ) -> ImageResult<Self> {
+        let capture_png = encode_png(|cursor| capture.write_to(cursor, ImageFormat::Png))?;
+        let binary_png = binary
+            .map(|binary| encode_png(|cursor| binary.write_to(cursor, ImageFormat::Png)))
+            .transpose()?;
+        Ok(Self {
+            sequence,
+            capture_png,
+            binary_png,
+            text,
+            rule,
+            team,
+            decision,
+        })
+    }
+}
+
+impl fmt::Debug for OcrDebugFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OcrDebugFrame")
+            .field("sequence", &self.sequence)
+            .field("text", &self.text)
+            .field("rule", &self.rule)
+            .field("team", &self.team)
+            .field("decision", &self.decision)
+            .finish_non_exhaustive()
+    }
+}
+
+fn encode_png(write: impl FnOnce(&mut Cursor<Vec<u8>>) -> ImageResult<()>) -> ImageResult<Vec<u8>> {
+    let mut cursor = Cursor::new(Vec::new());
+    write(&mut cursor)?;
+    Ok(cursor.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_images_are_png() {
+        let capture = RgbaImage::new(8, 4);
+        let binary = GrayImage::new(8, 4);
+        let frame = OcrDebugFrame::new(
+            1,
+            &capture,
+            Some(&binary),
+            "GOAL FOR ARSENAL".to_string(),
+            Some("default GOAL FOR/GOL pattern".to_string()),
+            Some("Arsenal".to_string()),
+            FrameDecision::Triggered,
+        )
+        .unwrap();
+
+        let decoded = image::load_from_memory(&frame.capture_png).unwrap();
+        assert_eq!((decoded.width(), decoded.height()), (8, 4));
+        assert!(image::load_from_memory(frame.binary_png.as_ref().unwrap()).is_ok());
+
+        let failed = OcrDebugFrame::new(
+            2,
+            &capture,
+            None,
+            String::new(),
+            None,
+            None,
+            FrameDecision::NoText,
+        )
+        .unwrap();
+        assert!(failed.binary_png.is_none());
+    }
+}
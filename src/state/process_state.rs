@@ -3,6 +3,8 @@
 /// Represents the lifecycle of the detection process with clear state transitions.
 use std::time::Instant;
 
+use serde::{Serialize, Serializer};
+
 /// State of the detection process
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum ProcessState {
@@ -52,6 +54,23 @@ impl ProcessState {
             ProcessState::Stopping => "Stopping...",
         }
     }
+
+    /// Stable lowercase name used in JSON
+    pub fn name(&self) -> &'static str {
+        match self {
+            ProcessState::Stopped => "stopped",
+            ProcessState::Starting => "starting",
+            ProcessState::Running { .. } => "running",
+            ProcessState::Stopping => "stopping",
+        }
+    }
+}
+
+/// Serialized as its `name`; the start `Instant` means nothing outside the process
+impl Serialize for ProcessState {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
 }
 
 impl Default for ProcessState {
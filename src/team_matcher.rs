@@ -1,60 +1,553 @@
-use crate::teams::Team;
+use crate::config::SelectedTeam;
+use crate::ocr::text_extraction;
+use crate::teams::{Team, TeamDatabase};
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+/// Default minimum score for a detected name to count as a match
+pub const DEFAULT_MATCH_THRESHOLD: f32 = 0.85;
+
+/// Minimum Jaro-Winkler similarity for two tokens to count as the same word
+const TOKEN_SIMILARITY_MIN: f32 = 0.85;
+
+/// Tokens shorter than this must match exactly ("fc" vs "ac", "man" vs "ham")
+const FUZZY_TOKEN_MIN_LEN: usize = 4;
+
+/// Unmatched text scoring at least this is offered as a new variation
+const NEAR_MISS_MIN_SCORE: f32 = 0.6;
 
 /// Matcher for checking if a detected team name matches the selected team
 pub struct TeamMatcher {
-    normalized_variations: Vec<String>,
+    /// Variations as written in the team database
+    names: Vec<String>,
+    variations: Vec<NormalizedName>,
+    threshold: f32,
+}
+
+/// A team name folded for comparison
+struct NormalizedName {
+    text: String,
+    /// Characters without whitespace, for whole-name edit distance
+    compact: Vec<char>,
+    tokens: Vec<Vec<char>>,
+}
+
+impl NormalizedName {
+    fn new(text: &str) -> Self {
+        let text = TeamMatcher::normalize(text);
+        Self {
+            compact: text.chars().filter(|c| !c.is_whitespace()).collect(),
+            tokens: text
+                .split_whitespace()
+                .map(|token| token.chars().collect())
+                .collect(),
+            text,
+        }
+    }
 }
 
 impl TeamMatcher {
     /// Create a new matcher for a specific team
     pub fn new(team: &Team) -> Self {
-        let normalized_variations = team.variations.iter().map(|v| Self::normalize(v)).collect();
+        Self::with_threshold(team, DEFAULT_MATCH_THRESHOLD)
+    }
 
+    /// Create a matcher accepting names that score at least `threshold` (0.0-1.0)
+    pub fn with_threshold(team: &Team, threshold: f32) -> Self {
         Self {
-            normalized_variations,
+            names: team.variations.clone(),
+            variations: team
+                .variations
+                .iter()
+                .map(|v| NormalizedName::new(v))
+                .collect(),
+            threshold: threshold.clamp(0.0, 1.0),
         }
     }
 
+    /// The variation that best matches the detected text, with its score
+    pub fn best_variation(&self, detected_name: &str) -> Option<(&str, f32)> {
+        let detected = NormalizedName::new(detected_name);
+        if detected.compact.is_empty() {
+            return None;
+        }
+
+        let mut best: Option<(usize, f32)> = None;
+        for (index, variation) in self.variations.iter().enumerate() {
+            let score = if variation.text == detected.text {
+                1.0
+            } else {
+                score_variation(variation, &detected).0
+            };
+            if best
+                .map(|(_, best_score)| score > best_score)
+                .unwrap_or(true)
+            {
+                best = Some((index, score));
+            }
+        }
+        best.map(|(index, score)| (self.names[index].as_str(), score))
+    }
+
+    /// Whether unmatched text looks like an unknown way of writing this team
+    ///
+    /// True when the text does not match but comes close, or reads as an
+    /// abbreviation of a variation: same initials and its letters in order,
+    /// as in "Man Utd" for "Manchester United" or "Gala" for "Galatasaray".
+    pub fn is_near_miss(&self, detected_name: &str) -> bool {
+        let detected = NormalizedName::new(detected_name);
+        if detected.compact.len() < 3 {
+            return false;
+        }
+        let (score, _) = self.best_match(&detected);
+        if score >= self.threshold {
+            return false;
+        }
+        score >= NEAR_MISS_MIN_SCORE
+            || self
+                .variations
+                .iter()
+                .any(|variation| is_abbreviation(&detected, variation))
+    }
+
     /// Check if the detected team name matches any variation of the selected team
     pub fn matches(&self, detected_name: &str) -> bool {
+        self.score(detected_name) >= self.threshold
+    }
+
+    /// Score how well the detected text matches this team, from 0.0 to 1.0
+    ///
+    /// Each variation is scored two ways and the best result wins:
+    /// - token overlap: every variation token is looked up among the detected
+    ///   tokens, allowing OCR typos via Jaro-Winkler similarity, and the
+    ///   similarities are averaged. Extra detected tokens do not count
+    ///   against the score, so "fc internazionale milano" fully matches
+    ///   "fc internazionale".
+    /// - whole-name edit distance, which copes with OCR merging or splitting
+    ///   words ("MANCHESTERUNITED").
+    pub fn score(&self, detected_name: &str) -> f32 {
         let normalized_detected = Self::normalize(detected_name);
 
         // Fast path: exact equality with any normalized variation
         if self
-            .normalized_variations
+            .variations
             .iter()
-            .any(|variation| variation == &normalized_detected)
+            .any(|variation| variation.text == normalized_detected)
         {
-            return true;
+            return 1.0;
         }
 
-        // Token-subset match:
-        // Accept when all tokens from a variation are present in detected tokens.
-        // This covers cases like detected: "fc internazionale milano" vs variation: "fc internazionale".
-        let detected_tokens = Self::tokens(&normalized_detected);
-        self.normalized_variations.iter().any(|variation| {
-            let var_tokens = Self::tokens(variation);
-            !var_tokens.is_empty() && var_tokens.is_subset(&detected_tokens)
-        })
+        self.best_match(&NormalizedName::new(&normalized_detected))
+            .0
+    }
+
+    /// Best (score, coverage) over all variations
+    ///
+    /// Coverage is the share of detected tokens explained by the variation and
+    /// is only used to break ties between teams.
+    fn best_match(&self, detected: &NormalizedName) -> (f32, f32) {
+        if detected.compact.is_empty() {
+            return (0.0, 0.0);
+        }
+
+        if self
+            .variations
+            .iter()
+            .any(|variation| variation.text == detected.text)
+        {
+            return (1.0, 1.0);
+        }
+
+        self.variations
+            .iter()
+            .map(|variation| score_variation(variation, detected))
+            .fold(
+                (0.0, 0.0),
+                |best, candidate| {
+                    if candidate > best {
+                        candidate
+                    } else {
+                        best
+                    }
+                },
+            )
     }
 
     /// Normalize a team name for matching
-    /// - Convert to lowercase
-    /// - Remove special characters (keep only ASCII alphanumerics and spaces)
+    /// - Fold diacritics via Unicode NFD decomposition ("Atlético" → "atletico")
+    /// - Map letters without a decomposition (ı, ø, ß, ...) to ASCII
+    /// - Convert to lowercase and drop punctuation
     /// - Normalize whitespace (trim and collapse multiple spaces)
     fn normalize(text: &str) -> String {
-        text.to_lowercase()
-            .chars()
-            .filter(|c| c.is_ascii_alphanumeric() || c.is_ascii_whitespace())
-            .collect::<String>()
-            .split_whitespace()
-            .collect::<Vec<&str>>()
-            .join(" ")
+        let mut folded = String::with_capacity(text.len());
+        let mut push = |c: char| {
+            if c == ' ' {
+                if !folded.is_empty() && !folded.ends_with(' ') {
+                    folded.push(' ');
+                }
+            } else {
+                folded.push(c);
+            }
+        };
+
+        for ch in text.nfd() {
+            if is_combining_mark(ch) {
+                continue;
+            }
+            match ch {
+                'ı' => push('i'),
+                'ø' | 'Ø' => push('o'),
+                'đ' | 'Đ' => push('d'),
+                'ł' | 'Ł' => push('l'),
+                'æ' | 'Æ' => "ae".chars().for_each(&mut push),
+                'ß' => "ss".chars().for_each(&mut push),
+                c if c.is_ascii() => {
+                    if c.is_ascii_alphanumeric() {
+                        push(c.to_ascii_lowercase());
+                    } else if c.is_ascii_whitespace() {
+                        push(' ');
+                    }
+                }
+                c if c.is_alphanumeric() => c.to_lowercase().for_each(&mut push),
+                c if c.is_whitespace() => push(' '),
+                _ => {}
+            }
+        }
+
+        if folded.ends_with(' ') {
+            folded.pop();
+        }
+        folded
+    }
+}
+
+/// Score one variation against the detected name, returning (score, coverage)
+fn score_variation(variation: &NormalizedName, detected: &NormalizedName) -> (f32, f32) {
+    if variation.tokens.is_empty() {
+        return (0.0, 0.0);
     }
 
-    /// Split a normalized string into lowercase ASCII tokens
-    fn tokens(text: &str) -> std::collections::HashSet<&str> {
-        text.split_whitespace().collect()
+    let mut total = 0.0;
+    let mut matched = 0;
+    for token in &variation.tokens {
+        let best = detected
+            .tokens
+            .iter()
+            .map(|candidate| token_similarity(token, candidate))
+            .fold(0.0, f32::max);
+        if best > 0.0 {
+            matched += 1;
+        }
+        total += best;
+    }
+    let token_score = total / variation.tokens.len() as f32;
+    let whole_score = edit_similarity(&variation.compact, &detected.compact);
+
+    let coverage = matched as f32 / detected.tokens.len().max(1) as f32;
+    (token_score.max(whole_score), coverage.min(1.0))
+}
+
+/// Whether `short` abbreviates `full`: same first letter, every token starting
+/// like a token of `full`, and all letters appearing in order
+fn is_abbreviation(short: &NormalizedName, full: &NormalizedName) -> bool {
+    if short.compact.len() >= full.compact.len() || short.compact.first() != full.compact.first() {
+        return false;
+    }
+
+    let starts_like_full_token = |token: &Vec<char>| {
+        full.tokens
+            .iter()
+            .any(|full_token| full_token.first() == token.first())
+    };
+    if !short.tokens.iter().all(starts_like_full_token) {
+        return false;
+    }
+
+    let mut remaining = full.compact.iter();
+    short
+        .compact
+        .iter()
+        .all(|c| remaining.any(|candidate| candidate == c))
+}
+
+/// Similarity of two tokens, or 0.0 when they should not be treated as the same word
+fn token_similarity(a: &[char], b: &[char]) -> f32 {
+    if a == b {
+        return 1.0;
+    }
+    if a.len() < FUZZY_TOKEN_MIN_LEN || b.len() < FUZZY_TOKEN_MIN_LEN {
+        return 0.0;
+    }
+    let similarity = jaro_winkler(a, b);
+    if similarity >= TOKEN_SIMILARITY_MIN {
+        similarity
+    } else {
+        0.0
+    }
+}
+
+/// Jaro-Winkler similarity (0.0-1.0), favouring strings with a common prefix
+fn jaro_winkler(a: &[char], b: &[char]) -> f32 {
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    let match_distance = (a.len().max(b.len()) / 2).saturating_sub(1);
+    let mut a_matched = vec![false; a.len()];
+    let mut b_matched = vec![false; b.len()];
+    let mut matches = 0usize;
+
+    for (i, ca) in a.iter().enumerate() {
+        let start = i.saturating_sub(match_distance);
+        let end = (i + match_distance + 1).min(b.len());
+        if let Some(j) = (start..end).find(|&j| !b_matched[j] && b[j] == *ca) {
+            a_matched[i] = true;
+            b_matched[j] = true;
+            matches += 1;
+        }
+    }
+    if matches == 0 {
+        return 0.0;
+    }
+
+    let b_in_order: Vec<char> = b
+        .iter()
+        .zip(&b_matched)
+        .filter(|(_, &m)| m)
+        .map(|(c, _)| *c)
+        .collect();
+    let transpositions = a
+        .iter()
+        .zip(&a_matched)
+        .filter(|(_, &m)| m)
+        .zip(&b_in_order)
+        .filter(|((ca, _), cb)| *ca != *cb)
+        .count();
+
+    let m = matches as f32;
+    let jaro =
+        (m / a.len() as f32 + m / b.len() as f32 + (m - transpositions as f32 / 2.0) / m) / 3.0;
+    let prefix = a
+        .iter()
+        .zip(b)
+        .take(4)
+        .take_while(|(ca, cb)| ca == cb)
+        .count();
+
+    jaro + prefix as f32 * 0.1 * (1.0 - jaro)
+}
+
+/// 1.0 minus the Levenshtein distance normalised by the longer length
+fn edit_similarity(a: &[char], b: &[char]) -> f32 {
+    let max_len = a.len().max(b.len());
+    if max_len == 0 {
+        return 1.0;
+    }
+
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != cb);
+            current[j + 1] = (previous[j] + cost)
+                .min(previous[j + 1] + 1)
+                .min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    1.0 - previous[b.len()] as f32 / max_len as f32
+}
+
+/// Whether `text` mentions `name`, ignoring case, accents and punctuation
+///
+/// Used for competition names read from the screen, e.g. "UEFA Champions
+/// League - Final" mentions "champions league".
+pub fn mentions(text: &str, name: &str) -> bool {
+    let name = TeamMatcher::normalize(name);
+    !name.is_empty() && TeamMatcher::normalize(text).contains(&name)
+}
+
+/// Best team found in the database for a piece of detected text
+#[derive(Debug, Clone)]
+pub struct TeamMatch {
+    pub league: String,
+    pub team_key: String,
+    pub team: Team,
+    /// Match quality in (0.0, 1.0], see `TeamMatcher::score`
+    pub score: f32,
+}
+
+impl TeamMatch {
+    pub fn to_selected_team(&self) -> SelectedTeam {
+        SelectedTeam {
+            league: self.league.clone(),
+            team_key: self.team_key.clone(),
+            display_name: self.team.display_name.clone(),
+        }
+    }
+}
+
+/// Resolves detected text against every team in a `TeamDatabase`
+///
+/// Used to name the scoring club when no team is selected.
+pub struct TeamIdentifier {
+    candidates: Vec<(String, String, Team, TeamMatcher)>,
+    threshold: f32,
+}
+
+impl TeamIdentifier {
+    /// Build matchers for every team in the database
+    pub fn new(database: &TeamDatabase) -> Self {
+        Self::with_threshold(database, DEFAULT_MATCH_THRESHOLD)
+    }
+
+    /// Build matchers accepting names that score at least `threshold`
+    pub fn with_threshold(database: &TeamDatabase, threshold: f32) -> Self {
+        let mut candidates: Vec<(String, String, Team, TeamMatcher)> = database
+            .iter_teams()
+            .map(|(league, key, team)| {
+                (
+                    league.to_string(),
+                    key.to_string(),
+                    team.clone(),
+                    TeamMatcher::with_threshold(team, threshold),
+                )
+            })
+            .collect();
+        // Stable order so ties always resolve to the same team
+        candidates.sort_by(|a, b| (&a.0, &a.1).cmp(&(&b.0, &b.1)));
+
+        Self {
+            candidates,
+            threshold,
+        }
+    }
+
+    /// Find the team that best matches the detected text
+    ///
+    /// Goal banner prefixes such as "GOAL FOR" are stripped first when present.
+    /// Ties on score go to the team explaining more of the detected words, so
+    /// "West Ham United" beats a team matching only "United".
+    ///
+    /// # Returns
+    /// The highest scoring team at or above the threshold, or `None`
+    pub fn identify(&self, detected_text: &str) -> Option<TeamMatch> {
+        let team_text = text_extraction::extract_team_name(detected_text)
+            .unwrap_or_else(|| detected_text.to_string());
+        let detected = NormalizedName::new(&team_text);
+
+        let mut best: Option<((f32, f32), usize)> = None;
+        for (index, (_, _, _, matcher)) in self.candidates.iter().enumerate() {
+            let candidate = matcher.best_match(&detected);
+            if candidate.0 < matcher.threshold || candidate.0 <= 0.0 {
+                continue;
+            }
+            let better = match best {
+                Some((best_score, _)) => candidate > best_score,
+                None => true,
+            };
+            if better {
+                best = Some((candidate, index));
+            }
+        }
+
+        best.map(|((score, _), index)| {
+            let (league, team_key, team, _) = &self.candidates[index];
+            TeamMatch {
+                league: league.clone(),
+                team_key: team_key.clone(),
+                team: team.clone(),
+                score,
+            }
+        })
+    }
+
+    /// Find both clubs of a kickoff or scoreboard line such as
+    /// "Galatasaray 0-0 Fenerbahçe"
+    ///
+    /// # Returns
+    /// The fixture when each side resolves to a different team, `None` otherwise
+    pub fn identify_fixture(&self, text: &str) -> Option<Fixture> {
+        let (home_text, away_text) = text_extraction::split_fixture(text)?;
+        let home = self.identify(&home_text)?;
+        let away = self.identify(&away_text)?;
+        if home.league == away.league && home.team_key == away.team_key {
+            return None;
+        }
+        Some(Fixture {
+            home,
+            away,
+            threshold: self.threshold,
+        })
+    }
+}
+
+/// The two clubs of the match being played, read at kickoff or from the scoreboard
+#[derive(Debug, Clone)]
+pub struct Fixture {
+    pub home: TeamMatch,
+    pub away: TeamMatch,
+    /// Match threshold of the identifier that read the fixture
+    threshold: f32,
+}
+
+impl Fixture {
+    /// "Home vs Away" label for display
+    pub fn label(&self) -> String {
+        format!(
+            "{} vs {}",
+            self.home.team.display_name, self.away.team.display_name
+        )
+    }
+
+    /// Side of the fixture named by a goal detector
+    ///
+    /// Accepts either club's display name or the generic "Home"/"Away".
+    pub fn side(&self, name: &str) -> Option<&TeamMatch> {
+        if name.eq_ignore_ascii_case("home")
+            || name.eq_ignore_ascii_case(&self.home.team.display_name)
+        {
+            Some(&self.home)
+        } else if name.eq_ignore_ascii_case("away")
+            || name.eq_ignore_ascii_case(&self.away.team.display_name)
+        {
+            Some(&self.away)
+        } else {
+            None
+        }
+    }
+
+    /// The club `team` plays against, if it is one of the two sides
+    pub fn opponent_of(&self, team: &SelectedTeam) -> Option<&TeamMatch> {
+        let is = |side: &TeamMatch| side.league == team.league && side.team_key == team.team_key;
+        if is(&self.home) {
+            Some(&self.away)
+        } else if is(&self.away) {
+            Some(&self.home)
+        } else {
+            None
+        }
+    }
+
+    /// Side whose name variations match a goal banner better
+    ///
+    /// # Returns
+    /// The club scoring at least the identifier's threshold and strictly
+    /// higher than the other, or `None` when the banner doesn't tell them apart
+    pub fn scorer(&self, text: &str) -> Option<&TeamMatch> {
+        let team_text =
+            text_extraction::extract_team_name(text).unwrap_or_else(|| text.to_string());
+        let home = TeamMatcher::new(&self.home.team).score(&team_text);
+        let away = TeamMatcher::new(&self.away.team).score(&team_text);
+        if home >= self.threshold && home > away {
+            Some(&self.home)
+        } else if away >= self.threshold && away > home {
+            Some(&self.away)
+        } else {
+            None
+        }
     }
 }
 
@@ -88,7 +581,7 @@ mod tests {
     #[test]
     fn test_normalize_special_chars() {
         assert_eq!(TeamMatcher::normalize("FC Barcelona"), "fc barcelona");
-        assert_eq!(TeamMatcher::normalize("Atlético Madrid"), "atltico madrid");
+        assert_eq!(TeamMatcher::normalize("Atlético Madrid"), "atletico madrid");
         assert_eq!(TeamMatcher::normalize("Man. City!"), "man city");
     }
 
@@ -150,6 +643,230 @@ mod tests {
         assert!(!matcher.matches("Manchester"));
     }
 
+    #[test]
+    fn test_normalize_folds_diacritics() {
+        assert_eq!(TeamMatcher::normalize("Beşiktaş"), "besiktas");
+        assert_eq!(TeamMatcher::normalize("BEŞİKTAŞ"), "besiktas");
+        assert_eq!(TeamMatcher::normalize("Fenerbahçe"), "fenerbahce");
+        assert_eq!(TeamMatcher::normalize("Kasımpaşa"), "kasimpasa");
+        assert_eq!(TeamMatcher::normalize("Bodø/Glimt"), "bodoglimt");
+        assert_eq!(
+            TeamMatcher::normalize("Borussia Mönchengladbach"),
+            "borussia monchengladbach"
+        );
+    }
+
+    #[test]
+    fn test_matches_across_diacritics() {
+        let team = Team {
+            display_name: "Beşiktaş".to_string(),
+            variations: vec!["Beşiktaş".to_string(), "Beşiktaş JK".to_string()],
+        };
+        let matcher = TeamMatcher::new(&team);
+
+        assert!(matcher.matches("BESIKTAS"));
+        assert!(matcher.matches("GOAL FOR BEŞİKTAŞ"));
+        assert!(!matcher.matches("Fenerbahçe"));
+    }
+
+    #[test]
+    fn test_matches_ocr_typos() {
+        let team = create_test_team();
+        let matcher = TeamMatcher::new(&team);
+
+        assert!(matcher.matches("Manchestr United"));
+        assert!(matcher.matches("MANCHESTER UNlTED"));
+        assert!(matcher.matches("MANCHESTERUNITED"));
+        assert!(matcher.score("Manchestr United") < 1.0);
+    }
+
+    #[test]
+    fn test_score_ignores_extra_detected_text() {
+        let team = create_test_team();
+        let matcher = TeamMatcher::new(&team);
+
+        assert_eq!(matcher.score("Manchester United"), 1.0);
+        assert_eq!(matcher.score("Manchester United more text"), 1.0);
+        assert!(matcher.score("Liverpool") < 0.5);
+    }
+
+    #[test]
+    fn test_configurable_threshold() {
+        let team = create_test_team();
+        let strict = TeamMatcher::with_threshold(&team, 1.0);
+        let lenient = TeamMatcher::with_threshold(&team, 0.5);
+
+        assert!(!strict.matches("Manchestr United"));
+        assert!(strict.matches("Manchester United"));
+        assert!(lenient.matches("Manchester"));
+    }
+
+    #[test]
+    fn test_jaro_winkler() {
+        let chars = |s: &str| s.chars().collect::<Vec<_>>();
+        assert_eq!(jaro_winkler(&chars("arsenal"), &chars("arsenal")), 1.0);
+        assert!(jaro_winkler(&chars("martha"), &chars("marhta")) > 0.96);
+        assert_eq!(jaro_winkler(&chars("abc"), &chars("xyz")), 0.0);
+    }
+
+    #[test]
+    fn test_edit_similarity() {
+        let chars = |s: &str| s.chars().collect::<Vec<_>>();
+        assert_eq!(edit_similarity(&chars("kitten"), &chars("kitten")), 1.0);
+        assert_eq!(
+            edit_similarity(&chars("kitten"), &chars("sitting")),
+            1.0 - 3.0 / 7.0
+        );
+    }
+
+    fn create_test_database() -> TeamDatabase {
+        serde_json::from_str(
+            r#"{
+                "Premier League": {
+                    "arsenal": {"display_name": "Arsenal", "variations": ["Arsenal", "Gunners"]},
+                    "manchester_united": {
+                        "display_name": "Manchester United",
+                        "variations": ["Man United", "Manchester United", "Man Utd"]
+                    },
+                    "west_ham": {"display_name": "West Ham", "variations": ["West Ham United", "West Ham"]}
+                },
+                "La Liga": {
+                    "barcelona": {"display_name": "Barcelona", "variations": ["Barcelona", "FC Barcelona"]}
+                }
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_identify_team_from_banner() {
+        let identifier = TeamIdentifier::new(&create_test_database());
+
+        let found = identifier.identify("GOAL FOR ARSENAL").unwrap();
+        assert_eq!(found.league, "Premier League");
+        assert_eq!(found.team_key, "arsenal");
+        assert_eq!(found.score, 1.0);
+
+        let found = identifier.identify("GOL FC Barcelona").unwrap();
+        assert_eq!(found.team_key, "barcelona");
+        assert_eq!(found.to_selected_team().display_name, "Barcelona");
+    }
+
+    #[test]
+    fn test_identify_team_with_trailing_text() {
+        let identifier = TeamIdentifier::new(&create_test_database());
+
+        let found = identifier.identify("GOAL FOR MAN UTD MORE TEXT").unwrap();
+        assert_eq!(found.team_key, "manchester_united");
+
+        let found = identifier.identify("GOAL FOR ARSENAI").unwrap();
+        assert_eq!(found.team_key, "arsenal");
+        assert!(found.score < 1.0);
+
+        let found = identifier.identify("GOAL FOR WEST HAM UNITED").unwrap();
+        assert_eq!(found.team_key, "west_ham");
+    }
+
+    #[test]
+    fn test_identify_unknown_team() {
+        let identifier = TeamIdentifier::new(&create_test_database());
+        assert!(identifier.identify("GOAL FOR Real Madrid").is_none());
+        assert!(identifier.identify("").is_none());
+    }
+
+    #[test]
+    fn test_identify_fixture() {
+        let identifier = TeamIdentifier::new(&create_test_database());
+
+        let fixture = identifier
+            .identify_fixture("Kick Off  Man Utd 0-0 Arsenal")
+            .unwrap();
+        assert_eq!(fixture.home.team_key, "manchester_united");
+        assert_eq!(fixture.away.team_key, "arsenal");
+        assert_eq!(fixture.label(), "Manchester United vs Arsenal");
+
+        assert!(identifier
+            .identify_fixture("Arsenal vs Real Madrid")
+            .is_none());
+        assert!(identifier.identify_fixture("Arsenal vs Gunners").is_none());
+        assert!(identifier.identify_fixture("GOAL FOR Arsenal").is_none());
+    }
+
+    #[test]
+    fn test_fixture_sides() {
+        let identifier = TeamIdentifier::new(&create_test_database());
+        let fixture = identifier.identify_fixture("West Ham v Barcelona").unwrap();
+
+        assert_eq!(fixture.side("Home").unwrap().team_key, "west_ham");
+        assert_eq!(fixture.side("barcelona").unwrap().team_key, "barcelona");
+        assert!(fixture.side("Arsenal").is_none());
+
+        let scorer = fixture.scorer("GOAL FOR FC BARCELONA").unwrap();
+        assert_eq!(scorer.team_key, "barcelona");
+        assert!(fixture.scorer("GOAL FOR Arsenal").is_none());
+
+        // The user's match threshold carries over to the fixture
+        let strict = TeamIdentifier::with_threshold(&create_test_database(), 0.95);
+        let strict_fixture = strict.identify_fixture("West Ham v Barcelona").unwrap();
+        assert_eq!(strict_fixture.threshold, 0.95);
+        assert_eq!(fixture.threshold, DEFAULT_MATCH_THRESHOLD);
+
+        let opponent = fixture.opponent_of(&scorer.to_selected_team()).unwrap();
+        assert_eq!(opponent.team_key, "west_ham");
+        assert!(fixture
+            .opponent_of(&SelectedTeam {
+                league: scorer.league.clone(),
+                team_key: "arsenal".to_string(),
+                display_name: "Arsenal".to_string(),
+            })
+            .is_none());
+    }
+
+    #[test]
+    fn test_mentions() {
+        assert!(mentions(
+            "UEFA Champions League - Final",
+            "champions league"
+        ));
+        assert!(mentions("Türkiye Kupası Finali", "Turkiye Kupasi"));
+        assert!(!mentions("Premier League", "Champions League"));
+        assert!(!mentions("Premier League", "  "));
+    }
+
+    #[test]
+    fn test_best_variation() {
+        let team = Team {
+            display_name: "Manchester United".to_string(),
+            variations: vec!["Manchester United".to_string(), "Man Utd".to_string()],
+        };
+        let matcher = TeamMatcher::new(&team);
+
+        assert_eq!(matcher.best_variation("MAN UTD"), Some(("Man Utd", 1.0)));
+        let (name, score) = matcher.best_variation("MANCHESTER UNITEO").unwrap();
+        assert_eq!(name, "Manchester United");
+        assert!(score > 0.85);
+        assert_eq!(matcher.best_variation("  "), None);
+    }
+
+    #[test]
+    fn test_near_miss() {
+        let matcher = TeamMatcher::new(&create_test_team());
+        assert!(matcher.is_near_miss("Man Utd"));
+        assert!(matcher.is_near_miss("MUFC"));
+        // Matches and unrelated names are not near misses
+        assert!(!matcher.is_near_miss("Manchester United"));
+        assert!(!matcher.is_near_miss("Liverpool"));
+        assert!(!matcher.is_near_miss("Utd"));
+
+        let galatasaray = Team {
+            display_name: "Galatasaray".to_string(),
+            variations: vec!["Galatasaray".to_string()],
+        };
+        let matcher = TeamMatcher::new(&galatasaray);
+        assert!(matcher.is_near_miss("G.Saray"));
+        assert!(!matcher.is_near_miss("Fenerbahce"));
+    }
+
     #[test]
     fn test_matcher_performance() {
         let team = create_test_team();
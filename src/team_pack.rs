@@ -0,0 +1,568 @@
+/// Shareable team packs
+///
+/// A team pack is a zip archive (`.fmteams`) holding a `manifest.json` with
+/// leagues, teams and name variations, plus the teams' PNG logos under
+/// `logos/`. Packs let users share whole databases, for example lower leagues
+/// or fictional FM databases, including the custom logos stored in the
+/// config directory.
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use zip::write::SimpleFileOptions;
+use zip::{ZipArchive, ZipWriter};
+
+use crate::teams::{is_safe_name, normalize_variations, Team, TeamDatabase};
+
+/// File extension used for team packs
+pub const PACK_EXTENSION: &str = "fmteams";
+
+const MANIFEST_FILE: &str = "manifest.json";
+const FORMAT_VERSION: u32 = 1;
+/// Largest logo accepted from a pack
+const MAX_LOGO_BYTES: u64 = 2 * 1024 * 1024;
+
+/// A team as stored in a pack manifest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PackTeam {
+    pub display_name: String,
+    pub variations: Vec<String>,
+    /// Path of the logo inside the archive
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logo: Option<String>,
+}
+
+/// Contents of `manifest.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamPackManifest {
+    pub format_version: u32,
+    pub name: String,
+    /// League name -> team key -> team
+    pub leagues: BTreeMap<String, BTreeMap<String, PackTeam>>,
+}
+
+/// What to do when a pack team already exists in the database
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictResolution {
+    /// Keep the existing team untouched
+    Skip,
+    /// Overwrite the existing team (and logo) with the pack's version
+    Replace,
+    /// Keep the existing name and add the pack's variations to it
+    MergeVariations,
+}
+
+/// Outcome of importing a pack; entries are (league, team key)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImportReport {
+    pub added: Vec<(String, String)>,
+    pub replaced: Vec<(String, String)>,
+    pub merged: Vec<(String, String)>,
+    pub skipped: Vec<(String, String)>,
+}
+
+impl ImportReport {
+    /// One-line summary for status messages
+    pub fn summary(&self) -> String {
+        format!(
+            "{} added, {} replaced, {} merged, {} skipped",
+            self.added.len(),
+            self.replaced.len(),
+            self.merged.len(),
+            self.skipped.len()
+        )
+    }
+}
+
+/// A team pack loaded in memory
+#[derive(Debug, Clone)]
+pub struct TeamPack {
+    pub manifest: TeamPackManifest,
+    /// Logo bytes keyed by their archive path
+    logos: BTreeMap<String, Vec<u8>>,
+}
+
+impl TeamPack {
+    /// Build a pack from teams of a database
+    ///
+    /// Logos are picked up from `logo_root/<league>/<key>.png` when present.
+    /// Unknown (league, key) pairs are ignored.
+    pub fn from_database<'a>(
+        name: &str,
+        database: &TeamDatabase,
+        teams: impl IntoIterator<Item = (&'a str, &'a str)>,
+        logo_root: &Path,
+    ) -> Result<Self> {
+        let mut manifest = TeamPackManifest {
+            format_version: FORMAT_VERSION,
+            name: name.to_string(),
+            leagues: BTreeMap::new(),
+        };
+        let mut logos = BTreeMap::new();
+
+        for (league, key) in teams {
+            let Some(team) = database.find_team(league, key) else {
+                continue;
+            };
+
+            let logo_path = logo_root.join(league).join(format!("{key}.png"));
+            let logo = if logo_path.exists() {
+                let bytes = fs::read(&logo_path)
+                    .with_context(|| format!("Failed to read logo {}", logo_path.display()))?;
+                let entry = format!("logos/{}/{}.png", league, key);
+                logos.insert(entry.clone(), bytes);
+                Some(entry)
+            } else {
+                None
+            };
+
+            manifest
+                .leagues
+                .entry(league.to_string())
+                .or_default()
+                .insert(
+                    key.to_string(),
+                    PackTeam {
+                        display_name: team.display_name,
+                        variations: team.variations,
+                        logo,
+                    },
+                );
+        }
+
+        if manifest.leagues.is_empty() {
+            bail!("No teams to export");
+        }
+        Ok(Self { manifest, logos })
+    }
+
+    /// Number of teams in the pack
+    pub fn team_count(&self) -> usize {
+        self.manifest
+            .leagues
+            .values()
+            .map(|teams| teams.len())
+            .sum()
+    }
+
+    /// Iterate over the pack's teams as (league, key, team)
+    pub fn teams(&self) -> impl Iterator<Item = (&str, &str, &PackTeam)> {
+        self.manifest.leagues.iter().flat_map(|(league, teams)| {
+            teams
+                .iter()
+                .map(move |(key, team)| (league.as_str(), key.as_str(), team))
+        })
+    }
+
+    /// Pack teams that already exist in the database
+    pub fn conflicts(&self, database: &TeamDatabase) -> Vec<(String, String)> {
+        self.teams()
+            .filter(|(league, key, _)| database.has_team(league, key))
+            .map(|(league, key, _)| (league.to_string(), key.to_string()))
+            .collect()
+    }
+
+    /// Logo bytes for a pack team, if it ships one
+    pub fn logo(&self, team: &PackTeam) -> Option<&[u8]> {
+        team.logo
+            .as_ref()
+            .and_then(|entry| self.logos.get(entry))
+            .map(Vec::as_slice)
+    }
+
+    /// Write the pack as a zip archive
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let file =
+            File::create(path).with_context(|| format!("Failed to create {}", path.display()))?;
+        let mut zip = ZipWriter::new(file);
+        let options = SimpleFileOptions::default();
+
+        zip.start_file(MANIFEST_FILE, options)?;
+        zip.write_all(serde_json::to_string_pretty(&self.manifest)?.as_bytes())?;
+        for (entry, bytes) in &self.logos {
+            zip.start_file(entry.as_str(), options)?;
+            zip.write_all(bytes)?;
+        }
+        zip.finish()?;
+
+        tracing::info!(
+            "[team_pack] Exported {} teams to {}",
+            self.team_count(),
+            path.display()
+        );
+        Ok(())
+    }
+
+    /// Read and validate a pack archive
+    pub fn read(path: &Path) -> Result<Self> {
+        let file =
+            File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+        let mut zip = ZipArchive::new(file).context("Not a valid team pack archive")?;
+
+        let manifest: TeamPackManifest = {
+            let mut entry = zip
+                .by_name(MANIFEST_FILE)
+                .map_err(|_| anyhow!("Team pack has no {MANIFEST_FILE}"))?;
+            let mut json = String::new();
+            entry.read_to_string(&mut json)?;
+            serde_json::from_str(&json).context("Invalid team pack manifest")?
+        };
+        validate_manifest(&manifest)?;
+
+        let mut logos = BTreeMap::new();
+        for team in manifest.leagues.values().flat_map(|teams| teams.values()) {
+            let Some(entry_name) = &team.logo else {
+                continue;
+            };
+            let Ok(entry) = zip.by_name(entry_name) else {
+                tracing::warn!("[team_pack] Missing logo {} in pack", entry_name);
+                continue;
+            };
+            if entry.size() > MAX_LOGO_BYTES {
+                tracing::warn!("[team_pack] Skipping oversized logo {}", entry_name);
+                continue;
+            }
+            let mut bytes = Vec::new();
+            entry.take(MAX_LOGO_BYTES).read_to_end(&mut bytes)?;
+            if !bytes.starts_with(b"\x89PNG") {
+                tracing::warn!("[team_pack] Skipping non-PNG logo {}", entry_name);
+                continue;
+            }
+            logos.insert(entry_name.clone(), bytes);
+        }
+
+        Ok(Self { manifest, logos })
+    }
+
+    /// Merge the pack's teams into a database
+    ///
+    /// Leagues are created as needed. Logos are not written here; callers
+    /// install them for the added and replaced teams in the report.
+    pub fn import_into(
+        &self,
+        database: &mut TeamDatabase,
+        resolution: ConflictResolution,
+    ) -> ImportReport {
+        let mut report = ImportReport::default();
+
+        for (league, key, pack_team) in self.teams() {
+            let id = (league.to_string(), key.to_string());
+            let team = Team {
+                display_name: pack_team.display_name.clone(),
+                variations: pack_team.variations.clone(),
+            };
+
+            let Some(existing) = database.find_team(league, key) else {
+                match database.add_team(league.to_string(), key.to_string(), team) {
+                    Ok(()) => report.added.push(id),
+                    Err(err) => {
+                        tracing::warn!("[team_pack] {}", err);
+                        report.skipped.push(id);
+                    }
+                }
+                continue;
+            };
+
+            match resolution {
+                ConflictResolution::Skip => report.skipped.push(id),
+                ConflictResolution::Replace => {
+                    let replaced = database
+                        .remove_team(league, key)
+                        .and_then(|_| database.add_team(league.to_string(), key.to_string(), team));
+                    match replaced {
+                        Ok(()) => report.replaced.push(id),
+                        Err(err) => {
+                            tracing::warn!("[team_pack] {}", err);
+                            report.skipped.push(id);
+                        }
+                    }
+                }
+                ConflictResolution::MergeVariations => {
+                    let mut variations = existing.variations.clone();
+                    variations.extend(pack_team.variations.iter().cloned());
+                    match database.set_variations(league, key, variations) {
+                        Ok(()) => report.merged.push(id),
+                        Err(err) => {
+                            tracing::warn!("[team_pack] {}", err);
+                            report.skipped.push(id);
+                        }
+                    }
+                }
+            }
+        }
+
+        tracing::info!(
+            "[team_pack] Imported pack '{}': {}",
+            self.manifest.name,
+            report.summary()
+        );
+        report
+    }
+
+    /// Write the pack's logos for the given teams to `logo_root/<league>/<key>.png`
+    pub fn install_logos<'a>(
+        &self,
+        logo_root: &Path,
+        teams: impl IntoIterator<Item = &'a (String, String)>,
+    ) -> Result<usize> {
+        let mut installed = 0;
+        for (league, key) in teams {
+            let Some(bytes) = self
+                .manifest
+                .leagues
+                .get(league)
+                .and_then(|teams| teams.get(key))
+                .and_then(|team| self.logo(team))
+            else {
+                continue;
+            };
+            let dir = logo_root.join(league);
+            fs::create_dir_all(&dir)
+                .with_context(|| format!("Failed to create logo directory {}", dir.display()))?;
+            fs::write(dir.join(format!("{key}.png")), bytes)
+                .with_context(|| format!("Failed to write logo for {key}"))?;
+            installed += 1;
+        }
+        Ok(installed)
+    }
+}
+
+/// Reject manifests that are unusable or could write outside the logo folder
+fn validate_manifest(manifest: &TeamPackManifest) -> Result<()> {
+    if manifest.format_version > FORMAT_VERSION {
+        bail!(
+            "Team pack format {} is newer than supported ({}); update the app",
+            manifest.format_version,
+            FORMAT_VERSION
+        );
+    }
+
+    for (league, teams) in &manifest.leagues {
+        if !is_safe_name(league) {
+            bail!("Invalid league name '{}' in team pack", league);
+        }
+        for (key, team) in teams {
+            if !is_safe_name(key) {
+                bail!("Invalid team key '{}' in team pack", key);
+            }
+            if team.display_name.trim().is_empty() {
+                bail!("Team '{}' in team pack has no name", key);
+            }
+            normalize_variations(team.variations.clone())
+                .map_err(|err| anyhow!("Team '{}' in team pack: {}", key, err))?;
+        }
+    }
+
+    if manifest.leagues.values().all(|teams| teams.is_empty()) {
+        bail!("Team pack contains no teams");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    const PNG: &[u8] = b"\x89PNG\r\n\x1a\nfake";
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "fm_goal_musics_pack_{}_{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn database() -> TeamDatabase {
+        let mut db = TeamDatabase::default();
+        for (league, key, name) in [
+            ("League Two", "Grimsby", "Grimsby"),
+            ("League Two", "Wrexham", "Wrexham"),
+            ("National League", "Barnet", "Barnet"),
+        ] {
+            db.add_team(
+                league.to_string(),
+                key.to_string(),
+                Team {
+                    display_name: name.to_string(),
+                    variations: vec![name.to_string()],
+                },
+            )
+            .unwrap();
+        }
+        db
+    }
+
+    #[test]
+    fn test_export_import_roundtrip_with_logos() {
+        let dir = temp_dir("roundtrip");
+        let logos = dir.join("logos");
+        fs::create_dir_all(logos.join("League Two")).unwrap();
+        fs::write(logos.join("League Two").join("Wrexham.png"), PNG).unwrap();
+
+        let source = database();
+        let pack = TeamPack::from_database(
+            "Lower leagues",
+            &source,
+            [("League Two", "Grimsby"), ("League Two", "Wrexham")],
+            &logos,
+        )
+        .unwrap();
+        let path = dir.join("lower.fmteams");
+        pack.write(&path).unwrap();
+
+        let loaded = TeamPack::read(&path).unwrap();
+        assert_eq!(loaded.manifest.name, "Lower leagues");
+        assert_eq!(loaded.team_count(), 2);
+
+        let mut target = TeamDatabase::default();
+        let report = loaded.import_into(&mut target, ConflictResolution::Skip);
+        assert_eq!(report.added.len(), 2);
+        assert!(target.has_team("League Two", "Wrexham"));
+
+        let installed_root = dir.join("installed");
+        assert_eq!(
+            loaded
+                .install_logos(&installed_root, &report.added)
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            fs::read(installed_root.join("League Two").join("Wrexham.png")).unwrap(),
+            PNG
+        );
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_conflict_resolution() {
+        let pack_team = |variations: &[&str]| PackTeam {
+            display_name: "Wrexham AFC".to_string(),
+            variations: variations.iter().map(|v| v.to_string()).collect(),
+            logo: None,
+        };
+        let mut leagues = BTreeMap::new();
+        leagues.insert(
+            "League Two".to_string(),
+            BTreeMap::from([(
+                "Wrexham".to_string(),
+                pack_team(&["Wrexham AFC", "Red Dragons"]),
+            )]),
+        );
+        let pack = TeamPack {
+            manifest: TeamPackManifest {
+                format_version: FORMAT_VERSION,
+                name: "test".to_string(),
+                leagues,
+            },
+            logos: BTreeMap::new(),
+        };
+
+        let mut db = database();
+        assert_eq!(pack.conflicts(&db).len(), 1);
+        let report = pack.import_into(&mut db, ConflictResolution::Skip);
+        assert_eq!(report.skipped.len(), 1);
+        assert_eq!(
+            db.find_team("League Two", "Wrexham").unwrap().display_name,
+            "Wrexham"
+        );
+
+        let report = pack.import_into(&mut db, ConflictResolution::MergeVariations);
+        assert_eq!(report.merged.len(), 1);
+        let merged = db.find_team("League Two", "Wrexham").unwrap();
+        assert_eq!(merged.display_name, "Wrexham");
+        assert_eq!(
+            merged.variations,
+            vec!["Wrexham", "Wrexham AFC", "Red Dragons"]
+        );
+
+        let report = pack.import_into(&mut db, ConflictResolution::Replace);
+        assert_eq!(report.replaced.len(), 1);
+        assert_eq!(
+            db.find_team("League Two", "Wrexham").unwrap().display_name,
+            "Wrexham AFC"
+        );
+    }
+
+    #[test]
+    fn test_merge_variations_keeps_embedded_key() {
+        let mut db = TeamDatabase::default();
+        db.add_team(
+            "Premier League".to_string(),
+            "arsenal".to_string(),
+            Team {
+                display_name: "Arsenal".to_string(),
+                variations: vec!["Arsenal".to_string()],
+            },
+        )
+        .unwrap();
+        let pack = TeamPack {
+            manifest: TeamPackManifest {
+                format_version: FORMAT_VERSION,
+                name: "test".to_string(),
+                leagues: BTreeMap::from([(
+                    "Premier League".to_string(),
+                    BTreeMap::from([(
+                        "arsenal".to_string(),
+                        PackTeam {
+                            display_name: "Arsenal FC".to_string(),
+                            variations: vec!["Gunners".to_string()],
+                            logo: None,
+                        },
+                    )]),
+                )]),
+            },
+            logos: BTreeMap::new(),
+        };
+
+        let report = pack.import_into(&mut db, ConflictResolution::MergeVariations);
+        assert_eq!(
+            report.merged,
+            vec![("Premier League".to_string(), "arsenal".to_string())]
+        );
+        let merged = db.find_team("Premier League", "arsenal").unwrap();
+        assert_eq!(merged.display_name, "Arsenal");
+        assert_eq!(merged.variations, vec!["Arsenal", "Gunners"]);
+        assert!(!db.has_team("Premier League", "Arsenal"));
+    }
+
+    #[test]
+    fn test_rejects_unsafe_names() {
+        assert!(is_safe_name("Premier League"));
+        assert!(is_safe_name("Süper Lig"));
+        assert!(!is_safe_name("../etc"));
+        assert!(!is_safe_name(".."));
+        assert!(!is_safe_name("a\\b"));
+        assert!(!is_safe_name(" "));
+    }
+
+    #[test]
+    fn test_read_rejects_invalid_archives() {
+        let dir = temp_dir("invalid");
+
+        let not_zip = dir.join("bad.fmteams");
+        fs::write(&not_zip, b"not a zip").unwrap();
+        assert!(TeamPack::read(&not_zip).is_err());
+
+        let traversal = dir.join("traversal.fmteams");
+        let mut zip = ZipWriter::new(File::create(&traversal).unwrap());
+        zip.start_file(MANIFEST_FILE, SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(
+            br#"{"format_version":1,"name":"x","leagues":{"../evil":{"a":{"display_name":"A","variations":["A"]}}}}"#,
+        )
+        .unwrap();
+        zip.finish().unwrap();
+        assert!(TeamPack::read(&traversal).is_err());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
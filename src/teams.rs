@@ -3,46 +3,293 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 
+use crate::app_paths;
+use crate::persistence::{self, LoadProblem};
+
+/// Team database shipped with the application
+const EMBEDDED_DB: &str = include_str!("../config/teams.json");
+
 /// Represents a single team with display name and name variations
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Team {
     pub display_name: String,
     pub variations: Vec<String>,
 }
 
 /// Database of all teams organized by league
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TeamDatabase {
     #[serde(flatten)]
     leagues: HashMap<String, HashMap<String, Team>>,
 }
 
+/// The embedded database a user's teams.json was last merged with
+///
+/// Stored next to teams.json so the next release can tell which differences
+/// are user edits and which are upstream changes.
+#[derive(Debug, Serialize, Deserialize)]
+struct BaseSnapshot {
+    /// Content hash of the embedded database
+    version: String,
+    database: TeamDatabase,
+}
+
+/// What changed when merging an embedded database update into teams.json
+///
+/// Entries are formatted as "League / Team".
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MergeReport {
+    /// Teams shipped for the first time
+    pub added: Vec<String>,
+    /// Teams whose name or variations were updated
+    pub updated: Vec<String>,
+    /// Teams dropped upstream that the user had not edited
+    pub removed: Vec<String>,
+    /// Teams where the user's own edit was kept over an upstream change
+    pub kept_user_edits: Vec<String>,
+}
+
+impl MergeReport {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.updated.is_empty()
+            && self.removed.is_empty()
+            && self.kept_user_edits.is_empty()
+    }
+
+    /// One-line summary for status messages
+    pub fn summary(&self) -> String {
+        let mut summary = format!(
+            "{} added, {} updated, {} removed",
+            self.added.len(),
+            self.updated.len(),
+            self.removed.len()
+        );
+        if !self.kept_user_edits.is_empty() {
+            summary.push_str(&format!(
+                ", {} of your edits kept",
+                self.kept_user_edits.len()
+            ));
+        }
+        summary
+    }
+}
+
 impl TeamDatabase {
     /// Load team database from JSON file
     pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        Self::load_with_report().map(|(database, _, _)| database)
+    }
+
+    /// Load the team database, merging in any update to the embedded database
+    ///
+    /// On first run the embedded database is copied to the user config
+    /// directory. On later runs, if the embedded database changed since the
+    /// last merge, upstream additions, fixes and removals are merged into the
+    /// user's teams.json without overwriting the user's own edits.
+    ///
+    /// A teams.json that doesn't parse is replaced by its newest readable
+    /// backup, or rebuilt from the embedded database if there is none.
+    ///
+    /// # Returns
+    /// The database, a report of what the merge changed (empty if nothing)
+    /// and, if teams.json was unreadable, what went wrong
+    pub fn load_with_report(
+    ) -> Result<(Self, MergeReport, Option<LoadProblem>), Box<dyn std::error::Error>> {
         let db_path = Self::database_path()?;
+        let upstream = Self::load_embedded()?;
+        let version = embedded_version();
+
+        let recovered = persistence::load_with_recovery(&db_path, |content| {
+            serde_json::from_str::<TeamDatabase>(content)
+        })?;
+        let (mut database, problem) = match recovered {
+            Some(persistence::Recovered {
+                value: Some(database),
+                problem,
+            }) => (database, problem),
+            recovered => {
+                // First run, or nothing readable: start from the embedded database
+                let problem = recovered.and_then(|r| r.problem);
+                if problem.is_none() {
+                    tracing::info!("[teams] teams.json not found in user config directory, creating from embedded default");
+                }
+                upstream.save()?;
+                Self::save_base(&version, &upstream)?;
+                return Ok((upstream, MergeReport::default(), problem));
+            }
+        };
+        if problem.is_some() {
+            // Replace the broken file with the restored backup
+            database.save()?;
+        }
 
-        if !db_path.exists() {
-            // First run: copy embedded database to user config directory
-            tracing::info!("[teams] teams.json not found in user config directory, creating from embedded default");
-            let database = Self::load_embedded()?;
+        let base = Self::load_base();
+        if base.as_ref().map(|b| b.version == version).unwrap_or(false) {
+            return Ok((database, MergeReport::default(), problem));
+        }
+
+        let report = database.merge_upstream(base.as_ref().map(|b| &b.database), &upstream);
+        if !report.is_empty() {
             database.save()?;
-            return Ok(database);
+            tracing::info!(
+                "[teams] Merged embedded database update: {}",
+                report.summary()
+            );
+            for entry in &report.added {
+                tracing::info!("[teams]   added {}", entry);
+            }
+            for entry in &report.updated {
+                tracing::info!("[teams]   updated {}", entry);
+            }
+            for entry in &report.removed {
+                tracing::info!("[teams]   removed {}", entry);
+            }
+            for entry in &report.kept_user_edits {
+                tracing::info!("[teams]   kept your edit of {}", entry);
+            }
         }
+        Self::save_base(&version, &upstream)?;
 
-        // Load from user config directory
-        let content = fs::read_to_string(&db_path)?;
-        let database: TeamDatabase = serde_json::from_str(&content)?;
-        Ok(database)
+        Ok((database, report, problem))
     }
 
     /// Load embedded default database (fallback)
-    fn load_embedded() -> Result<Self, Box<dyn std::error::Error>> {
-        const EMBEDDED_DB: &str = include_str!("../config/teams.json");
+    pub fn load_embedded() -> Result<Self, Box<dyn std::error::Error>> {
         let database: TeamDatabase = serde_json::from_str(EMBEDDED_DB)?;
         Ok(database)
     }
 
+    fn base_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+        let db_path = Self::database_path()?;
+        Ok(db_path.with_file_name("teams_base.json"))
+    }
+
+    /// Snapshot of the embedded database from the last merge, if readable
+    fn load_base() -> Option<BaseSnapshot> {
+        let path = Self::base_path().ok()?;
+        let content = fs::read_to_string(path).ok()?;
+        match serde_json::from_str(&content) {
+            Ok(base) => Some(base),
+            Err(err) => {
+                tracing::warn!("[teams] Ignoring unreadable teams_base.json: {}", err);
+                None
+            }
+        }
+    }
+
+    fn save_base(version: &str, database: &TeamDatabase) -> Result<(), Box<dyn std::error::Error>> {
+        let snapshot = BaseSnapshot {
+            version: version.to_string(),
+            database: database.clone(),
+        };
+        persistence::write_atomic(
+            &Self::base_path()?,
+            serde_json::to_string(&snapshot)?.as_bytes(),
+        )?;
+        Ok(())
+    }
+
+    /// Three-way merge of an upstream database into this (user) database
+    ///
+    /// `base` is the upstream database this one was last merged with, or
+    /// `None` for databases created before merging existed; then nothing is
+    /// removed and existing teams only gain new variations.
+    ///
+    /// Rules per team:
+    /// - new upstream and unknown locally: added
+    /// - unchanged locally since `base`: replaced by the upstream version
+    /// - edited locally: the user's display name wins if they changed it, and
+    ///   upstream variation additions/removals are applied on top of the
+    ///   user's list
+    /// - deleted locally: stays deleted
+    /// - removed upstream: removed only if the user had not edited it
+    pub fn merge_upstream(
+        &mut self,
+        base: Option<&TeamDatabase>,
+        upstream: &TeamDatabase,
+    ) -> MergeReport {
+        let empty = TeamDatabase::default();
+        let has_base = base.is_some();
+        let base = base.unwrap_or(&empty);
+        let mut report = MergeReport::default();
+
+        let mut upstream_teams: Vec<(&str, &str, &Team)> = upstream.iter_teams().collect();
+        upstream_teams.sort_by(|a, b| (a.0, a.1).cmp(&(b.0, b.1)));
+
+        for (league, key, theirs) in upstream_teams {
+            let old = base.leagues.get(league).and_then(|teams| teams.get(key));
+            let ours = self.leagues.get(league).and_then(|teams| teams.get(key));
+            let label = format!("{} / {}", league, theirs.display_name);
+
+            let merged = match (old, ours) {
+                (None, None) => {
+                    report.added.push(label);
+                    theirs.clone()
+                }
+                // Deleted by the user
+                (Some(_), None) => continue,
+                (Some(old), Some(_)) if old == theirs => continue,
+                (Some(old), Some(ours)) if ours == old => {
+                    report.updated.push(label);
+                    theirs.clone()
+                }
+                (old, Some(ours)) => {
+                    let merged = merge_team(old, ours, theirs);
+                    let user_renamed =
+                        old.map_or(has_base, |o| o.display_name != ours.display_name);
+                    if user_renamed && ours.display_name != theirs.display_name {
+                        report
+                            .kept_user_edits
+                            .push(format!("{} / {}", league, ours.display_name));
+                    }
+                    if &merged == ours {
+                        continue;
+                    }
+                    report.updated.push(label);
+                    merged
+                }
+            };
+
+            self.leagues
+                .entry(league.to_string())
+                .or_default()
+                .insert(key.to_string(), merged);
+        }
+
+        let mut base_teams: Vec<(&str, &str, &Team)> = base.iter_teams().collect();
+        base_teams.sort_by(|a, b| (a.0, a.1).cmp(&(b.0, b.1)));
+
+        for (league, key, old) in base_teams {
+            if upstream.has_team(league, key) {
+                continue;
+            }
+            let Some(teams) = self.leagues.get_mut(league) else {
+                continue;
+            };
+            match teams.get(key) {
+                Some(ours) if ours == old => {
+                    teams.remove(key);
+                    report
+                        .removed
+                        .push(format!("{} / {}", league, old.display_name));
+                }
+                Some(ours) => {
+                    report
+                        .kept_user_edits
+                        .push(format!("{} / {}", league, ours.display_name));
+                }
+                None => {}
+            }
+            // Drop leagues emptied by upstream removals
+            if teams.is_empty() && !upstream.has_league(league) {
+                self.leagues.remove(league);
+            }
+        }
+
+        report
+    }
+
     /// Save team database to user config directory
     pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
         let db_path = Self::database_path()?;
@@ -53,7 +300,7 @@ impl TeamDatabase {
         }
 
         let json = serde_json::to_string_pretty(self)?;
-        fs::write(&db_path, json)?;
+        persistence::write_with_backups(&db_path, json.as_bytes())?;
 
         tracing::info!("[teams] Saved teams database to: {}", db_path.display());
         Ok(())
@@ -61,12 +308,7 @@ impl TeamDatabase {
 
     /// Get the path to the teams database file in user-writable config directory
     pub fn database_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
-        let base = dirs::config_dir().ok_or("Could not determine user config directory")?;
-        // Application-specific folder
-        let app_dir = base.join("FMGoalMusic");
-        // Ensure directory exists
-        fs::create_dir_all(&app_dir)?;
-        Ok(app_dir.join("teams.json"))
+        Ok(app_paths::config_dir()?.join("teams.json"))
     }
 
     /// Get the teams database path for display purposes
@@ -95,6 +337,15 @@ impl TeamDatabase {
         })
     }
 
+    /// Iterate over every team as (league, team_key, team)
+    pub fn iter_teams(&self) -> impl Iterator<Item = (&str, &str, &Team)> {
+        self.leagues.iter().flat_map(|(league, teams)| {
+            teams
+                .iter()
+                .map(move |(key, team)| (league.as_str(), key.as_str(), team))
+        })
+    }
+
     /// Find a specific team by league and team key
     pub fn find_team(&self, league: &str, team_key: &str) -> Option<Team> {
         self.leagues
@@ -132,6 +383,9 @@ impl TeamDatabase {
 
     /// Add a new league to the database
     pub fn add_league(&mut self, league_name: String) -> Result<(), String> {
+        if !is_safe_name(&league_name) {
+            return Err(format!("Invalid league name '{}'", league_name));
+        }
         if self.leagues.contains_key(&league_name) {
             return Err(format!("League '{}' already exists", league_name));
         }
@@ -183,12 +437,381 @@ impl TeamDatabase {
             .map(|teams| teams.contains_key(team_key))
             .unwrap_or(false)
     }
+
+    /// Replace a team's display name and variations
+    ///
+    /// The team key is only re-derived when the display name changes, so
+    /// editing variations alone never re-keys a team.
+    ///
+    /// # Returns
+    /// The team's key after the update
+    pub fn update_team(
+        &mut self,
+        league_name: &str,
+        team_key: &str,
+        display_name: &str,
+        variations: Vec<String>,
+    ) -> Result<String, String> {
+        let display_name = display_name.trim();
+        if display_name.is_empty() {
+            return Err("Team name cannot be empty".to_string());
+        }
+        let unchanged_name = self
+            .leagues
+            .get(league_name)
+            .and_then(|teams| teams.get(team_key))
+            .is_some_and(|team| team.display_name == display_name);
+        if unchanged_name {
+            self.set_variations(league_name, team_key, variations)?;
+            return Ok(team_key.to_string());
+        }
+
+        let new_key = crate::slug::slugify(display_name);
+        if new_key.is_empty() {
+            return Err(format!(
+                "Team name '{}' has no usable characters",
+                display_name
+            ));
+        }
+        let variations = normalize_variations(variations)?;
+
+        let teams = self
+            .leagues
+            .get_mut(league_name)
+            .ok_or_else(|| format!("League '{}' not found", league_name))?;
+        if !teams.contains_key(team_key) {
+            return Err(format!(
+                "Team '{}' not found in league '{}'",
+                team_key, league_name
+            ));
+        }
+        if new_key != team_key && teams.contains_key(&new_key) {
+            return Err(format!(
+                "Team '{}' already exists in league '{}'",
+                new_key, league_name
+            ));
+        }
+
+        teams.remove(team_key);
+        teams.insert(
+            new_key.clone(),
+            Team {
+                display_name: display_name.to_string(),
+                variations,
+            },
+        );
+        tracing::info!(
+            "[teams] Updated team {} in league {} (key: {})",
+            display_name,
+            league_name,
+            new_key
+        );
+        Ok(new_key)
+    }
+
+    /// Replace a team's variations, keeping its key and display name
+    pub fn set_variations(
+        &mut self,
+        league_name: &str,
+        team_key: &str,
+        variations: Vec<String>,
+    ) -> Result<(), String> {
+        let variations = normalize_variations(variations)?;
+        let team = self
+            .leagues
+            .get_mut(league_name)
+            .and_then(|teams| teams.get_mut(team_key))
+            .ok_or_else(|| format!("Team '{}' not found in league '{}'", team_key, league_name))?;
+        team.variations = variations;
+        tracing::info!(
+            "[teams] Updated variations of {} in league {}",
+            team.display_name,
+            league_name
+        );
+        Ok(())
+    }
+
+    /// Remove a team from a league
+    pub fn remove_team(&mut self, league_name: &str, team_key: &str) -> Result<Team, String> {
+        let team = self
+            .leagues
+            .get_mut(league_name)
+            .and_then(|teams| teams.remove(team_key))
+            .ok_or_else(|| format!("Team '{}' not found in league '{}'", team_key, league_name))?;
+        tracing::info!(
+            "[teams] Removed team {} from league {}",
+            team.display_name,
+            league_name
+        );
+        Ok(team)
+    }
+
+    /// Move a team to another existing league, keeping its key
+    pub fn move_team(
+        &mut self,
+        from_league: &str,
+        team_key: &str,
+        to_league: &str,
+    ) -> Result<(), String> {
+        if from_league == to_league {
+            return Ok(());
+        }
+        if !self.has_team(from_league, team_key) {
+            return Err(format!(
+                "Team '{}' not found in league '{}'",
+                team_key, from_league
+            ));
+        }
+        if !self.has_league(to_league) {
+            return Err(format!("League '{}' not found", to_league));
+        }
+        if self.has_team(to_league, team_key) {
+            return Err(format!(
+                "Team '{}' already exists in league '{}'",
+                team_key, to_league
+            ));
+        }
+
+        if let Some(team) = self
+            .leagues
+            .get_mut(from_league)
+            .and_then(|teams| teams.remove(team_key))
+        {
+            tracing::info!(
+                "[teams] Moved team {} from {} to {}",
+                team.display_name,
+                from_league,
+                to_league
+            );
+            if let Some(teams) = self.leagues.get_mut(to_league) {
+                teams.insert(team_key.to_string(), team);
+            }
+        }
+        Ok(())
+    }
+
+    /// Rename a league, keeping all of its teams
+    pub fn rename_league(&mut self, old_name: &str, new_name: &str) -> Result<(), String> {
+        let new_name = new_name.trim();
+        if new_name.is_empty() {
+            return Err("League name cannot be empty".to_string());
+        }
+        if !is_safe_name(new_name) {
+            return Err(format!("Invalid league name '{}'", new_name));
+        }
+        if old_name == new_name {
+            return Ok(());
+        }
+        if self.leagues.contains_key(new_name) {
+            return Err(format!("League '{}' already exists", new_name));
+        }
+
+        let teams = self
+            .leagues
+            .remove(old_name)
+            .ok_or_else(|| format!("League '{}' not found", old_name))?;
+        self.leagues.insert(new_name.to_string(), teams);
+        tracing::info!("[teams] Renamed league {} to {}", old_name, new_name);
+        Ok(())
+    }
+
+    /// Remove a league and every team in it
+    ///
+    /// # Returns
+    /// Number of teams that were removed with the league
+    pub fn remove_league(&mut self, league_name: &str) -> Result<usize, String> {
+        let teams = self
+            .leagues
+            .remove(league_name)
+            .ok_or_else(|| format!("League '{}' not found", league_name))?;
+        tracing::info!(
+            "[teams] Removed league {} with {} teams",
+            league_name,
+            teams.len()
+        );
+        Ok(teams.len())
+    }
+}
+
+/// League names and team keys become path components for logos
+pub fn is_safe_name(name: &str) -> bool {
+    let trimmed = name.trim();
+    !trimmed.is_empty()
+        && trimmed != "."
+        && trimmed != ".."
+        && !name.contains(['/', '\\', ':'])
+        && !name.chars().any(char::is_control)
+}
+
+/// Trim variations and drop blanks and case-insensitive duplicates
+///
+/// Fails if no variation is left, since a team without variations can
+/// never be matched.
+pub fn normalize_variations(variations: Vec<String>) -> Result<Vec<String>, String> {
+    let mut result: Vec<String> = Vec::new();
+    for variation in variations {
+        let variation = variation.trim();
+        if variation.is_empty() {
+            continue;
+        }
+        let lower = variation.to_lowercase();
+        if !result.iter().any(|v| v.to_lowercase() == lower) {
+            result.push(variation.to_string());
+        }
+    }
+    if result.is_empty() {
+        return Err("A team needs at least one name variation".to_string());
+    }
+    Ok(result)
+}
+
+/// Merge one team edited locally with its upstream version
+fn merge_team(old: Option<&Team>, ours: &Team, theirs: &Team) -> Team {
+    let display_name = match old {
+        Some(old) if old.display_name == ours.display_name => theirs.display_name.clone(),
+        _ => ours.display_name.clone(),
+    };
+
+    let mut variations = ours.variations.clone();
+    for variation in &theirs.variations {
+        let new_upstream = !matches!(old, Some(o) if o.variations.contains(variation));
+        if new_upstream && !variations.contains(variation) {
+            variations.push(variation.clone());
+        }
+    }
+    if let Some(old) = old {
+        variations.retain(|v| !old.variations.contains(v) || theirs.variations.contains(v));
+    }
+
+    Team {
+        display_name,
+        variations,
+    }
+}
+
+/// Stable content hash of the embedded database (FNV-1a, hex)
+fn embedded_version() -> String {
+    let hash = EMBEDDED_DB
+        .bytes()
+        .fold(0xcbf29ce484222325u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        });
+    format!("{:016x}", hash)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn team(name: &str, variations: &[&str]) -> Team {
+        Team {
+            display_name: name.to_string(),
+            variations: variations.iter().map(|v| v.to_string()).collect(),
+        }
+    }
+
+    fn database(teams: &[(&str, &str, Team)]) -> TeamDatabase {
+        let mut db = TeamDatabase::default();
+        for (league, key, team) in teams {
+            db.leagues
+                .entry(league.to_string())
+                .or_default()
+                .insert(key.to_string(), team.clone());
+        }
+        db
+    }
+
+    #[test]
+    fn test_merge_adds_new_upstream_teams() {
+        let base = database(&[("L1", "a", team("A", &["A"]))]);
+        let upstream = database(&[
+            ("L1", "a", team("A", &["A"])),
+            ("L2", "b", team("B", &["B"])),
+        ]);
+        let mut user = base.clone();
+        user.leagues
+            .get_mut("L1")
+            .unwrap()
+            .insert("mine".to_string(), team("Mine", &["Mine"]));
+
+        let report = user.merge_upstream(Some(&base), &upstream);
+        assert_eq!(report.added, vec!["L2 / B"]);
+        assert!(user.has_team("L2", "b"));
+        assert!(user.has_team("L1", "mine"));
+    }
+
+    #[test]
+    fn test_merge_updates_untouched_and_keeps_user_edits() {
+        let base = database(&[("L", "a", team("A", &["A"])), ("L", "b", team("B", &["B"]))]);
+        let upstream = database(&[
+            ("L", "a", team("A Fixed", &["A", "A FC"])),
+            ("L", "b", team("B Upstream", &["B", "B2"])),
+        ]);
+        let mut user = database(&[
+            ("L", "a", team("A", &["A"])),
+            ("L", "b", team("B Mine", &["B", "Bee"])),
+        ]);
+
+        let report = user.merge_upstream(Some(&base), &upstream);
+        assert_eq!(
+            user.find_team("L", "a").unwrap(),
+            team("A Fixed", &["A", "A FC"])
+        );
+        assert_eq!(
+            user.find_team("L", "b").unwrap(),
+            team("B Mine", &["B", "Bee", "B2"])
+        );
+        assert_eq!(report.updated.len(), 2);
+        assert_eq!(report.kept_user_edits, vec!["L / B Mine"]);
+    }
+
+    #[test]
+    fn test_merge_respects_deletions_and_upstream_removals() {
+        let base = database(&[
+            ("L", "gone", team("Gone", &["Gone"])),
+            ("L", "deleted", team("Deleted", &["Deleted"])),
+            ("Old", "x", team("X", &["X"])),
+        ]);
+        let upstream = database(&[("L", "deleted", team("Deleted", &["Deleted", "Del"]))]);
+        let mut user = database(&[
+            ("L", "gone", team("Gone", &["Gone"])),
+            ("Old", "x", team("X", &["X"])),
+        ]);
+
+        let report = user.merge_upstream(Some(&base), &upstream);
+        assert!(!user.has_team("L", "deleted"));
+        assert!(!user.has_team("L", "gone"));
+        assert!(!user.has_league("Old"));
+        assert_eq!(report.removed.len(), 2);
+    }
+
+    #[test]
+    fn test_merge_without_base_only_adds() {
+        let upstream = database(&[("L", "a", team("A", &["A", "A FC"]))]);
+        let mut user = database(&[
+            ("L", "a", team("My A", &["A"])),
+            ("L", "c", team("C", &["C"])),
+        ]);
+
+        let report = user.merge_upstream(None, &upstream);
+        assert_eq!(
+            user.find_team("L", "a").unwrap(),
+            team("My A", &["A", "A FC"])
+        );
+        assert!(user.has_team("L", "c"));
+        assert!(report.removed.is_empty());
+    }
+
+    #[test]
+    fn test_merge_is_idempotent() {
+        let base = database(&[("L", "a", team("A", &["A"]))]);
+        let upstream = database(&[("L", "a", team("A", &["A", "A2"]))]);
+        let mut user = base.clone();
+        user.merge_upstream(Some(&base), &upstream);
+        let report = user.merge_upstream(Some(&upstream), &upstream);
+        assert!(report.is_empty());
+    }
+
     #[test]
     fn test_load_embedded() {
         let db = TeamDatabase::load_embedded();
@@ -234,10 +857,165 @@ mod tests {
             .any(|(_, _, t)| t.display_name.to_lowercase().contains("manchester")));
     }
 
+    #[test]
+    fn test_iter_teams_covers_all_leagues() {
+        let db = TeamDatabase::load_embedded().unwrap();
+        let total: usize = db
+            .get_leagues()
+            .iter()
+            .map(|league| db.get_teams(league).map_or(0, |teams| teams.len()))
+            .sum();
+        assert_eq!(db.iter_teams().count(), total);
+        assert!(db
+            .iter_teams()
+            .any(|(league, key, _)| league == "Premier League" && key == "manchester_united"));
+    }
+
     #[test]
     fn test_search_team_case_insensitive() {
         let db = TeamDatabase::load_embedded().unwrap();
         let results = db.search_team("LIVERPOOL");
         assert!(!results.is_empty());
     }
+
+    #[test]
+    fn test_normalize_variations() {
+        let variations = vec![
+            " Arsenal ".to_string(),
+            "".to_string(),
+            "ARSENAL".to_string(),
+            "Gunners".to_string(),
+        ];
+        assert_eq!(
+            normalize_variations(variations).unwrap(),
+            vec!["Arsenal", "Gunners"]
+        );
+        assert!(normalize_variations(vec!["  ".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_update_team_renames_key() {
+        let mut db = database(&[("EPL", "arsenal", team("Arsenal", &["Arsenal"]))]);
+
+        // Editing only the variations keeps the embedded key
+        let key = db
+            .update_team(
+                "EPL",
+                "arsenal",
+                "Arsenal",
+                vec!["Arsenal".to_string(), "Gunners".to_string()],
+            )
+            .unwrap();
+        assert_eq!(key, "arsenal");
+        assert_eq!(db.find_team("EPL", "arsenal").unwrap().variations.len(), 2);
+
+        let key = db
+            .update_team(
+                "EPL",
+                "arsenal",
+                "Arsenal FC",
+                vec!["Arsenal FC".to_string(), "Arsenal".to_string()],
+            )
+            .unwrap();
+
+        assert_eq!(key, "Arsenal_FC");
+        assert!(!db.has_team("EPL", "arsenal"));
+        let updated = db.find_team("EPL", &key).unwrap();
+        assert_eq!(updated.display_name, "Arsenal FC");
+        assert_eq!(updated.variations.len(), 2);
+    }
+
+    #[test]
+    fn test_update_team_validation() {
+        let mut db = database(&[
+            ("EPL", "Arsenal", team("Arsenal", &["Arsenal"])),
+            ("EPL", "Chelsea", team("Chelsea", &["Chelsea"])),
+        ]);
+
+        // Key collision with another team
+        assert!(db
+            .update_team("EPL", "Arsenal", "Chelsea", vec!["Chelsea".to_string()])
+            .is_err());
+        // Empty name and empty variations
+        assert!(db
+            .update_team("EPL", "Arsenal", "  ", vec!["Arsenal".to_string()])
+            .is_err());
+        assert!(db.update_team("EPL", "Arsenal", "Arsenal", vec![]).is_err());
+        // Unknown team
+        assert!(db
+            .update_team("EPL", "Spurs", "Spurs", vec!["Spurs".to_string()])
+            .is_err());
+
+        // Keeping the same key is allowed
+        db.update_team(
+            "EPL",
+            "Arsenal",
+            "Arsenal",
+            vec!["Arsenal".to_string(), "Gunners".to_string()],
+        )
+        .unwrap();
+        assert_eq!(db.find_team("EPL", "Arsenal").unwrap().variations.len(), 2);
+    }
+
+    #[test]
+    fn test_remove_team() {
+        let mut db = database(&[("EPL", "arsenal", team("Arsenal", &["Arsenal"]))]);
+        assert_eq!(
+            db.remove_team("EPL", "arsenal").unwrap().display_name,
+            "Arsenal"
+        );
+        assert!(!db.has_team("EPL", "arsenal"));
+        assert!(db.has_league("EPL"));
+        assert!(db.remove_team("EPL", "arsenal").is_err());
+    }
+
+    #[test]
+    fn test_move_team() {
+        let mut db = database(&[
+            ("EPL", "leeds", team("Leeds", &["Leeds"])),
+            (
+                "Championship",
+                "sunderland",
+                team("Sunderland", &["Sunderland"]),
+            ),
+        ]);
+
+        db.move_team("EPL", "leeds", "Championship").unwrap();
+        assert!(!db.has_team("EPL", "leeds"));
+        assert!(db.has_team("Championship", "leeds"));
+
+        assert!(db.move_team("Championship", "leeds", "Serie A").is_err());
+        assert!(db.move_team("EPL", "leeds", "Championship").is_err());
+    }
+
+    #[test]
+    fn test_rename_and_remove_league() {
+        let mut db = database(&[
+            ("EPL", "arsenal", team("Arsenal", &["Arsenal"])),
+            ("EPL", "chelsea", team("Chelsea", &["Chelsea"])),
+            ("La Liga", "sevilla", team("Sevilla", &["Sevilla"])),
+        ]);
+
+        assert!(db.rename_league("EPL", "La Liga").is_err());
+        assert!(db.rename_league("EPL", " ").is_err());
+        assert!(db.rename_league("EPL", "..").is_err());
+        assert!(db.rename_league("EPL", "EPL/Cup").is_err());
+        db.rename_league("EPL", "Premier League").unwrap();
+        assert!(!db.has_league("EPL"));
+        assert!(db.has_team("Premier League", "arsenal"));
+
+        assert_eq!(db.remove_league("Premier League").unwrap(), 2);
+        assert!(!db.has_league("Premier League"));
+        assert!(db.remove_league("Premier League").is_err());
+    }
+
+    #[test]
+    fn test_add_league_rejects_unsafe_names() {
+        let mut db = TeamDatabase::default();
+        assert!(db.add_league("..".to_string()).is_err());
+        assert!(db.add_league(".".to_string()).is_err());
+        assert!(db.add_league("a/b".to_string()).is_err());
+        db.add_league("Süper Lig".to_string()).unwrap();
+        assert!(db.has_league("Süper Lig"));
+    }
 }
@@ -1,4 +1,5 @@
-use std::time::{Duration, Instant};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 /// Debounce helper to prevent rapid repeated triggers
 pub struct Debouncer {
@@ -41,18 +42,52 @@ impl Debouncer {
     }
 
     /// Reset the debouncer
-    #[allow(dead_code)]
     pub fn reset(&mut self) {
         self.last_trigger = None;
     }
 }
 
+/// Format a point in time as a UTC timestamp usable in file and folder names
+///
+/// Example: `20241018-142233`
+pub fn file_timestamp(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let days = (secs / 86_400) as i64;
+    let rem = secs % 86_400;
+
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!(
+        "{:04}{:02}{:02}-{:02}{:02}{:02}",
+        year,
+        month,
+        day,
+        rem / 3_600,
+        (rem % 3_600) / 60,
+        rem % 60
+    )
+}
+
 /// Timing measurements for a single iteration
 #[derive(Debug, Clone, Copy)]
 pub struct IterationTiming {
     pub capture_us: f64,
     pub preprocess_us: f64,
     pub ocr_us: f64,
+    /// Goal rules and team matching on the recognised text
+    pub match_us: f64,
     pub audio_trigger_us: f64,
     pub total_us: f64,
 }
@@ -63,12 +98,12 @@ impl IterationTiming {
             capture_us: 0.0,
             preprocess_us: 0.0,
             ocr_us: 0.0,
+            match_us: 0.0,
             audio_trigger_us: 0.0,
             total_us: 0.0,
         }
     }
 
-    #[allow(dead_code)]
     pub fn total_ms(&self) -> f64 {
         self.total_us / 1000.0
     }
@@ -102,12 +137,10 @@ impl LatencyStats {
         self.timings.push(timing);
     }
 
-    #[allow(dead_code)]
     pub fn len(&self) -> usize {
         self.timings.len()
     }
 
-    #[allow(dead_code)]
     pub fn is_empty(&self) -> bool {
         self.timings.is_empty()
     }
@@ -123,100 +156,131 @@ impl LatencyStats {
     }
 
     /// Calculate statistics for a specific stage
-    fn stage_stats(&self, extract: impl Fn(&IterationTiming) -> f64) -> (f64, f64, f64, f64) {
+    fn stage_stats(&self, extract: impl Fn(&IterationTiming) -> f64) -> StageStats {
         if self.timings.is_empty() {
-            return (0.0, 0.0, 0.0, 0.0);
+            return StageStats::default();
         }
 
         let mut values: Vec<f64> = self.timings.iter().map(&extract).collect();
         values.sort_by(|a, b| a.partial_cmp(b).unwrap());
 
-        let mean = values.iter().sum::<f64>() / values.len() as f64;
-        let p50 = Self::percentile(&values, 50.0);
-        let p95 = Self::percentile(&values, 95.0);
-        let p99 = Self::percentile(&values, 99.0);
+        StageStats {
+            mean_us: values.iter().sum::<f64>() / values.len() as f64,
+            p50_us: Self::percentile(&values, 50.0),
+            p95_us: Self::percentile(&values, 95.0),
+            p99_us: Self::percentile(&values, 99.0),
+        }
+    }
 
-        (mean, p50, p95, p99)
+    /// Statistics for every stage
+    pub fn report(&self) -> LatencyReport {
+        LatencyReport {
+            iterations: self.timings.len(),
+            capture: self.stage_stats(|t| t.capture_us),
+            preprocess: self.stage_stats(|t| t.preprocess_us),
+            ocr: self.stage_stats(|t| t.ocr_us),
+            matching: self.stage_stats(|t| t.match_us),
+            audio_trigger: self.stage_stats(|t| t.audio_trigger_us),
+            total: self.stage_stats(|t| t.total_us),
+        }
     }
 
-    /// Print comprehensive benchmark report
-    pub fn print_report(&self) {
+    /// Lines of the benchmark report, for printing to the console
+    pub fn report_lines(&self) -> Vec<String> {
         if self.timings.is_empty() {
-            tracing::info!("No timing data collected");
-            return;
+            return vec!["No timing data collected".to_string()];
         }
 
-        tracing::info!("\n╔═══════════════════════════════════════════════════════════════╗");
-        tracing::info!("║           FM Goal Musics - Latency Benchmark Report          ║");
-        tracing::info!("╚═══════════════════════════════════════════════════════════════╝");
-        tracing::info!("\nSample Size: {} iterations\n", self.timings.len());
-
-        // Calculate stats for each stage
-        let capture_stats = self.stage_stats(|t| t.capture_us);
-        let preprocess_stats = self.stage_stats(|t| t.preprocess_us);
-        let ocr_stats = self.stage_stats(|t| t.ocr_us);
-        let audio_stats = self.stage_stats(|t| t.audio_trigger_us);
-        let total_stats = self.stage_stats(|t| t.total_us);
-
-        // Print table header
-        tracing::info!("┌─────────────────┬──────────┬──────────┬──────────┬──────────┐");
-        tracing::info!("│ Stage           │   Mean   │   p50    │   p95    │   p99    │");
-        tracing::info!("├─────────────────┼──────────┼──────────┼──────────┼──────────┤");
-
-        // Print each stage (in microseconds)
-        Self::print_row("Capture", capture_stats);
-        Self::print_row("Preprocess", preprocess_stats);
-        Self::print_row("OCR", ocr_stats);
-        Self::print_row("Audio Trigger", audio_stats);
-        tracing::info!("├─────────────────┼──────────┼──────────┼──────────┼──────────┤");
-        Self::print_row("TOTAL", total_stats);
-        tracing::info!("└─────────────────┴──────────┴──────────┴──────────┴──────────┘");
+        let report = self.report();
+        let mut lines = vec![
+            "╔═══════════════════════════════════════════════════════════════╗".to_string(),
+            "║           FM Goal Musics - Latency Benchmark Report          ║".to_string(),
+            "╚═══════════════════════════════════════════════════════════════╝".to_string(),
+            format!("\nSample Size: {} iterations\n", report.iterations),
+            "┌─────────────────┬──────────┬──────────┬──────────┬──────────┐".to_string(),
+            "│ Stage           │   Mean   │   p50    │   p95    │   p99    │".to_string(),
+            "├─────────────────┼──────────┼──────────┼──────────┼──────────┤".to_string(),
+        ];
+
+        // Each stage in microseconds
+        for (name, stats) in report.stages() {
+            lines.push(Self::format_row(name, stats));
+        }
+        lines.push("├─────────────────┼──────────┼──────────┼──────────┼──────────┤".to_string());
+        lines.push(Self::format_row("TOTAL", report.total));
+        lines.push("└─────────────────┴──────────┴──────────┴──────────┴──────────┘".to_string());
 
         // Convert to milliseconds for summary
-        let total_p95_ms = total_stats.2 / 1000.0;
-        let total_p99_ms = total_stats.3 / 1000.0;
+        let total_p95_ms = report.total.p95_us / 1000.0;
+        let total_p99_ms = report.total.p99_us / 1000.0;
 
-        tracing::info!("\n📊 Summary:");
-        tracing::info!("  • Total p95 latency: {:.2} ms", total_p95_ms);
-        tracing::info!("  • Total p99 latency: {:.2} ms", total_p99_ms);
+        lines.push("\n📊 Summary:".to_string());
+        lines.push(format!("  • Total p95 latency: {:.2} ms", total_p95_ms));
+        lines.push(format!("  • Total p99 latency: {:.2} ms", total_p99_ms));
 
         // Performance verdict
         if total_p95_ms < 100.0 {
-            tracing::info!("  ✅ Performance target MET (p95 < 100ms)");
+            lines.push("  ✅ Performance target MET (p95 < 100ms)".to_string());
         } else {
-            tracing::info!("  ❌ Performance target MISSED (p95 >= 100ms)");
-            tracing::info!("     Target: < 100ms, Actual: {:.2}ms", total_p95_ms);
+            lines.push("  ❌ Performance target MISSED (p95 >= 100ms)".to_string());
+            lines.push(format!(
+                "     Target: < 100ms, Actual: {:.2}ms",
+                total_p95_ms
+            ));
         }
 
         // Identify bottleneck
-        let stages = [
-            ("Capture", capture_stats.2),
-            ("Preprocess", preprocess_stats.2),
-            ("OCR", ocr_stats.2),
-            ("Audio Trigger", audio_stats.2),
-        ];
-
-        let bottleneck = stages
-            .iter()
-            .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+        let bottleneck = report
+            .stages()
+            .into_iter()
+            .max_by(|a, b| a.1.p95_us.partial_cmp(&b.1.p95_us).unwrap())
             .unwrap();
-        tracing::info!(
+        lines.push(format!(
             "\n🔍 Bottleneck: {} ({:.0} µs p95)",
-            bottleneck.0,
-            bottleneck.1
-        );
-        tracing::info!("");
+            bottleneck.0, bottleneck.1.p95_us
+        ));
+        lines
     }
 
-    fn print_row(name: &str, stats: (f64, f64, f64, f64)) {
-        tracing::info!(
+    fn format_row(name: &str, stats: StageStats) -> String {
+        format!(
             "│ {:<15} │ {:>6.0} µs │ {:>6.0} µs │ {:>6.0} µs │ {:>6.0} µs │",
-            name,
-            stats.0,
-            stats.1,
-            stats.2,
-            stats.3
-        );
+            name, stats.mean_us, stats.p50_us, stats.p95_us, stats.p99_us
+        )
+    }
+}
+
+/// Mean and percentiles of one stage, in microseconds
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct StageStats {
+    pub mean_us: f64,
+    pub p50_us: f64,
+    pub p95_us: f64,
+    pub p99_us: f64,
+}
+
+/// Result of a benchmark run; saved as JSON to compare builds
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LatencyReport {
+    pub iterations: usize,
+    pub capture: StageStats,
+    pub preprocess: StageStats,
+    pub ocr: StageStats,
+    pub matching: StageStats,
+    pub audio_trigger: StageStats,
+    pub total: StageStats,
+}
+
+impl LatencyReport {
+    /// Every stage except the total, in pipeline order
+    pub fn stages(&self) -> [(&'static str, StageStats); 5] {
+        [
+            ("Capture", self.capture),
+            ("Preprocess", self.preprocess),
+            ("OCR", self.ocr),
+            ("Match", self.matching),
+            ("Audio Trigger", self.audio_trigger),
+        ]
     }
 }
 
@@ -300,6 +364,7 @@ mod tests {
             capture_us: 10000.0,
             preprocess_us: 5000.0,
             ocr_us: 15000.0,
+            match_us: 0.0,
             audio_trigger_us: 100.0,
             total_us: 30100.0,
         };
@@ -310,6 +375,30 @@ mod tests {
         assert!(!stats.is_empty());
     }
 
+    #[test]
+    fn test_latency_report_percentiles() {
+        let mut stats = LatencyStats::with_capacity(100);
+        for ms in 1..=100 {
+            stats.add(IterationTiming {
+                ocr_us: ms as f64 * 1000.0,
+                total_us: ms as f64 * 1000.0 + 500.0,
+                ..IterationTiming::new()
+            });
+        }
+
+        let report = stats.report();
+        assert_eq!(report.iterations, 100);
+        assert_eq!(report.ocr.mean_us, 50_500.0);
+        assert_eq!(report.ocr.p50_us, 51_000.0);
+        assert_eq!(report.ocr.p95_us, 95_000.0);
+        assert_eq!(report.ocr.p99_us, 99_000.0);
+        assert_eq!(report.capture, StageStats::default());
+
+        let lines = stats.report_lines();
+        assert!(lines.iter().any(|line| line.contains("target MET")));
+        assert!(lines.iter().any(|line| line.contains("Bottleneck: OCR")));
+    }
+
     #[test]
     fn test_latency_stats_with_capacity() {
         let stats = LatencyStats::with_capacity(500);
@@ -318,6 +407,19 @@ mod tests {
         assert!(stats.is_empty());
     }
 
+    #[test]
+    fn test_file_timestamp() {
+        assert_eq!(file_timestamp(UNIX_EPOCH), "19700101-000000");
+        assert_eq!(
+            file_timestamp(UNIX_EPOCH + Duration::from_secs(1_729_261_353)),
+            "20241018-142233"
+        );
+        assert_eq!(
+            file_timestamp(UNIX_EPOCH + Duration::from_secs(951_782_400)),
+            "20000229-000000"
+        );
+    }
+
     #[test]
     fn test_debouncer_multiple_cycles() {
         let mut debouncer = Debouncer::new(50);
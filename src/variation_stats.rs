@@ -0,0 +1,210 @@
+/// How often each team name variation matched a goal banner
+///
+/// Counts live in `variation_stats.json` next to teams.json rather than in
+/// the team database itself, so embedded database merges don't mistake them
+/// for user edits. They tell which variations are actually used and which
+/// are stale and can be pruned.
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::app_paths;
+use crate::persistence;
+use crate::teams::Team;
+
+/// A team needs this many matches before its unused variations count as stale
+pub const STALE_MIN_TEAM_MATCHES: u32 = 5;
+
+/// Usage of a single variation
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct VariationUsage {
+    pub matches: u32,
+    /// Unix time of the last match in milliseconds
+    pub last_matched_ms: u128,
+}
+
+/// Match counts per team, keyed by "league/team_key", then by variation
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VariationStats {
+    teams: BTreeMap<String, BTreeMap<String, VariationUsage>>,
+}
+
+fn team_id(league: &str, team_key: &str) -> String {
+    format!("{league}/{team_key}")
+}
+
+impl VariationStats {
+    pub fn path() -> Result<PathBuf> {
+        Ok(app_paths::config_dir()?.join("variation_stats.json"))
+    }
+
+    /// Load stats from disk; a missing file gives empty stats
+    ///
+    /// Fails when the file can't be read or parsed, so that it is never
+    /// saved over and its match history lost
+    pub fn load() -> Result<Self> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("{} could not be read", path.display()))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+        persistence::write_atomic(&path, serde_json::to_string_pretty(self)?.as_bytes())?;
+        Ok(())
+    }
+
+    pub fn record_match(&mut self, league: &str, team_key: &str, variation: &str) {
+        let usage = self
+            .teams
+            .entry(team_id(league, team_key))
+            .or_default()
+            .entry(variation.to_string())
+            .or_default();
+        usage.matches += 1;
+        usage.last_matched_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis())
+            .unwrap_or(0);
+    }
+
+    /// Matches of a variation (0 if it never matched)
+    pub fn matches(&self, league: &str, team_key: &str, variation: &str) -> u32 {
+        self.teams
+            .get(&team_id(league, team_key))
+            .and_then(|variations| variations.get(variation))
+            .map(|usage| usage.matches)
+            .unwrap_or(0)
+    }
+
+    /// Variations that never matched although the team is matched regularly
+    ///
+    /// The display name is never reported, and neither is every variation
+    /// of a team, so pruning always leaves something to match.
+    pub fn stale_variations(&self, league: &str, team_key: &str, team: &Team) -> Vec<String> {
+        let total: u32 = team
+            .variations
+            .iter()
+            .map(|variation| self.matches(league, team_key, variation))
+            .sum();
+        if total < STALE_MIN_TEAM_MATCHES {
+            return Vec::new();
+        }
+
+        team.variations
+            .iter()
+            .filter(|variation| !variation.eq_ignore_ascii_case(&team.display_name))
+            .filter(|variation| self.matches(league, team_key, variation) == 0)
+            .cloned()
+            .collect()
+    }
+
+    /// Carry a team's counts over to its new league and/or key
+    pub fn rekey(&mut self, from: (&str, &str), to: (&str, &str)) {
+        if let Some(usage) = self.teams.remove(&team_id(from.0, from.1)) {
+            self.teams.insert(team_id(to.0, to.1), usage);
+        }
+    }
+
+    /// Carry the counts of every team in a league over to its new name
+    pub fn rename_league(&mut self, old_name: &str, new_name: &str) {
+        let prefix = format!("{old_name}/");
+        let moved: Vec<String> = self
+            .teams
+            .keys()
+            .filter(|id| id.starts_with(&prefix))
+            .cloned()
+            .collect();
+        for id in moved {
+            if let Some(usage) = self.teams.remove(&id) {
+                self.teams
+                    .insert(format!("{new_name}/{}", &id[prefix.len()..]), usage);
+            }
+        }
+    }
+
+    /// Drop the counts of a removed team
+    pub fn forget(&mut self, league: &str, team_key: &str) {
+        self.teams.remove(&team_id(league, team_key));
+    }
+
+    /// Drop the counts of every team in a removed league
+    pub fn forget_league(&mut self, league: &str) {
+        let prefix = format!("{league}/");
+        self.teams.retain(|id, _| !id.starts_with(&prefix));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn team() -> Team {
+        Team {
+            display_name: "Manchester United".to_string(),
+            variations: vec![
+                "Manchester United".to_string(),
+                "Man Utd".to_string(),
+                "MUFC".to_string(),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_record_and_count() {
+        let mut stats = VariationStats::default();
+        stats.record_match("EPL", "man_utd", "Man Utd");
+        stats.record_match("EPL", "man_utd", "Man Utd");
+        assert_eq!(stats.matches("EPL", "man_utd", "Man Utd"), 2);
+        assert_eq!(stats.matches("EPL", "man_utd", "MUFC"), 0);
+        assert_eq!(stats.matches("La Liga", "man_utd", "Man Utd"), 0);
+    }
+
+    #[test]
+    fn test_stale_variations_need_enough_matches() {
+        let mut stats = VariationStats::default();
+        for _ in 0..STALE_MIN_TEAM_MATCHES - 1 {
+            stats.record_match("EPL", "man_utd", "Man Utd");
+        }
+        assert!(stats.stale_variations("EPL", "man_utd", &team()).is_empty());
+
+        stats.record_match("EPL", "man_utd", "Man Utd");
+        // The display name is kept even though it never matched
+        assert_eq!(
+            stats.stale_variations("EPL", "man_utd", &team()),
+            vec!["MUFC"]
+        );
+    }
+
+    #[test]
+    fn test_rekey_and_rename_league() {
+        let mut stats = VariationStats::default();
+        stats.record_match("EPL", "man_utd", "Man Utd");
+        stats.record_match("EPL", "arsenal", "Arsenal");
+
+        stats.rekey(("EPL", "man_utd"), ("EPL", "Manchester_United"));
+        assert_eq!(stats.matches("EPL", "Manchester_United", "Man Utd"), 1);
+
+        stats.rename_league("EPL", "Premier League");
+        assert_eq!(stats.matches("Premier League", "arsenal", "Arsenal"), 1);
+        assert_eq!(stats.matches("EPL", "arsenal", "Arsenal"), 0);
+
+        stats.forget("Premier League", "arsenal");
+        assert_eq!(stats.matches("Premier League", "arsenal", "Arsenal"), 0);
+
+        stats.forget_league("Premier League");
+        assert_eq!(
+            stats.matches("Premier League", "Manchester_United", "Man Utd"),
+            0
+        );
+    }
+}
@@ -2,6 +2,7 @@
 ///
 /// Saves and loads wizard completion state.
 use super::state::WizardState;
+use crate::app_paths;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
@@ -38,7 +39,9 @@ impl WizardPersistence {
 
     /// Get config file path
     pub fn config_file_path() -> Option<PathBuf> {
-        dirs::config_dir().map(|dir| dir.join("FMGoalMusic").join("wizard.json"))
+        app_paths::config_dir()
+            .ok()
+            .map(|dir| dir.join("wizard.json"))
     }
 
     /// Save wizard state to disk